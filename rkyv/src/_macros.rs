@@ -20,3 +20,37 @@ macro_rules! match_pointer_width {
         $s64
     };
 }
+
+// Archived multibyte primitives have the same representation as their native
+// equivalents when they are aligned and have the same endianness as the target.
+#[cfg(all(
+    not(feature = "unaligned"),
+    any(
+        all(feature = "big_endian", target_endian = "big"),
+        all(not(feature = "big_endian"), target_endian = "little"),
+    ),
+))]
+macro_rules! match_native_format {
+    (
+        native: { $($native:tt)* },
+        fallback: { $($fallback:tt)* } $(,)?
+    ) => {
+        $($native)*
+    };
+}
+
+#[cfg(not(all(
+    not(feature = "unaligned"),
+    any(
+        all(feature = "big_endian", target_endian = "big"),
+        all(not(feature = "big_endian"), target_endian = "little"),
+    ),
+)))]
+macro_rules! match_native_format {
+    (
+        native: { $($native:tt)* },
+        fallback: { $($fallback:tt)* } $(,)?
+    ) => {
+        $($fallback)*
+    };
+}
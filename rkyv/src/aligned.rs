@@ -0,0 +1,172 @@
+//! An archived value with a minimum alignment.
+
+use core::{borrow::Borrow, fmt, ops::Deref};
+
+use munge::munge;
+
+use crate::{seal::Seal, Place, Portable};
+
+/// A power-of-two alignment which can be used with [`ArchivedAligned`].
+pub struct ConstAlign<const N: usize>;
+
+/// A type-level alignment.
+///
+/// This is implemented for [`ConstAlign<N>`] for every power of two `N` from
+/// 1 up to and including 2<sup>29</sup>, the largest alignment supported by
+/// Rust.
+pub trait Alignment {
+    /// A zero-sized type with the alignment.
+    type Archetype: Copy + Portable;
+}
+
+macro_rules! impl_alignment {
+    ($($archetype:ident: $align:literal),* $(,)?) => {
+        $(
+            #[doc = concat!(
+                "A zero-sized type with an alignment of ",
+                $align,
+                ".",
+            )]
+            #[derive(Clone, Copy, Debug, Portable)]
+            #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+            #[rkyv(crate)]
+            #[repr(C, align($align))]
+            pub struct $archetype;
+
+            impl Alignment for ConstAlign<$align> {
+                type Archetype = $archetype;
+            }
+        )*
+    };
+}
+
+impl_alignment! {
+    Align1: 1,
+    Align2: 2,
+    Align4: 4,
+    Align8: 8,
+    Align16: 16,
+    Align32: 32,
+    Align64: 64,
+    Align128: 128,
+    Align256: 256,
+    Align512: 512,
+    Align1024: 1024,
+    Align2048: 2048,
+    Align4096: 4096,
+    Align8192: 8192,
+    Align16384: 16384,
+    Align32768: 32768,
+    Align65536: 65536,
+    Align131072: 131072,
+    Align262144: 262144,
+    Align524288: 524288,
+    Align1048576: 1048576,
+    Align2097152: 2097152,
+    Align4194304: 4194304,
+    Align8388608: 8388608,
+    Align16777216: 16777216,
+    Align33554432: 33554432,
+    Align67108864: 67108864,
+    Align134217728: 134217728,
+    Align268435456: 268435456,
+    Align536870912: 536870912,
+}
+
+/// An archived value which is aligned to at least `N` bytes.
+///
+/// This can be selected for a field with the
+/// [`Aligned`](crate::with::Aligned) wrapper.
+#[derive(Portable)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedAligned<T, const N: usize>
+where
+    ConstAlign<N>: Alignment,
+{
+    _align: [<ConstAlign<N> as Alignment>::Archetype; 0],
+    value: T,
+}
+
+impl<T, const N: usize> ArchivedAligned<T, N>
+where
+    ConstAlign<N>: Alignment,
+{
+    /// Returns a reference to the aligned value.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a sealed mutable reference to the aligned value.
+    #[inline]
+    pub fn get_seal(this: Seal<'_, Self>) -> Seal<'_, T> {
+        munge!(let Self { value, .. } = this);
+        value
+    }
+
+    /// Returns a place for the aligned value of an `ArchivedAligned`.
+    #[inline]
+    pub fn place_value(out: Place<Self>) -> Place<T> {
+        munge!(let Self { value, .. } = out);
+        value
+    }
+}
+
+impl<T, const N: usize> AsRef<T> for ArchivedAligned<T, N>
+where
+    ConstAlign<N>: Alignment,
+{
+    fn as_ref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T, const N: usize> Borrow<T> for ArchivedAligned<T, N>
+where
+    ConstAlign<N>: Alignment,
+{
+    fn borrow(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArchivedAligned<T, N>
+where
+    ConstAlign<N>: Alignment,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<T, const N: usize> Deref for ArchivedAligned<T, N>
+where
+    ConstAlign<N>: Alignment,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<U> for ArchivedAligned<T, N>
+where
+    ConstAlign<N>: Alignment,
+{
+    fn eq(&self, other: &U) -> bool {
+        self.get().eq(other)
+    }
+}
+
+impl<T: PartialOrd<U>, U, const N: usize> PartialOrd<U>
+    for ArchivedAligned<T, N>
+where
+    ConstAlign<N>: Alignment,
+{
+    fn partial_cmp(&self, other: &U) -> Option<core::cmp::Ordering> {
+        self.get().partial_cmp(other)
+    }
+}
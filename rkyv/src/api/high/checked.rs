@@ -3,20 +3,28 @@
 //! These APIs have default writers, automatically manage allocators, and
 //! support shared pointers.
 
+use core::{error::Error, fmt};
+
 use bytecheck::CheckBytes;
-use rancor::{Source, Strategy};
+use rancor::{fail, Source, Strategy};
 
 use crate::{
     api::{
         access_pos_unchecked_mut, access_pos_with_context, access_with_context,
-        check_pos_with_context, deserialize_using, root_position,
+        check_pos_with_context, deserialize_using,
+        high::{patch_pos, HighSerializer},
+        root_first_position, root_position, ROOT_HEADER_SIZE,
     },
     de::pooling::Pool,
     seal::Seal,
+    ser::allocator::ArenaHandle,
+    util::AlignedVec,
     validation::{
-        archive::ArchiveValidator, shared::SharedValidator, Validator,
+        archive::{ArchiveValidator, UnorderedArchiveValidator},
+        shared::SharedValidator,
+        Validator,
     },
-    Archive, Deserialize, Portable,
+    Archive, Deserialize, Portable, Serialize,
 };
 
 /// A high-level validator.
@@ -29,6 +37,12 @@ fn validator(bytes: &[u8]) -> Validator<ArchiveValidator<'_>, SharedValidator> {
     Validator::new(ArchiveValidator::new(bytes), SharedValidator::new())
 }
 
+/// A high-level validator for archives which may have been patched.
+///
+/// This is part of the [high-level API](crate::api::high).
+pub type UnorderedValidator<'a, E> =
+    Strategy<Validator<UnorderedArchiveValidator<'a>, SharedValidator>, E>;
+
 /// Access a byte slice with a given root position.
 ///
 /// This is a safe alternative to [`access_pos_unchecked`] and is part of the
@@ -115,6 +129,59 @@ where
     access_with_context::<_, _, E>(bytes, &mut validator(bytes))
 }
 
+#[derive(Debug)]
+struct MissingRootHeader {
+    len: usize,
+}
+
+impl fmt::Display for MissingRootHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a root header requires {} bytes but the buffer only has {} bytes",
+            ROOT_HEADER_SIZE, self.len,
+        )
+    }
+}
+
+impl Error for MissingRootHeader {}
+
+/// Access a root-first byte slice.
+///
+/// The root position is read from the header at the start of the bytes. See
+/// [`serialize_root_first_using`] for details on the layout.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// [`serialize_root_first_using`]: crate::api::serialize_root_first_using
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::{access_root_first, to_bytes_root_first},
+///     rancor::Error,
+///     Archived,
+/// };
+///
+/// let value = vec![1, 2, 3, 4];
+/// let bytes = to_bytes_root_first::<Error>(&value).unwrap();
+/// let archived =
+///     access_root_first::<Archived<Vec<i32>>, Error>(&bytes).unwrap();
+///
+/// assert_eq!(archived.as_slice(), [1, 2, 3, 4]);
+/// ```
+pub fn access_root_first<T, E>(bytes: &[u8]) -> Result<&T, E>
+where
+    T: Portable + for<'a> CheckBytes<HighValidator<'a, E>>,
+    E: Source,
+{
+    let Some(pos) = root_first_position(bytes) else {
+        fail!(MissingRootHeader { len: bytes.len() });
+    };
+    access_pos(bytes, pos)
+}
+
 /// Mutably access a byte slice with a given root position.
 ///
 /// This is a safe alternative to [`access_pos_unchecked_mut`] and is part of
@@ -256,3 +323,98 @@ where
     let mut deserializer = Pool::default();
     deserialize_using(access::<T::Archived, E>(bytes)?, &mut deserializer)
 }
+
+/// Access a byte slice which may have been patched with a given root position.
+///
+/// Patching an archive with [`patch`] or [`patch_pos`] appends values after
+/// the root, which [`access_pos`] rejects. This validates the archive with an
+/// [`UnorderedArchiveValidator`] instead, which is slower but accepts subtrees
+/// in any order as long as they do not overlap.
+///
+/// This is part of the [high-level API](crate::api::high).
+pub fn access_pos_unordered<T, E>(bytes: &[u8], pos: usize) -> Result<&T, E>
+where
+    T: Portable + for<'a> CheckBytes<UnorderedValidator<'a, E>>,
+    E: Source,
+{
+    access_pos_with_context::<_, _, E>(
+        bytes,
+        pos,
+        &mut Validator::new(
+            UnorderedArchiveValidator::new(bytes),
+            SharedValidator::new(),
+        ),
+    )
+}
+
+/// Update a value at a path in an archive by appending a new archived value to
+/// the end of the bytes.
+///
+/// The root of the archive is validated at `root_pos` with
+/// [`access_pos_unordered`] and passed to `path`, which returns the archived
+/// value to replace. The new `value` is then patched over it with
+/// [`patch_pos`]. The root of the archive does not move, so `root_pos` can be
+/// used to access the archive again after patching.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::{
+///         high::{access_pos_unordered, patch},
+///         root_position,
+///     },
+///     rancor::Error,
+///     to_bytes, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: String,
+///     values: Vec<i32>,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     values: vec![3, 1, 4],
+/// };
+///
+/// let mut bytes = to_bytes::<Error>(&value).unwrap();
+/// let root_pos = root_position::<ArchivedExample>(bytes.len());
+///
+/// patch::<ArchivedExample, _, Error>(
+///     &mut bytes,
+///     root_pos,
+///     |root| &root.values,
+///     &vec![3, 1, 4, 1, 5, 9],
+/// )
+/// .unwrap();
+///
+/// let archived =
+///     access_pos_unordered::<ArchivedExample, Error>(&bytes, root_pos)
+///         .unwrap();
+/// assert_eq!(archived.name, "pi");
+/// assert_eq!(archived.values, [3, 1, 4, 1, 5, 9]);
+/// ```
+pub fn patch<R, T, E>(
+    bytes: &mut AlignedVec,
+    root_pos: usize,
+    path: impl FnOnce(&R) -> &T::Archived,
+    value: &T,
+) -> Result<(), E>
+where
+    R: Portable + for<'a> CheckBytes<UnorderedValidator<'a, E>>,
+    T: for<'a, 'b> Serialize<
+        HighSerializer<&'a mut AlignedVec, ArenaHandle<'b>, E>,
+    >,
+    E: Source,
+{
+    let root = access_pos_unordered::<R, E>(bytes, root_pos)?;
+    let target = path(root) as *const T::Archived as usize;
+    // Targets outside of the bytes wrap around to a position which is out of
+    // bounds and are rejected by `patch_pos`.
+    let pos = target.wrapping_sub(bytes.as_ptr() as usize);
+    patch_pos(bytes, pos, value)
+}
@@ -0,0 +1,251 @@
+use rancor::{Source, Strategy};
+
+use crate::{
+    api::{deserialize_using, serialize_using},
+    context::Contextual,
+    de::Pool,
+    ser::{allocator::ArenaHandle, sharing::Share, Serializer},
+    util::{with_arena, AlignedVec},
+    Deserialize, Serialize,
+};
+
+/// A high-level serializer with a user-provided context.
+///
+/// This is part of the [high-level API](crate::api::high).
+pub type HighSerializerWithContext<'c, W, A, C, E> =
+    Strategy<Contextual<'c, Serializer<W, A, Share>, C>, E>;
+
+/// A high-level deserializer with a user-provided context.
+///
+/// This is part of the [high-level API](crate::api::high).
+pub type HighDeserializerWithContext<'c, C, E> =
+    Strategy<Contextual<'c, Pool, C>, E>;
+
+/// Serialize a value to bytes with a user-provided context.
+///
+/// Serialization implementations can access the context by adding a
+/// [`Context`](crate::context::Context) bound to their serializer.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access,
+///     api::high::{deserialize_with_context, to_bytes_with_context},
+///     context::Context,
+///     rancor::{Error, Fallible},
+///     with::{ArchiveWith, DeserializeWith, SerializeWith},
+///     Archive, Archived, Deserialize, Place, Serialize,
+/// };
+///
+/// /// Archives strings as indices into a table of strings.
+/// struct Interned;
+///
+/// impl ArchiveWith<String> for Interned {
+///     type Archived = Archived<u32>;
+///     type Resolver = u32;
+///
+///     fn resolve_with(_: &String, index: u32, out: Place<Self::Archived>) {
+///         index.resolve((), out);
+///     }
+/// }
+///
+/// impl<S> SerializeWith<String, S> for Interned
+/// where
+///     S: Fallible + Context<Vec<String>> + ?Sized,
+/// {
+///     fn serialize_with(
+///         field: &String,
+///         serializer: &mut S,
+///     ) -> Result<u32, S::Error> {
+///         let table = serializer.context_mut();
+///         let index = match table.iter().position(|s| s == field) {
+///             Some(index) => index,
+///             None => {
+///                 table.push(field.clone());
+///                 table.len() - 1
+///             }
+///         };
+///         Ok(index as u32)
+///     }
+/// }
+///
+/// impl<D> DeserializeWith<Archived<u32>, String, D> for Interned
+/// where
+///     D: Fallible + Context<Vec<String>> + ?Sized,
+/// {
+///     fn deserialize_with(
+///         field: &Archived<u32>,
+///         deserializer: &mut D,
+///     ) -> Result<String, D::Error> {
+///         Ok(deserializer.context()[field.to_native() as usize].clone())
+///     }
+/// }
+///
+/// #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+/// struct Event {
+///     #[rkyv(with = Interned)]
+///     kind: String,
+///     value: i32,
+/// }
+///
+/// let events = vec![
+///     Event {
+///         kind: "click".to_string(),
+///         value: 1,
+///     },
+///     Event {
+///         kind: "scroll".to_string(),
+///         value: 2,
+///     },
+///     Event {
+///         kind: "click".to_string(),
+///         value: 3,
+///     },
+/// ];
+///
+/// let mut table = Vec::new();
+/// let bytes = to_bytes_with_context::<_, Error>(&events, &mut table).unwrap();
+/// assert_eq!(table, ["click", "scroll"]);
+///
+/// let archived = access::<Archived<Vec<Event>>, Error>(&bytes).unwrap();
+/// assert_eq!(archived[2].kind, 0);
+///
+/// let deserialized =
+///     deserialize_with_context::<Vec<Event>, _, Error>(archived, &mut table)
+///         .unwrap();
+/// assert_eq!(deserialized, events);
+/// ```
+pub fn to_bytes_with_context<'c, C, E>(
+    value: &impl for<'a> Serialize<
+        HighSerializerWithContext<'c, AlignedVec, ArenaHandle<'a>, C, E>,
+    >,
+    context: &'c mut C,
+) -> Result<AlignedVec, E>
+where
+    C: ?Sized,
+    E: Source,
+{
+    with_arena(|arena| {
+        let mut serializer = Contextual::new(
+            Serializer::new(AlignedVec::new(), arena.acquire(), Share::new()),
+            context,
+        );
+        serialize_using(value, &mut serializer)?;
+        Ok(serializer.into_inner().into_writer())
+    })
+}
+
+/// Deserialize a value with a user-provided context.
+///
+/// Deserialization implementations can access the context by adding a
+/// [`Context`](crate::context::Context) bound to their deserializer. See
+/// [`to_bytes_with_context`] for an example.
+///
+/// This is part of the [high-level API](crate::api::high).
+pub fn deserialize_with_context<'c, T, C, E>(
+    value: &impl Deserialize<T, HighDeserializerWithContext<'c, C, E>>,
+    context: &'c mut C,
+) -> Result<T, E>
+where
+    C: ?Sized,
+{
+    deserialize_using(value, &mut Contextual::new(Pool::new(), context))
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::{Fallible, Panic};
+
+    use crate::{
+        alloc::{
+            rc::Rc,
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
+        api::{
+            access_unchecked,
+            high::{deserialize_with_context, to_bytes_with_context},
+        },
+        context::Context,
+        with::{ArchiveWith, DeserializeWith, SerializeWith},
+        Archive, Archived, Deserialize, Place, Serialize,
+    };
+
+    /// Archives a value as its offset from a base value in the context.
+    struct Offset;
+
+    impl ArchiveWith<u32> for Offset {
+        type Archived = Archived<u32>;
+        type Resolver = u32;
+
+        fn resolve_with(_: &u32, offset: u32, out: Place<Self::Archived>) {
+            offset.resolve((), out);
+        }
+    }
+
+    impl<S> SerializeWith<u32, S> for Offset
+    where
+        S: Fallible + Context<u32> + ?Sized,
+    {
+        fn serialize_with(
+            field: &u32,
+            serializer: &mut S,
+        ) -> Result<u32, S::Error> {
+            Ok(field - serializer.context())
+        }
+    }
+
+    impl<D> DeserializeWith<Archived<u32>, u32, D> for Offset
+    where
+        D: Fallible + Context<u32> + ?Sized,
+    {
+        fn deserialize_with(
+            field: &Archived<u32>,
+            deserializer: &mut D,
+        ) -> Result<u32, D::Error> {
+            Ok(field.to_native() + deserializer.context())
+        }
+    }
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate)]
+    struct Sample {
+        #[rkyv(with = Offset)]
+        timestamp: u32,
+        label: Rc<String>,
+    }
+
+    #[test]
+    fn context_roundtrip() {
+        let label = Rc::new("a label which is shared".to_string());
+        let value = vec![
+            Sample {
+                timestamp: 1_000_005,
+                label: label.clone(),
+            },
+            Sample {
+                timestamp: 1_000_042,
+                label,
+            },
+        ];
+
+        let mut base = 1_000_000u32;
+        let bytes =
+            to_bytes_with_context::<_, Panic>(&value, &mut base).unwrap();
+        let archived =
+            unsafe { access_unchecked::<Archived<Vec<Sample>>>(&bytes) };
+        assert_eq!(archived[0].timestamp, 5);
+        assert_eq!(archived[1].timestamp, 42);
+
+        let deserialized = deserialize_with_context::<Vec<Sample>, _, Panic>(
+            archived, &mut base,
+        )
+        .unwrap();
+        assert_eq!(deserialized, value);
+        assert!(Rc::ptr_eq(&deserialized[0].label, &deserialized[1].label));
+    }
+}
@@ -5,20 +5,34 @@
 
 #[cfg(feature = "bytecheck")]
 mod checked;
+mod context;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod reusable;
 
-use rancor::Strategy;
+use core::{
+    error::Error,
+    fmt,
+    mem::{align_of, size_of},
+};
+
+use rancor::{fail, Source, Strategy};
 
 #[cfg(feature = "bytecheck")]
 pub use self::checked::*;
+#[cfg(feature = "rayon")]
+pub use self::parallel::*;
+pub use self::{context::*, reusable::*};
 use crate::{
     access_unchecked,
-    api::{deserialize_using, serialize_using},
+    api::{deserialize_using, serialize_root_first_using, serialize_using},
     de::Pool,
     ser::{
-        allocator::ArenaHandle, sharing::Share, Allocator, Serializer, Writer,
+        allocator::ArenaHandle, sharing::Share, writer::Counter, Allocator,
+        Positional as _, Serializer, WriteAt, Writer,
     },
     util::{with_arena, AlignedVec},
-    Archive, Deserialize, Serialize,
+    Archive, Deserialize, Place, Serialize,
 };
 
 /// A high-level serializer.
@@ -33,7 +47,11 @@ pub type HighDeserializer<E> = Strategy<Pool, E>;
 
 /// Serialize a value to bytes.
 ///
-/// Returns the serialized bytes in an [`AlignedVec`].
+/// Returns the serialized bytes in an [`AlignedVec`]. Like the standard library
+/// collections, the `AlignedVec` and arena abort the process if they fail to
+/// allocate memory. To return allocation failures as errors instead, wrap them
+/// in a [`TryAlloc`](crate::ser::TryAlloc) and serialize with
+/// [`to_bytes_in_with_alloc`].
 ///
 /// This is part of the [high-level API](crate::api::high).
 ///
@@ -113,6 +131,49 @@ where
     with_arena(|arena| to_bytes_in_with_alloc(value, writer, arena.acquire()))
 }
 
+/// Computes the exact number of bytes that serializing a value will produce.
+///
+/// This serializes the value into a [`Counter`], which tracks the position of
+/// the writer without storing any output. The result is the length of the
+/// bytes returned by [`to_bytes`] for the same value.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::serialized_size, rancor::Error, util::AlignedVec, Archive,
+///     Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: String,
+///     values: Vec<u32>,
+/// }
+///
+/// let value = Example {
+///     name: "a name which is too long to inline".to_string(),
+///     values: vec![1, 2, 3, 4],
+/// };
+///
+/// let size = serialized_size::<Error>(&value).unwrap();
+/// let mut bytes = AlignedVec::<16>::with_capacity(size);
+/// let bytes =
+///     rkyv::api::high::to_bytes_in::<_, Error>(&value, bytes).unwrap();
+/// assert_eq!(bytes.len(), size);
+/// assert_eq!(bytes.capacity(), size);
+/// ```
+pub fn serialized_size<E>(
+    value: &impl for<'a> Serialize<HighSerializer<Counter, ArenaHandle<'a>, E>>,
+) -> Result<usize, E>
+where
+    E: rancor::Source,
+{
+    Ok(to_bytes_in(value, Counter::new())?.pos())
+}
+
 /// Serialize a value using the given allocator.
 ///
 /// This is part of the [high-level API](crate::api::high).
@@ -211,6 +272,191 @@ where
     Ok(serializer.into_writer())
 }
 
+/// Serialize a value to bytes with the root position recorded at the start.
+///
+/// See [`serialize_root_first_using`] for details on the layout. The root can
+/// be located with [`root_first_position`], or accessed directly with
+/// [`access_root_first`].
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// [`root_first_position`]: crate::api::root_first_position
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::{access_root_first, to_bytes_root_first},
+///     rancor::Error,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     value: 31415926,
+/// };
+///
+/// let bytes = to_bytes_root_first::<Error>(&value).unwrap();
+/// let archived = access_root_first::<ArchivedExample, Error>(&bytes).unwrap();
+///
+/// assert_eq!(archived.name, "pi");
+/// assert_eq!(archived.value, 31415926);
+/// ```
+pub fn to_bytes_root_first<E>(
+    // rustfmt insists on inlining this parameter even though it exceeds the
+    // max line length
+    #[rustfmt::skip] value: &impl for<'a> Serialize<
+        HighSerializer<AlignedVec, ArenaHandle<'a>, E>,
+    >,
+) -> Result<AlignedVec, E>
+where
+    E: rancor::Source,
+{
+    to_bytes_in_root_first(value, AlignedVec::new())
+}
+
+/// Serialize a value with the root position recorded at the start, and write
+/// the bytes to the given writer.
+///
+/// See [`serialize_root_first_using`] for details on the layout.
+///
+/// This is part of the [high-level API](crate::api::high).
+pub fn to_bytes_in_root_first<W, E>(
+    value: &impl for<'a> Serialize<HighSerializer<W, ArenaHandle<'a>, E>>,
+    writer: W,
+) -> Result<W, E>
+where
+    W: WriteAt<E>,
+    E: rancor::Source,
+{
+    with_arena(|arena| {
+        let mut serializer =
+            Serializer::new(writer, arena.acquire(), Share::new());
+        serialize_root_first_using(value, &mut serializer)?;
+        Ok(serializer.into_writer())
+    })
+}
+
+#[derive(Debug)]
+struct InvalidPatchPosition {
+    pos: usize,
+    size: usize,
+    align: usize,
+    len: usize,
+}
+
+impl fmt::Display for InvalidPatchPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot patch a value of size {} and alignment {} at position {} \
+             in a buffer of {} bytes",
+            self.size, self.align, self.pos, self.len,
+        )
+    }
+}
+
+impl Error for InvalidPatchPosition {}
+
+/// Replace the archived value at the given position by appending a new
+/// archived value to the end of the bytes.
+///
+/// The dependencies of `value` (for example the bytes of a string or the
+/// elements of a vec) are serialized to the end of `bytes`, and then `value` is
+/// resolved over the existing archived value at `pos`. Only the archived value
+/// at `pos` is rewritten, so the root of the archive does not move and the old
+/// dependencies are left in place but unreachable. Serializing the archive
+/// again drops the unreachable bytes.
+///
+/// Because the root is no longer at the end of `bytes` after patching, patched
+/// archives must be accessed with the root position of the original archive.
+/// The appended values are also located after the values which point to them,
+/// so patched archives must be validated with
+/// [`access_pos_unordered`](crate::api::high::access_pos_unordered). See
+/// `patch` for a checked version which locates the value to patch from the
+/// root.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::{
+///         access_pos_unchecked,
+///         high::{patch_pos, to_bytes},
+///         root_position,
+///     },
+///     rancor::Error,
+///     Archived,
+/// };
+///
+/// let mut bytes = to_bytes::<Error>(&"hello".to_string()).unwrap();
+/// let root_pos = root_position::<Archived<String>>(bytes.len());
+///
+/// let value = "a string too long to fit where the old one was".to_string();
+/// patch_pos::<_, Error>(&mut bytes, root_pos, &value).unwrap();
+///
+/// let archived =
+///     unsafe { access_pos_unchecked::<Archived<String>>(&bytes, root_pos) };
+/// assert_eq!(archived, &value);
+/// ```
+pub fn patch_pos<T, E>(
+    bytes: &mut AlignedVec,
+    pos: usize,
+    value: &T,
+) -> Result<(), E>
+where
+    T: for<'a, 'b> Serialize<
+        HighSerializer<&'a mut AlignedVec, ArenaHandle<'b>, E>,
+    >,
+    E: Source,
+{
+    let size = size_of::<T::Archived>();
+    let align = align_of::<T::Archived>();
+    let len = bytes.len();
+    let in_bounds = pos.checked_add(size).is_some_and(|end| end <= len);
+    // Serializing `value` may reallocate `bytes`, so alignment is checked
+    // relative to the start of the buffer, which is always aligned to
+    // `AlignedVec::ALIGNMENT`.
+    let is_aligned = align <= AlignedVec::<16>::ALIGNMENT && pos % align == 0;
+    if !in_bounds || !is_aligned {
+        fail!(InvalidPatchPosition {
+            pos,
+            size,
+            align,
+            len,
+        });
+    }
+
+    let resolver = with_arena(|arena| {
+        let mut serializer =
+            Serializer::new(&mut *bytes, arena.acquire(), Share::new());
+        value.serialize(Strategy::wrap(&mut serializer))
+    })?;
+
+    // SAFETY: We checked that `pos` is in bounds, and serializing only appends
+    // to `bytes`. The start of `bytes` is aligned to `AlignedVec::ALIGNMENT`,
+    // which we checked is at least the alignment of `T::Archived`, so `pos` is
+    // properly aligned for `T::Archived` even if `bytes` was reallocated. All
+    // of the bytes in an `AlignedVec` are initialized.
+    let out = unsafe {
+        Place::new_unchecked(
+            pos,
+            bytes.as_mut_ptr().add(pos).cast::<T::Archived>(),
+        )
+    };
+    value.resolve(resolver, out);
+
+    Ok(())
+}
+
 /// Deserialize a value from the given bytes.
 ///
 /// This function does not check that the data is valid. Use [`from_bytes`] to
@@ -297,7 +543,7 @@ pub fn deserialize<T, E>(
 
 #[cfg(test)]
 mod tests {
-    use rancor::Panic;
+    use rancor::{Failure, Panic};
 
     use crate::{
         alloc::{string::ToString, vec::Vec},
@@ -310,4 +556,303 @@ mod tests {
         let bytes = to_bytes_in::<_, Panic>(&value, Vec::new()).unwrap();
         assert!(!bytes.is_empty());
     }
+
+    #[test]
+    fn serialized_size_and_hint() {
+        use crate::{
+            alloc::{boxed::Box, rc::Rc, string::String, vec},
+            api::{high::serialized_size, serialized_size_hint},
+            to_bytes, Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, size_hint)]
+        struct Node<T> {
+            label: String,
+            value: T,
+            children: Vec<(String, T)>,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, size_hint)]
+        enum Item {
+            Empty,
+            Pair(u8, Option<Box<[u64]>>),
+            Named {
+                node: Node<(u16, u128)>,
+                shared: Rc<String>,
+            },
+        }
+
+        let leaf = |label: &str| Node {
+            label: label.to_string(),
+            value: (1, 2),
+            children: Vec::new(),
+        };
+        let child = |label: &str| (label.to_string(), (3, 4));
+        let shared = Rc::new("a shared string which is long".to_string());
+        let items = vec![
+            Item::Empty,
+            Item::Pair(3, Some(Box::from([1u64, 2, 3].as_slice()))),
+            Item::Pair(4, None),
+            Item::Named {
+                node: Node {
+                    label: "a root label which is not inline".to_string(),
+                    value: (5, 6),
+                    children: vec![child("x"), child("a longer child label")],
+                },
+                shared: shared.clone(),
+            },
+            Item::Named {
+                node: leaf("y"),
+                shared,
+            },
+        ];
+
+        let bytes = to_bytes::<Panic>(&items).unwrap();
+        let size = serialized_size::<Panic>(&items).unwrap();
+        assert_eq!(size, bytes.len());
+        assert!(serialized_size_hint(&items) >= size);
+
+        for item in items.iter() {
+            let bytes = to_bytes::<Panic>(item).unwrap();
+            assert_eq!(serialized_size::<Panic>(item).unwrap(), bytes.len());
+            assert!(serialized_size_hint(item) >= bytes.len());
+        }
+    }
+
+    #[test]
+    fn oversized_grow_fails() {
+        use rancor::{Fallible, Source};
+
+        use crate::{
+            api::high::to_bytes_in_with_alloc,
+            ser::{TryAlloc, Writer},
+            util::{with_arena, AlignedVec},
+            Archive, Place, Serialize,
+        };
+
+        /// Pads the archive with more bytes than can ever be allocated.
+        struct Oversized;
+
+        impl Archive for Oversized {
+            type Archived = ();
+            type Resolver = ();
+
+            fn resolve(&self, _: Self::Resolver, _: Place<Self::Archived>) {}
+        }
+
+        impl<S> Serialize<S> for Oversized
+        where
+            S: Fallible + Writer + ?Sized,
+            S::Error: Source,
+        {
+            fn serialize(&self, serializer: &mut S) -> Result<(), S::Error> {
+                serializer.write_padding(isize::MAX as usize)
+            }
+        }
+
+        let result = with_arena(|arena| {
+            to_bytes_in_with_alloc::<_, _, Failure>(
+                &Oversized,
+                TryAlloc::new(AlignedVec::<16>::new()),
+                TryAlloc::new(arena.acquire()),
+            )
+        });
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn patch_archive() {
+        use crate::{
+            alloc::{string::String, vec},
+            api::{
+                high::{
+                    access_pos, access_pos_unordered, patch, patch_pos,
+                    to_bytes,
+                },
+                root_position,
+            },
+            util::AlignedVec,
+            Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Example {
+            name: String,
+            values: Vec<String>,
+            count: u32,
+        }
+
+        let value = Example {
+            name: "pi".to_string(),
+            values: vec!["a".to_string(), "b".to_string()],
+            count: 2,
+        };
+
+        let mut bytes = to_bytes::<Panic>(&value).unwrap();
+        let root_pos = root_position::<ArchivedExample>(bytes.len());
+        let len = bytes.len();
+
+        let name = "a name which is too long to be stored inline".to_string();
+        patch::<ArchivedExample, _, Panic>(
+            &mut bytes,
+            root_pos,
+            |root| &root.name,
+            &name,
+        )
+        .unwrap();
+        let values = (0..10).map(|i| i.to_string()).collect::<Vec<_>>();
+        patch::<ArchivedExample, _, Panic>(
+            &mut bytes,
+            root_pos,
+            |root| &root.values,
+            &values,
+        )
+        .unwrap();
+        patch::<ArchivedExample, _, Panic>(
+            &mut bytes,
+            root_pos,
+            |root| &root.count,
+            &10u32,
+        )
+        .unwrap();
+        assert!(bytes.len() > len);
+
+        // Patched values are located after the root
+        assert!(
+            access_pos::<ArchivedExample, Failure>(&bytes, root_pos).is_err()
+        );
+
+        let archived =
+            access_pos_unordered::<ArchivedExample, Panic>(&bytes, root_pos)
+                .unwrap();
+        assert_eq!(archived.name, name);
+        assert_eq!(archived.values, values);
+        assert_eq!(archived.count, 10);
+
+        // Values outside of the archive can't be patched
+        let len = bytes.len();
+        patch_pos::<u32, Failure>(&mut bytes, len, &1).unwrap_err();
+        patch_pos::<u32, Failure>(&mut bytes, len - 2, &1).unwrap_err();
+
+        // Values aligned more strictly than the buffer can't be patched, even
+        // at positions which happen to be aligned in the current allocation
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, align = 64)]
+        struct OverAligned {
+            value: u32,
+        }
+
+        let mut bytes = AlignedVec::<16>::new();
+        bytes.resize(128, 0);
+        let value = OverAligned { value: 1 };
+        patch_pos::<_, Failure>(&mut bytes, 0, &value).unwrap_err();
+        patch_pos::<_, Failure>(&mut bytes, 64, &value).unwrap_err();
+    }
+
+    #[cfg(all(feature = "bytecheck", feature = "std"))]
+    #[test]
+    fn access_unordered() {
+        use std::collections::{BTreeMap, HashMap};
+
+        use crate::{
+            alloc::{rc::Rc, string::String, vec},
+            api::{
+                high::{access_pos_unordered, to_bytes},
+                root_position,
+            },
+            Archived,
+        };
+
+        type Value = (
+            BTreeMap<String, Vec<String>>,
+            HashMap<u32, String>,
+            (Rc<String>, Rc<String>),
+        );
+
+        let shared = Rc::new("shared between two pointers".to_string());
+        let value: Value = (
+            (0..20)
+                .map(|i| (i.to_string(), vec![i.to_string(); i]))
+                .collect(),
+            (0..20).map(|i| (i, "x".repeat(i as usize))).collect(),
+            (shared.clone(), shared),
+        );
+
+        // Unpatched archives are also valid in any order
+        let bytes = to_bytes::<Panic>(&value).unwrap();
+        let root_pos = root_position::<Archived<Value>>(bytes.len());
+        let archived =
+            access_pos_unordered::<Archived<Value>, Panic>(&bytes, root_pos)
+                .unwrap();
+        assert_eq!(archived.0.len(), 20);
+        assert_eq!(archived.1.len(), 20);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn root_first_layout() {
+        use std::io::Cursor;
+
+        use crate::{
+            alloc::{string::String, vec},
+            api::{
+                access_pos_unchecked,
+                high::{to_bytes_in_root_first, to_bytes_root_first},
+                root_first_position, root_position, ROOT_HEADER_SIZE,
+            },
+            ser::{writer::IoWriter, WriteAt},
+            util::AlignedVec,
+            Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Example {
+            name: String,
+            values: Vec<String>,
+        }
+
+        let value = Example {
+            name: "a name which is too long to be stored inline".to_string(),
+            values: vec!["a".to_string(), "b".to_string()],
+        };
+
+        let bytes = to_bytes_root_first::<Panic>(&value).unwrap();
+        let pos = root_first_position(&bytes[..ROOT_HEADER_SIZE]).unwrap();
+        assert_eq!(pos, root_position::<ArchivedExample>(bytes.len()));
+        assert_eq!(root_first_position(&bytes[..ROOT_HEADER_SIZE - 1]), None);
+
+        let archived =
+            unsafe { access_pos_unchecked::<ArchivedExample>(&bytes, pos) };
+        assert_eq!(archived.name, value.name);
+        assert_eq!(archived.values, value.values);
+
+        #[cfg(feature = "bytecheck")]
+        {
+            use crate::api::high::access_root_first;
+
+            let archived =
+                access_root_first::<ArchivedExample, Panic>(&bytes).unwrap();
+            assert_eq!(archived.name, value.name);
+            assert!(access_root_first::<ArchivedExample, Failure>(&bytes[..2])
+                .is_err());
+        }
+
+        // Seekable streams produce the same bytes
+        let writer = IoWriter::new(Cursor::new(Vec::new()));
+        let writer =
+            to_bytes_in_root_first::<_, Panic>(&value, writer).unwrap();
+        assert_eq!(writer.into_inner().into_inner(), bytes.as_slice());
+
+        // Bytes which have not been written yet can't be overwritten
+        let mut bytes = AlignedVec::<16>::new();
+        bytes.extend_from_slice(&[1, 2, 3, 4]);
+        WriteAt::<Panic>::write_at(&mut bytes, 2, &[5, 6]).unwrap();
+        assert_eq!(bytes.as_slice(), [1, 2, 5, 6]);
+        WriteAt::<Failure>::write_at(&mut bytes, 3, &[7, 8]).unwrap_err();
+    }
 }
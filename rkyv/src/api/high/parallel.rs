@@ -0,0 +1,259 @@
+use core::mem::{align_of, size_of};
+
+use rancor::{Source, Strategy};
+use rayon::{current_num_threads, prelude::*};
+
+use crate::{
+    alloc::vec::Vec,
+    api::high::HighSerializer,
+    ser::{allocator::ArenaHandle, sharing::Share, Serializer},
+    util::{with_arena, AlignedVec},
+    vec::{ArchivedVec, VecResolver},
+    Place, Serialize,
+};
+
+/// The number of chunks to split a slice into for each thread.
+///
+/// Using more chunks than threads balances the work when some elements take
+/// longer to serialize than others.
+const CHUNKS_PER_THREAD: usize = 4;
+
+struct Chunk<R> {
+    bytes: AlignedVec,
+    resolvers: Vec<R>,
+}
+
+fn serialize_chunk<T, E>(values: &[T]) -> Result<Chunk<T::Resolver>, E>
+where
+    T: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, E>>,
+    E: Source,
+{
+    with_arena(|arena| {
+        let mut serializer =
+            Serializer::new(AlignedVec::new(), arena.acquire(), Share::new());
+        let resolvers = values
+            .iter()
+            .map(|value| value.serialize(Strategy::wrap(&mut serializer)))
+            .collect::<Result<Vec<_>, E>>()?;
+        Ok(Chunk {
+            bytes: serializer.into_writer(),
+            resolvers,
+        })
+    })
+}
+
+/// Serialize a slice to bytes, serializing its elements in parallel.
+///
+/// The slice is split into chunks, and the elements of each chunk are
+/// serialized into a separate buffer on the [`rayon`] thread pool. The buffers
+/// are then spliced together and the elements are resolved into a single
+/// archived vector. The bytes can be accessed as an `ArchivedVec` of the
+/// archived elements, exactly like the bytes returned by [`to_bytes`] for a
+/// `Vec` of the same elements.
+///
+/// Relative pointers never cross between chunks, so they remain valid after
+/// the chunks are spliced together. As a consequence, shared pointers are only
+/// deduplicated within each chunk. Values shared between elements in
+/// different chunks are serialized once per chunk, and will not be shared when
+/// deserialized.
+///
+/// Chunks are spliced at multiples of [`AlignedVec::ALIGNMENT`], so archived
+/// types must not require a greater alignment.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// [`to_bytes`]: crate::api::high::to_bytes
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_par, from_bytes, rancor::Error, Archive,
+///     Deserialize, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+/// struct Record {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// let records = (0..1000)
+///     .map(|id| Record {
+///         id,
+///         name: format!("record number {id}"),
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let bytes = to_bytes_par::<_, Error>(&records).unwrap();
+/// let deserialized = from_bytes::<Vec<Record>, Error>(&bytes).unwrap();
+///
+/// assert_eq!(deserialized, records);
+/// ```
+pub fn to_bytes_par<T, E>(values: &[T]) -> Result<AlignedVec, E>
+where
+    T: Sync + for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, E>>,
+    T::Resolver: Send,
+    E: Source + Send,
+{
+    let chunk_len = values
+        .len()
+        .div_ceil(current_num_threads() * CHUNKS_PER_THREAD)
+        .max(1);
+    let chunks = values
+        .par_chunks(chunk_len)
+        .map(serialize_chunk)
+        .collect::<Result<Vec<_>, E>>()?;
+
+    let elements_size = size_of::<T::Archived>() * values.len();
+    let capacity = chunks
+        .iter()
+        .map(|chunk| chunk.bytes.len() + AlignedVec::<16>::ALIGNMENT)
+        .sum::<usize>()
+        + elements_size
+        + size_of::<ArchivedVec<T::Archived>>()
+        + align_of::<T::Archived>()
+        + align_of::<ArchivedVec<T::Archived>>();
+    let mut bytes = AlignedVec::<16>::with_capacity(capacity);
+
+    let mut bases = Vec::with_capacity(chunks.len());
+    for chunk in chunks.iter() {
+        let base = bytes.len().next_multiple_of(AlignedVec::<16>::ALIGNMENT);
+        bytes.resize(base, 0);
+        bytes.extend_from_slice(&chunk.bytes);
+        bases.push(base);
+    }
+
+    let elements_pos = bytes.len().next_multiple_of(align_of::<T::Archived>());
+    bytes.resize(elements_pos + elements_size, 0);
+
+    let mut pos = elements_pos;
+    for ((values, chunk), base) in
+        values.chunks(chunk_len).zip(chunks).zip(bases)
+    {
+        for (value, resolver) in values.iter().zip(chunk.resolvers) {
+            // The resolvers of a chunk hold positions relative to the start of
+            // the chunk, so the element is resolved at a position relative to
+            // the start of the chunk as well.
+            let out = unsafe {
+                Place::new_unchecked(
+                    pos - base,
+                    bytes.as_mut_ptr().add(pos).cast::<T::Archived>(),
+                )
+            };
+            value.resolve(resolver, out);
+            pos += size_of::<T::Archived>();
+        }
+    }
+
+    let root_pos = bytes
+        .len()
+        .next_multiple_of(align_of::<ArchivedVec<T::Archived>>());
+    bytes.resize(root_pos + size_of::<ArchivedVec<T::Archived>>(), 0);
+    let out = unsafe {
+        Place::new_unchecked(
+            root_pos,
+            bytes
+                .as_mut_ptr()
+                .add(root_pos)
+                .cast::<ArchivedVec<T::Archived>>(),
+        )
+    };
+    ArchivedVec::resolve_from_len(
+        values.len(),
+        VecResolver::from_pos(elements_pos),
+        out,
+    );
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{
+        alloc::{
+            boxed::Box,
+            collections::BTreeMap,
+            string::{String, ToString},
+            sync::Arc,
+            vec,
+            vec::Vec,
+        },
+        api::{
+            access_unchecked,
+            high::{to_bytes, to_bytes_par},
+            test::deserialize,
+        },
+        vec::ArchivedVec,
+        Archive, Archived, Deserialize, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate, derive(Debug))]
+    struct Record {
+        id: u64,
+        name: String,
+        tags: Vec<String>,
+        scores: BTreeMap<String, u32>,
+        flag: Option<Box<u8>>,
+    }
+
+    fn records(len: u64) -> Vec<Record> {
+        (0..len)
+            .map(|id| Record {
+                id,
+                name: format!("a record with a long name #{id}"),
+                tags: (0..id % 5).map(|i| i.to_string()).collect(),
+                scores: (0..id % 3)
+                    .map(|i| (format!("score {i}"), i as u32))
+                    .collect(),
+                flag: (id % 2 == 0).then(|| Box::new(id as u8)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn parallel_matches_sequential() {
+        for len in [0, 1, 2, 7, 100, 1000] {
+            let value = records(len);
+            let bytes = to_bytes_par::<_, Panic>(&value).unwrap();
+
+            let archived = unsafe {
+                access_unchecked::<ArchivedVec<ArchivedRecord>>(&bytes)
+            };
+            assert_eq!(archived.len(), value.len());
+            for (archived, record) in archived.iter().zip(value.iter()) {
+                assert_eq!(archived.id, record.id);
+                assert_eq!(archived.name, record.name);
+                assert_eq!(archived.tags.len(), record.tags.len());
+            }
+            assert_eq!(deserialize::<Vec<Record>>(archived), value);
+
+            #[cfg(feature = "bytecheck")]
+            crate::access::<Archived<Vec<Record>>, rancor::Error>(&bytes)
+                .unwrap();
+        }
+
+        // Elements which serialize nothing out-of-line still splice correctly.
+        let value = (0..1000u32).collect::<Vec<_>>();
+        let bytes = to_bytes_par::<_, Panic>(&value).unwrap();
+        let sequential = to_bytes::<Panic>(&value).unwrap();
+        assert_eq!(bytes.as_slice(), sequential.as_slice());
+    }
+
+    #[test]
+    fn parallel_shared_pointers() {
+        let shared = Arc::new("shared between every element".to_string());
+        let value = vec![Arc::clone(&shared); 1000];
+
+        let bytes = to_bytes_par::<_, Panic>(&value).unwrap();
+        let archived =
+            unsafe { access_unchecked::<Archived<Vec<Arc<String>>>>(&bytes) };
+        assert!(archived.iter().all(|s| **s == *shared));
+
+        let deserialized = deserialize::<Vec<Arc<String>>>(archived);
+        assert_eq!(deserialized, value);
+    }
+}
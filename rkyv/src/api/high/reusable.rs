@@ -0,0 +1,159 @@
+use core::mem;
+
+use rancor::Source;
+
+use crate::{
+    api::{high::HighSerializer, serialize_using},
+    ser::{
+        allocator::{Arena, ArenaHandle},
+        sharing::Share,
+        Serializer,
+    },
+    util::AlignedVec,
+    Serialize,
+};
+
+/// A serializer which reuses its buffer, arena, and shared pointer state
+/// across serializations.
+///
+/// [`to_bytes`](crate::api::high::to_bytes) creates a new output buffer and
+/// shared pointer map for every call. `ReusableSerializer` keeps them between
+/// calls, so serializing many values of similar size stops allocating once the
+/// buffers have grown large enough.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::ReusableSerializer, rancor::Error, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Message {
+///     id: u32,
+///     body: String,
+/// }
+///
+/// let mut serializer = ReusableSerializer::new();
+/// for id in 0..10 {
+///     let message = Message {
+///         id,
+///         body: format!("message {id}"),
+///     };
+///     let bytes = serializer.serialize::<_, Error>(&message).unwrap();
+///     let archived = rkyv::access::<ArchivedMessage, Error>(bytes).unwrap();
+///     assert_eq!(archived.id, id);
+/// }
+/// ```
+#[derive(Default)]
+pub struct ReusableSerializer {
+    writer: AlignedVec,
+    arena: Arena,
+    sharing: Share,
+}
+
+impl ReusableSerializer {
+    /// Creates a new reusable serializer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new reusable serializer with at least the given capacity for
+    /// its output buffer.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            writer: AlignedVec::with_capacity(capacity),
+            arena: Arena::new(),
+            sharing: Share::new(),
+        }
+    }
+
+    /// Serializes a value and returns the serialized bytes.
+    ///
+    /// The returned bytes are overwritten by the next call to `serialize`.
+    pub fn serialize<T, E>(&mut self, value: &T) -> Result<&[u8], E>
+    where
+        T: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, E>>,
+        E: Source,
+    {
+        let mut writer = mem::take(&mut self.writer);
+        writer.clear();
+        self.sharing.clear();
+
+        let mut serializer = Serializer::new(
+            writer,
+            self.arena.acquire(),
+            mem::take(&mut self.sharing),
+        );
+        let result = serialize_using(value, &mut serializer);
+        let (writer, _, sharing) = serializer.into_raw_parts();
+        self.writer = writer;
+        self.sharing = sharing;
+
+        result?;
+        Ok(self.writer.as_slice())
+    }
+
+    /// Returns the capacity of the output buffer.
+    pub fn capacity(&self) -> usize {
+        self.writer.capacity()
+    }
+
+    /// Consumes the reusable serializer and returns the output buffer.
+    pub fn into_writer(self) -> AlignedVec {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{
+        alloc::{
+            rc::Rc,
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
+        api::{access_unchecked, high::ReusableSerializer},
+        Archive, Serialize,
+    };
+
+    #[test]
+    fn reuse_buffers() {
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Message {
+            id: u32,
+            tags: Vec<String>,
+            shared: Rc<u32>,
+        }
+
+        let mut serializer = ReusableSerializer::new();
+        let mut first = None;
+        for id in 0..100 {
+            let shared = Rc::new(id);
+            let message = Message {
+                id,
+                tags: vec!["a long tag which is not inlined".to_string(); 4],
+                shared,
+            };
+            let bytes = serializer.serialize::<_, Panic>(&message).unwrap();
+            let ptr = bytes.as_ptr();
+            let len = bytes.len();
+            let archived =
+                unsafe { access_unchecked::<ArchivedMessage>(bytes) };
+            assert_eq!(archived.id, id);
+            assert_eq!(archived.tags.len(), 4);
+            assert_eq!(*archived.shared.get(), id);
+
+            match first {
+                None => first = Some((ptr, len)),
+                Some(first) => assert_eq!(first, (ptr, len)),
+            }
+        }
+    }
+}
@@ -24,13 +24,19 @@ pub mod low;
 #[cfg(test)]
 pub mod test;
 
-use core::mem::size_of;
+use core::{error::Error, fmt, mem::size_of, slice};
 
-use rancor::Strategy;
+use rancor::{fail, Source, Strategy};
 
 #[cfg(feature = "bytecheck")]
 pub use self::checked::*;
-use crate::{seal::Seal, ser::Writer, Deserialize, Portable, SerializeUnsized};
+use crate::{
+    primitive::{ArchivedUsize, FixedUsize},
+    seal::Seal,
+    ser::{WriteAt, Writer, WriterExt as _},
+    traits::SizeHintUnsized,
+    Deserialize, Portable, SerializeUnsized,
+};
 
 #[cfg(debug_assertions)]
 fn sanity_check_buffer<T: Portable>(ptr: *const u8, pos: usize, size: usize) {
@@ -101,6 +107,49 @@ pub fn root_position<T: Portable>(size: usize) -> usize {
     size.saturating_sub(size_of::<T>())
 }
 
+/// The size of the header at the start of a root-first archive.
+///
+/// See [`serialize_root_first_using`] for more information.
+pub const ROOT_HEADER_SIZE: usize = size_of::<ArchivedUsize>();
+
+/// Return the position of the root of a root-first archive.
+///
+/// The position is read from the header of the archive, so only the first
+/// [`ROOT_HEADER_SIZE`] bytes are required. Returns `None` if `header` is too
+/// short to contain a header.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::{
+///         access_pos_unchecked, high::to_bytes_root_first,
+///         root_first_position, ROOT_HEADER_SIZE,
+///     },
+///     rancor::Error,
+///     Archived,
+/// };
+///
+/// let bytes =
+///     to_bytes_root_first::<Error>(&"hello world".to_string()).unwrap();
+///
+/// let pos = root_first_position(&bytes[..ROOT_HEADER_SIZE]).unwrap();
+/// let archived =
+///     unsafe { access_pos_unchecked::<Archived<String>>(&bytes, pos) };
+/// assert_eq!(archived, "hello world");
+/// ```
+pub fn root_first_position(header: &[u8]) -> Option<usize> {
+    if header.len() < ROOT_HEADER_SIZE {
+        return None;
+    }
+
+    // SAFETY: `header` is long enough to contain an `ArchivedUsize`, and every
+    // bit pattern is a valid `ArchivedUsize`.
+    let pos =
+        unsafe { header.as_ptr().cast::<ArchivedUsize>().read_unaligned() };
+    Some(pos.to_native() as usize)
+}
+
 /// Access a byte slice with a given root position.
 ///
 /// Most of the time, the root position should be calculated using the root type
@@ -378,6 +427,125 @@ where
     value.serialize_unsized(Strategy::wrap(serializer))
 }
 
+#[derive(Debug)]
+struct RootPositionOverflow {
+    pos: usize,
+}
+
+impl fmt::Display for RootPositionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "root position {} does not fit in a root header of {} bytes",
+            self.pos, ROOT_HEADER_SIZE,
+        )
+    }
+}
+
+impl Error for RootPositionOverflow {}
+
+/// Serialize a value using the given serializer, and record the position of
+/// the root in a header at the start of the output.
+///
+/// Archives normally place their root at the end of the output, so readers must
+/// have the whole archive before they can locate the root. This reserves a
+/// header of [`ROOT_HEADER_SIZE`] bytes at the current position of the
+/// serializer, serializes the value after it, and then overwrites the header
+/// with the position of the root. Readers can then locate the root from the
+/// first bytes of a stream or a ranged read with [`root_first_position`].
+///
+/// The rest of the archive has the same layout as an archive without a
+/// header, so it can be accessed and validated with the `access_pos` family of
+/// functions. Returns the position of the root.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use rkyv::{
+///     api::{
+///         high::access_pos, root_first_position, serialize_root_first_using,
+///     },
+///     rancor::Error,
+///     ser::{writer::IoWriter, Serializer},
+///     util::AlignedVec,
+///     Archived,
+/// };
+///
+/// let value = "a string which is too long to inline".to_string();
+///
+/// // Root-first archives can be written to any seekable stream.
+/// let writer = IoWriter::new(Cursor::new(Vec::new()));
+/// let mut serializer = Serializer::new(writer, (), ());
+/// let pos = serialize_root_first_using::<_, Error>(&value, &mut serializer)
+///     .unwrap();
+/// let stream = serializer.into_writer().into_inner().into_inner();
+/// assert_eq!(root_first_position(&stream), Some(pos));
+///
+/// let mut bytes = AlignedVec::<16>::new();
+/// bytes.extend_from_slice(&stream);
+/// let archived = access_pos::<Archived<String>, Error>(&bytes, pos).unwrap();
+/// assert_eq!(archived, &value);
+/// ```
+pub fn serialize_root_first_using<S, E>(
+    value: &impl SerializeUnsized<Strategy<S, E>>,
+    serializer: &mut S,
+) -> Result<usize, E>
+where
+    S: WriteAt<E> + ?Sized,
+    E: Source,
+{
+    let header_pos = serializer.align_for::<ArchivedUsize>()?;
+    serializer.write(&[0; ROOT_HEADER_SIZE])?;
+
+    let pos = serialize_using(value, serializer)?;
+    let Ok(native) = FixedUsize::try_from(pos) else {
+        fail!(RootPositionOverflow { pos });
+    };
+    let header = ArchivedUsize::from_native(native);
+    // SAFETY: `ArchivedUsize` has no padding bytes, so all of its bytes are
+    // initialized.
+    let header_bytes = unsafe {
+        slice::from_raw_parts(
+            (&header as *const ArchivedUsize).cast::<u8>(),
+            ROOT_HEADER_SIZE,
+        )
+    };
+    serializer.write_at(header_pos, header_bytes)?;
+
+    Ok(pos)
+}
+
+/// Returns an upper bound on the number of bytes that serializing a value will
+/// produce.
+///
+/// This is computed from [`SizeHint`](crate::traits::SizeHint) without
+/// serializing the value, and so is cheaper than computing the exact size with
+/// [`serialized_size`](high::serialized_size). It can be used to reserve
+/// buffer space or reject oversized values early.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{api::serialized_size_hint, rancor::Error, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[rkyv(size_hint)]
+/// enum Message {
+///     Ping,
+///     Text(String),
+///     Batch(Vec<Option<Box<u64>>>),
+/// }
+///
+/// let message = Message::Batch(vec![Some(Box::new(1)), None]);
+/// let bytes = rkyv::to_bytes::<Error>(&message).unwrap();
+/// assert!(serialized_size_hint(&message) >= bytes.len());
+/// ```
+pub fn serialized_size_hint(value: &(impl SizeHintUnsized + ?Sized)) -> usize {
+    value.size_hint_unsized()
+}
+
 /// Deserialize a value using the given deserializer.
 ///
 /// Most of the time, [`deserialize`](high::deserialize) is a more ergonomic way
@@ -1,13 +1,19 @@
-use core::{marker::PhantomData, ptr::addr_of_mut};
+use core::{
+    borrow::Borrow,
+    fmt,
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    ptr::addr_of_mut,
+};
 
 use crate::{
     alloc::vec::Vec,
     collections::btree_map::{
-        entries_to_height, ArchivedBTreeMap, InnerNode, LeafNode, Node,
+        child_raw, entries_to_height, ArchivedBTreeMap, LeafNode, Node,
         NodeKind,
     },
     seal::Seal,
-    RelPtr,
 };
 
 impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
@@ -20,6 +26,31 @@ impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
         }
     }
 
+    /// Gets an iterator over the entries of the map whose keys are in the
+    /// given range, sorted by key.
+    ///
+    /// If the start of the range is greater than the end of the range, the
+    /// returned iterator is empty.
+    ///
+    /// Archived ranges like [`ArchivedRange`](crate::ops::ArchivedRange) can
+    /// be passed by reference. A pair of
+    /// [`ArchivedBound`](crate::ops::ArchivedBound)s can be passed as a tuple
+    /// of their [`as_ref`](crate::ops::ArchivedBound::as_ref)s.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V, E>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+        R: RangeBounds<Q>,
+    {
+        let start = self.start_index(range.start_bound());
+        let end = self.end_index(range.end_bound());
+        let this = (self as *const Self).cast_mut();
+        Iter {
+            inner: unsafe { RawIter::new_range(this, start, end) },
+            _phantom: PhantomData,
+        }
+    }
+
     /// Gets a mutable iterator over the entires of the map, sorted by key.
     pub fn iter_seal(this: Seal<'_, Self>) -> IterSeal<'_, K, V, E> {
         let this = unsafe { Seal::unseal_unchecked(this) as *mut Self };
@@ -55,12 +86,71 @@ impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
             _phantom: PhantomData,
         }
     }
+
+    /// Returns a cursor pointing at the gap before the first key which is
+    /// above the given bound.
+    ///
+    /// - `Bound::Included(x)` points at the gap before the first key greater
+    ///   than or equal to `x`.
+    /// - `Bound::Excluded(x)` points at the gap before the first key greater
+    ///   than `x`.
+    /// - `Bound::Unbounded` points at the gap before the first key.
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V, E>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        Cursor::new(self, self.start_index(bound))
+    }
+
+    /// Returns a cursor pointing at the gap after the last key which is below
+    /// the given bound.
+    ///
+    /// - `Bound::Included(x)` points at the gap after the last key less than or
+    ///   equal to `x`.
+    /// - `Bound::Excluded(x)` points at the gap after the last key less than
+    ///   `x`.
+    /// - `Bound::Unbounded` points at the gap after the last key.
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V, E>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        Cursor::new(self, self.end_index(bound))
+    }
+
+    fn start_index<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let this = (self as *const Self).cast_mut();
+        match bound {
+            Bound::Included(key) => Self::rank_raw(this, key, false),
+            Bound::Excluded(key) => Self::rank_raw(this, key, true),
+            Bound::Unbounded => 0,
+        }
+    }
+
+    fn end_index<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let this = (self as *const Self).cast_mut();
+        match bound {
+            Bound::Included(key) => Self::rank_raw(this, key, true),
+            Bound::Excluded(key) => Self::rank_raw(this, key, false),
+            Bound::Unbounded => self.len(),
+        }
+    }
 }
 
 /// An iterator over the entires of an `ArchivedBTreeMap`.
 ///
-/// This struct is created by the [`iter`](ArchivedBTreeMap::iter) method on
-/// [`ArchivedBTreeMap`]. See its documentation for more.
+/// This struct is created by the [`iter`](ArchivedBTreeMap::iter) and
+/// [`range`](ArchivedBTreeMap::range) methods on [`ArchivedBTreeMap`]. See
+/// their documentation for more.
 pub struct Iter<'a, K, V, const E: usize> {
     inner: RawIter<K, V, E>,
     _phantom: PhantomData<&'a ArchivedBTreeMap<K, V, E>>,
@@ -74,8 +164,24 @@ impl<'a, K, V, const E: usize> Iterator for Iter<'a, K, V, E> {
             .next()
             .map(|(k, v)| (unsafe { &*k }, unsafe { &*v }))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, const E: usize> DoubleEndedIterator for Iter<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(k, v)| (unsafe { &*k }, unsafe { &*v }))
+    }
 }
 
+impl<K, V, const E: usize> ExactSizeIterator for Iter<'_, K, V, E> {}
+
+impl<K, V, const E: usize> FusedIterator for Iter<'_, K, V, E> {}
+
 /// An iterator over the entires of an `ArchivedBTreeMap`.
 ///
 /// This struct is created by the [`iter_pin`](ArchivedBTreeMap::iter_pin)
@@ -93,8 +199,24 @@ impl<'a, K, V, const E: usize> Iterator for IterSeal<'a, K, V, E> {
             .next()
             .map(|(k, v)| (unsafe { &*k }, Seal::new(unsafe { &mut *v })))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, const E: usize> DoubleEndedIterator for IterSeal<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(k, v)| (unsafe { &*k }, Seal::new(unsafe { &mut *v })))
+    }
 }
 
+impl<K, V, const E: usize> ExactSizeIterator for IterSeal<'_, K, V, E> {}
+
+impl<K, V, const E: usize> FusedIterator for IterSeal<'_, K, V, E> {}
+
 /// An iterator over the keys of an `ArchivedBTreeMap`.
 ///
 /// This struct is created by the [`keys`](ArchivedBTreeMap::keys) method on
@@ -110,8 +232,22 @@ impl<'a, K, V, const E: usize> Iterator for Keys<'a, K, V, E> {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next().map(|(k, _)| unsafe { &*k })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
 }
 
+impl<K, V, const E: usize> DoubleEndedIterator for Keys<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, _)| unsafe { &*k })
+    }
+}
+
+impl<K, V, const E: usize> ExactSizeIterator for Keys<'_, K, V, E> {}
+
+impl<K, V, const E: usize> FusedIterator for Keys<'_, K, V, E> {}
+
 /// An iterator over the values of an `ArchivedBTreeMap`.
 ///
 /// This struct is created by the [`values`](ArchivedBTreeMap::keys) method on
@@ -127,8 +263,22 @@ impl<'a, K, V, const E: usize> Iterator for Values<'a, K, V, E> {
     fn next(&mut self) -> Option<Self::Item> {
         self.inner.next().map(|(_, v)| unsafe { &*v })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, const E: usize> DoubleEndedIterator for Values<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, v)| unsafe { &*v })
+    }
 }
 
+impl<K, V, const E: usize> ExactSizeIterator for Values<'_, K, V, E> {}
+
+impl<K, V, const E: usize> FusedIterator for Values<'_, K, V, E> {}
+
 /// A mutable iterator over the values of an `ArchivedBTreeMap`.
 ///
 /// This struct is created by the [`values_pin`](ArchivedBTreeMap::keys) method
@@ -146,37 +296,172 @@ impl<'a, K, V, const E: usize> Iterator for ValuesSeal<'a, K, V, E> {
             .next()
             .map(|(_, v)| Seal::new(unsafe { &mut *v }))
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V, const E: usize> DoubleEndedIterator for ValuesSeal<'_, K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(_, v)| Seal::new(unsafe { &mut *v }))
+    }
+}
+
+impl<K, V, const E: usize> ExactSizeIterator for ValuesSeal<'_, K, V, E> {}
+
+impl<K, V, const E: usize> FusedIterator for ValuesSeal<'_, K, V, E> {}
+
+/// A cursor over the entries of an [`ArchivedBTreeMap`].
+///
+/// Cursors point at the gaps between entries, including the gaps before the
+/// first entry and after the last entry. They are created by the
+/// [`lower_bound`](ArchivedBTreeMap::lower_bound) and
+/// [`upper_bound`](ArchivedBTreeMap::upper_bound) methods on
+/// [`ArchivedBTreeMap`]. Creating a cursor takes `O(log n)` time, and moving it
+/// takes amortized `O(1)` time.
+pub struct Cursor<'a, K, V, const E: usize> {
+    map: &'a ArchivedBTreeMap<K, V, E>,
+    index: usize,
+    // The path to the entry after the cursor. This is empty if the cursor is
+    // after the last entry.
+    next: Path<K, V, E>,
+}
+
+impl<K, V, const E: usize> Clone for Cursor<'_, K, V, E> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map,
+            index: self.index,
+            next: self.next.clone(),
+        }
+    }
 }
 
+impl<'a, K, V, const E: usize> Cursor<'a, K, V, E> {
+    fn new(map: &'a ArchivedBTreeMap<K, V, E>, index: usize) -> Self {
+        let mut next = Vec::new();
+        unsafe {
+            seek_path(
+                (map as *const ArchivedBTreeMap<K, V, E>).cast_mut(),
+                index,
+                &mut next,
+            );
+        }
+        Self { map, index, next }
+    }
+
+    /// Returns the number of entries before the cursor.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the entry after the cursor without moving it, or `None` if the
+    /// cursor is after the last entry.
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let &(node, i) = self.next.last()?;
+        let (k, v) = unsafe { entry(node, i) };
+        Some((unsafe { &*k }, unsafe { &*v }))
+    }
+
+    /// Returns the entry before the cursor without moving it, or `None` if the
+    /// cursor is before the first entry.
+    ///
+    /// This takes `O(log n)` time.
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        self.map.nth(self.index.checked_sub(1)?)
+    }
+
+    /// Moves the cursor past the next entry and returns it, or returns `None`
+    /// without moving if the cursor is after the last entry.
+    pub fn move_next(&mut self) -> Option<(&'a K, &'a V)> {
+        let result = self.peek_next()?;
+        self.index += 1;
+        if !unsafe { step_forward(&mut self.next) } {
+            self.next.clear();
+        }
+        Some(result)
+    }
+
+    /// Moves the cursor before the previous entry and returns it, or returns
+    /// `None` without moving if the cursor is before the first entry.
+    pub fn move_prev(&mut self) -> Option<(&'a K, &'a V)> {
+        let index = self.index.checked_sub(1)?;
+        let found = if self.next.is_empty() {
+            let map = (self.map as *const ArchivedBTreeMap<K, V, E>).cast_mut();
+            unsafe { seek_path(map, index, &mut self.next) }
+        } else {
+            unsafe { step_backward(&mut self.next) }
+        };
+        if !found {
+            self.next.clear();
+            return None;
+        }
+        self.index = index;
+        self.peek_next()
+    }
+}
+
+impl<K, V, const E: usize> fmt::Debug for Cursor<'_, K, V, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+// A path from the root of a B-tree to an entry. Each element is a node and an
+// index. For the last element, the index is the index of the current entry in
+// that node. For all other elements, the index is the index of the child which
+// the path descends into (with the greater node at `E`).
+type Path<K, V, const E: usize> = Vec<(*mut Node<K, V, E>, usize)>;
+
 struct RawIter<K, V, const E: usize> {
     remaining: usize,
-    stack: Vec<(*mut Node<K, V, E>, usize)>,
+    front: Path<K, V, E>,
+    back: Path<K, V, E>,
 }
 
 impl<K, V, const E: usize> RawIter<K, V, E> {
+    /// # Safety
+    ///
+    /// `map` must point to a valid `ArchivedBTreeMap`.
     unsafe fn new(map: *mut ArchivedBTreeMap<K, V, E>) -> Self {
-        let remaining = unsafe { (*map).len.to_native() as usize };
-        let mut stack = Vec::new();
+        let len = unsafe { (*map).len.to_native() as usize };
+        unsafe { Self::new_range(map, 0, len) }
+    }
+
+    /// # Safety
+    ///
+    /// `map` must point to a valid `ArchivedBTreeMap`.
+    unsafe fn new_range(
+        map: *mut ArchivedBTreeMap<K, V, E>,
+        start: usize,
+        end: usize,
+    ) -> Self {
+        let mut remaining = end.saturating_sub(start);
+        let mut front = Vec::new();
+        let mut back = Vec::new();
         if remaining != 0 {
-            stack.reserve(entries_to_height::<E>(remaining) as usize);
-            let mut current =
-                unsafe { RelPtr::as_ptr_raw(addr_of_mut!((*map).root)) };
-            loop {
-                stack.push((current, 0));
-                let kind = unsafe { (*current).kind };
-                match kind {
-                    NodeKind::Inner => {
-                        let inner = current.cast::<InnerNode<K, V, E>>();
-                        let lesser =
-                            unsafe { addr_of_mut!((*inner).lesser_nodes[0]) };
-                        current = unsafe { RelPtr::as_ptr_raw(lesser) };
-                    }
-                    NodeKind::Leaf => break,
-                }
+            let len = unsafe { (*map).len.to_native() as usize };
+            let height = entries_to_height::<E>(len) as usize;
+            front.reserve(height);
+            back.reserve(height);
+
+            let found_front = unsafe { seek_path(map, start, &mut front) };
+            let found_back = unsafe { seek_path(map, end - 1, &mut back) };
+            if !found_front || !found_back {
+                remaining = 0;
             }
         }
 
-        Self { remaining, stack }
+        Self {
+            remaining,
+            front,
+            back,
+        }
     }
 }
 
@@ -184,56 +469,190 @@ impl<K, V, const E: usize> Iterator for RawIter<K, V, E> {
     type Item = (*mut K, *mut V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (current, i) = self.stack.pop()?;
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (current, i) = *self.front.last()?;
+        self.remaining -= 1;
+        if self.remaining != 0 && !unsafe { step_forward(&mut self.front) } {
+            self.remaining = 0;
+        }
+
+        Some(unsafe { entry(current, i) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V, const E: usize> DoubleEndedIterator for RawIter<K, V, E> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (current, i) = *self.back.last()?;
         self.remaining -= 1;
+        if self.remaining != 0 && !unsafe { step_backward(&mut self.back) } {
+            self.remaining = 0;
+        }
+
+        Some(unsafe { entry(current, i) })
+    }
+}
 
-        let k = unsafe { addr_of_mut!((*current).keys[i]).cast::<K>() };
-        let v = unsafe { addr_of_mut!((*current).values[i]).cast::<V>() };
-        let next_i = i + 1;
-
-        // Advance to the next item
-        let kind = unsafe { (*current).kind };
-        match kind {
-            NodeKind::Inner => {
-                let inner = current.cast::<InnerNode<K, V, E>>();
-                if next_i < E {
-                    // More values in the current node
-                    self.stack.push((current, next_i));
-
-                    // Recurse to a lesser if valid
-                    let next_lesser =
-                        unsafe { addr_of_mut!((*inner).lesser_nodes[next_i]) };
-                    let next_lesser_is_invalid =
-                        unsafe { RelPtr::is_invalid_raw(next_lesser) };
-                    if !next_lesser_is_invalid {
-                        self.stack.push((
-                            unsafe { RelPtr::as_ptr_raw(next_lesser).cast() },
-                            0,
-                        ));
-                    }
-                } else {
-                    // Recurse to a greater if valid
-                    let next_greater =
-                        unsafe { addr_of_mut!((*inner).greater_node) };
-                    let next_greater_is_invalid =
-                        unsafe { RelPtr::is_invalid_raw(next_greater) };
-                    if !next_greater_is_invalid {
-                        self.stack.push((
-                            unsafe { RelPtr::as_ptr_raw(next_greater).cast() },
-                            0,
-                        ));
-                    }
-                }
+// Replaces `path` with the path to the `index`-th entry in key order. Returns
+// `false` and leaves the path empty if there is no such entry.
+unsafe fn seek_path<K, V, const E: usize>(
+    map: *mut ArchivedBTreeMap<K, V, E>,
+    index: usize,
+    path: &mut Path<K, V, E>,
+) -> bool {
+    path.clear();
+    match ArchivedBTreeMap::seek_raw(map, index, |n, i| path.push((n, i))) {
+        Some(entry) => {
+            path.push(entry);
+            true
+        }
+        None => {
+            path.clear();
+            false
+        }
+    }
+}
+
+// Returns pointers to the key and value of the `i`-th entry in a node.
+unsafe fn entry<K, V, const E: usize>(
+    node: *mut Node<K, V, E>,
+    i: usize,
+) -> (*mut K, *mut V) {
+    let k = unsafe { addr_of_mut!((*node).keys[i]).cast::<K>() };
+    let v = unsafe { addr_of_mut!((*node).values[i]).cast::<V>() };
+    (k, v)
+}
+
+// Returns the number of entries in a node, which is always `E` for inner
+// nodes.
+unsafe fn node_len<K, V, const E: usize>(node: *mut Node<K, V, E>) -> usize {
+    let kind = unsafe { (*node).kind };
+    match kind {
+        NodeKind::Inner => E,
+        NodeKind::Leaf => {
+            let leaf = node.cast::<LeafNode<K, V, E>>();
+            unsafe { (*leaf).len.to_native() as usize }
+        }
+    }
+}
+
+// Returns the child of a node at the given index, or `None` if the node is a
+// leaf or the child is invalid.
+unsafe fn child<K, V, const E: usize>(
+    node: *mut Node<K, V, E>,
+    i: usize,
+) -> Option<*mut Node<K, V, E>> {
+    let kind = unsafe { (*node).kind };
+    match kind {
+        NodeKind::Inner => unsafe { child_raw(node, i) },
+        NodeKind::Leaf => None,
+    }
+}
+
+// Extends the path to the first entry in the subtree rooted at `node`.
+unsafe fn descend_first<K, V, const E: usize>(
+    path: &mut Path<K, V, E>,
+    mut node: *mut Node<K, V, E>,
+) -> bool {
+    loop {
+        path.push((node, 0));
+        match unsafe { child(node, 0) } {
+            Some(lesser) => node = lesser,
+            None => return unsafe { node_len(node) } != 0,
+        }
+    }
+}
+
+// Extends the path to the last entry in the subtree rooted at `node`.
+unsafe fn descend_last<K, V, const E: usize>(
+    path: &mut Path<K, V, E>,
+    mut node: *mut Node<K, V, E>,
+) -> bool {
+    loop {
+        let len = unsafe { node_len(node) };
+        match unsafe { child(node, E) } {
+            Some(greater) => {
+                path.push((node, E));
+                node = greater;
             }
-            NodeKind::Leaf => {
-                let leaf = current.cast::<LeafNode<K, V, E>>();
-                let len = unsafe { (*leaf).len.to_native() as usize };
-                if next_i < len {
-                    self.stack.push((current, next_i));
-                }
+            None => {
+                path.push((node, len.wrapping_sub(1)));
+                return len != 0;
             }
         }
+    }
+}
+
+// Moves the path to the next entry in key order. Returns `false` if there are
+// no more entries.
+unsafe fn step_forward<K, V, const E: usize>(path: &mut Path<K, V, E>) -> bool {
+    let Some(&mut (current, ref mut i)) = path.last_mut() else {
+        return false;
+    };
 
-        Some((k, v))
+    // The next entry is either the first entry of the next child, or the next
+    // entry in the current node.
+    *i += 1;
+    if let Some(next) = unsafe { child(current, *i) } {
+        return unsafe { descend_first(path, next) };
     }
+    if *i < unsafe { node_len(current) } {
+        return true;
+    }
+
+    // Otherwise, we need to go back up the tree until we come up out of a
+    // lesser node. The entry after that lesser node is next.
+    path.pop();
+    while let Some(&mut (_, i)) = path.last_mut() {
+        if i < E {
+            return true;
+        }
+        path.pop();
+    }
+
+    false
+}
+
+// Moves the path to the previous entry in key order. Returns `false` if there
+// are no more entries.
+unsafe fn step_backward<K, V, const E: usize>(
+    path: &mut Path<K, V, E>,
+) -> bool {
+    let Some(&mut (current, ref mut i)) = path.last_mut() else {
+        return false;
+    };
+
+    // The previous entry is either the last entry of the lesser child, or the
+    // previous entry in the current node.
+    if let Some(lesser) = unsafe { child(current, *i) } {
+        return unsafe { descend_last(path, lesser) };
+    }
+    if *i > 0 {
+        *i -= 1;
+        return true;
+    }
+
+    // Otherwise, we need to go back up the tree until we come up out of a
+    // node which is not the first child. The entry before that node is
+    // previous.
+    path.pop();
+    while let Some((_, i)) = path.last_mut() {
+        if *i > 0 {
+            *i -= 1;
+            return true;
+        }
+        path.pop();
+    }
+
+    false
 }
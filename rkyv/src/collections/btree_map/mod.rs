@@ -28,6 +28,9 @@ use crate::{
 #[cfg(feature = "alloc")]
 mod iter;
 
+#[cfg(feature = "alloc")]
+pub use self::iter::{Cursor, Iter, IterSeal, Keys, Values, ValuesSeal};
+
 // B-trees are typically characterized as having a branching factor of B.
 // However, in this implementation our B-trees are characterized as having a
 // number of entries per node E where E = B - 1. This is done because it's
@@ -108,6 +111,48 @@ const fn ll_entries<const E: usize>(height: u32, n: usize) -> usize {
     n - entries_in_full_tree::<E>(height - 1)
 }
 
+// Because our B-trees are always built the same way, their shape is fully
+// determined by the number of entries in them. Every level except the last is
+// full, and the entries in the last level are packed into its leftmost leaves.
+// That lets us calculate the number of entries in any subtree from just its
+// position in the tree, which we use for order-statistic queries and to seek
+// iterators. Validation checks that archived B-trees have this shape.
+
+#[derive(Clone, Copy)]
+struct Shape<const E: usize> {
+    height: u32,
+    ll_entries: usize,
+}
+
+impl<const E: usize> Shape<E> {
+    fn new(len: usize) -> Self {
+        let height = entries_to_height::<E>(len);
+        Self {
+            height,
+            ll_entries: ll_entries::<E>(height, len),
+        }
+    }
+
+    // Returns the number of entries in the subtree rooted at the `index`-th
+    // node (from the left) of the level at `depth`.
+    fn subtree_len(&self, depth: u32, index: usize) -> usize {
+        // The subtree has `levels` full levels above the last level of the
+        // B-tree, and a number of leaves in the last level equal to the number
+        // of nodes in the next level.
+        let levels = (self.height - 1).saturating_sub(depth);
+        let leaves = (E + 1).saturating_pow(levels);
+        let ll_capacity = leaves.saturating_mul(E);
+        let ll_before = index.saturating_mul(ll_capacity);
+        let ll_entries = self.ll_entries.saturating_sub(ll_before);
+
+        (leaves - 1) + ll_entries.min(ll_capacity)
+    }
+}
+
+const fn child_index<const E: usize>(index: usize, child: usize) -> usize {
+    index.saturating_mul(E + 1).saturating_add(child)
+}
+
 #[derive(Clone, Copy, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 #[rkyv(crate)]
@@ -148,7 +193,38 @@ struct InnerNode<K, V, const E: usize> {
     greater_node: RelPtr<Node<K, V, E>>,
 }
 
+// Returns the `i`-th child of an inner node, where the child at `E` is the
+// greater node. Returns `None` if the child is invalid.
+//
+// # Safety
+//
+// `node` must point to a valid inner node and `i` must be at most `E`.
+unsafe fn child_raw<K, V, const E: usize>(
+    node: *mut Node<K, V, E>,
+    i: usize,
+) -> Option<*mut Node<K, V, E>> {
+    let inner = node.cast::<InnerNode<K, V, E>>();
+    let child = if i < E {
+        unsafe { addr_of_mut!((*inner).lesser_nodes[i]) }
+    } else {
+        unsafe { addr_of_mut!((*inner).greater_node) }
+    };
+    if unsafe { RelPtr::is_invalid_raw(child) } {
+        None
+    } else {
+        Some(unsafe { RelPtr::as_ptr_raw(child) })
+    }
+}
+
 /// An archived [`BTreeMap`](crate::alloc::collections::BTreeMap).
+///
+/// Archived B-trees always have the shape built by
+/// [`serialize_from_ordered_iter`](Self::serialize_from_ordered_iter),
+/// which is fully determined by the length of the map. Order-statistic queries
+/// like [`nth`](ArchivedBTreeMap::nth) and [`rank`](ArchivedBTreeMap::rank)
+/// compute the number of entries in each subtree from its position instead of
+/// storing subtree counts in every node. This keeps the archived format
+/// unchanged, and validation rejects B-trees which don't have that shape.
 #[cfg_attr(
     feature = "bytecheck",
     derive(bytecheck::CheckBytes),
@@ -329,6 +405,203 @@ impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
         }
     }
 
+    /// Returns the first key-value pair in the B-tree map, or `None` if it is
+    /// empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.nth(0)
+    }
+
+    /// Returns the last key-value pair in the B-tree map, or `None` if it is
+    /// empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.nth(self.len().checked_sub(1)?)
+    }
+
+    /// Returns the key-value pair at the given index in key order, or `None`
+    /// if the index is out of bounds.
+    ///
+    /// This takes `O(log n)` time.
+    pub fn nth(&self, index: usize) -> Option<(&K, &V)> {
+        let this = (self as *const Self).cast_mut();
+        Self::nth_raw(this, index)
+            .map(|(k, v)| (unsafe { &*k }, unsafe { &*v }))
+    }
+
+    /// Returns the mutable key-value pair at the given index in key order, or
+    /// `None` if the index is out of bounds.
+    pub fn nth_seal(
+        this: Seal<'_, Self>,
+        index: usize,
+    ) -> Option<(&K, Seal<'_, V>)> {
+        let this = unsafe { Seal::unseal_unchecked(this) as *mut Self };
+        Self::nth_raw(this, index)
+            .map(|(k, v)| (unsafe { &*k }, Seal::new(unsafe { &mut *v })))
+    }
+
+    /// Returns the number of keys in the B-tree map which are less than the
+    /// given key.
+    ///
+    /// If the key is present in the map, this is its index in key order. This
+    /// takes `O(log n)` time.
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let this = (self as *const Self).cast_mut();
+        Self::rank_raw(this, key, false)
+    }
+
+    fn nth_raw(this: *mut Self, index: usize) -> Option<(*mut K, *mut V)> {
+        let (node, i) = Self::seek_raw(this, index, |_, _| ())?;
+        let k = unsafe { addr_of_mut!((*node).keys[i]).cast::<K>() };
+        let v = unsafe { addr_of_mut!((*node).values[i]).cast::<V>() };
+        Some((k, v))
+    }
+
+    // Finds the node and entry index of the `index`-th entry in key order.
+    // `f` is called with each inner node and child index along the path to
+    // it.
+    fn seek_raw(
+        this: *mut Self,
+        mut index: usize,
+        mut f: impl FnMut(*mut Node<K, V, E>, usize),
+    ) -> Option<(*mut Node<K, V, E>, usize)> {
+        let len = unsafe { (*this).len.to_native() as usize };
+        if index >= len {
+            return None;
+        }
+
+        let shape = Shape::<E>::new(len);
+        let root_ptr = unsafe { addr_of_mut!((*this).root) };
+        let mut current = unsafe { RelPtr::as_ptr_raw(root_ptr) };
+        let mut depth = 0;
+        let mut position = 0;
+        'outer: loop {
+            let kind = unsafe { (*current).kind };
+
+            match kind {
+                NodeKind::Leaf => {
+                    let leaf = current.cast::<LeafNode<K, V, E>>();
+                    let len = unsafe { (*leaf).len.to_native() as usize };
+                    return (index < len).then_some((current, index));
+                }
+                NodeKind::Inner => {
+                    for i in 0..E {
+                        if let Some(lesser) = unsafe { child_raw(current, i) } {
+                            let lesser_position = child_index::<E>(position, i);
+                            let lesser_len =
+                                shape.subtree_len(depth + 1, lesser_position);
+                            if index < lesser_len {
+                                f(current, i);
+                                current = lesser;
+                                depth += 1;
+                                position = lesser_position;
+                                continue 'outer;
+                            }
+                            index -= lesser_len;
+                        }
+
+                        if index == 0 {
+                            return Some((current, i));
+                        }
+                        index -= 1;
+                    }
+
+                    let greater = unsafe { child_raw(current, E) }?;
+                    f(current, E);
+                    current = greater;
+                    depth += 1;
+                    position = child_index::<E>(position, E);
+                }
+            }
+        }
+    }
+
+    // Returns the number of keys less than the given key, or less than or
+    // equal to the given key if `inclusive` is true.
+    fn rank_raw<Q>(this: *mut Self, key: &Q, inclusive: bool) -> usize
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let len = unsafe { (*this).len.to_native() as usize };
+        if len == 0 {
+            return 0;
+        }
+
+        let shape = Shape::<E>::new(len);
+        let root_ptr = unsafe { addr_of_mut!((*this).root) };
+        let mut current = unsafe { RelPtr::as_ptr_raw(root_ptr) };
+        let mut depth = 0;
+        let mut position = 0;
+        let mut rank = 0;
+        'outer: loop {
+            let kind = unsafe { (*current).kind };
+
+            match kind {
+                NodeKind::Leaf => {
+                    let leaf = current.cast::<LeafNode<K, V, E>>();
+                    let len = unsafe { (*leaf).len.to_native() as usize };
+
+                    for i in 0..len {
+                        let k = unsafe {
+                            addr_of_mut!((*current).keys[i]).cast::<K>()
+                        };
+                        match key.cmp(unsafe { (*k).borrow() }) {
+                            Ordering::Less => return rank + i,
+                            Ordering::Equal => {
+                                return rank + i + inclusive as usize
+                            }
+                            Ordering::Greater => (),
+                        }
+                    }
+
+                    return rank + len;
+                }
+                NodeKind::Inner => {
+                    for i in 0..E {
+                        let lesser = unsafe { child_raw(current, i) };
+                        let lesser_position = child_index::<E>(position, i);
+                        let lesser_len = if lesser.is_some() {
+                            shape.subtree_len(depth + 1, lesser_position)
+                        } else {
+                            0
+                        };
+
+                        let k = unsafe {
+                            addr_of_mut!((*current).keys[i]).cast::<K>()
+                        };
+                        match key.cmp(unsafe { (*k).borrow() }) {
+                            Ordering::Less => {
+                                if let Some(lesser) = lesser {
+                                    current = lesser;
+                                    depth += 1;
+                                    position = lesser_position;
+                                    continue 'outer;
+                                } else {
+                                    return rank;
+                                }
+                            }
+                            Ordering::Equal => {
+                                return rank + lesser_len + inclusive as usize;
+                            }
+                            Ordering::Greater => rank += lesser_len + 1,
+                        }
+                    }
+
+                    if let Some(greater) = unsafe { child_raw(current, E) } {
+                        current = greater;
+                        depth += 1;
+                        position = child_index::<E>(position, E);
+                    } else {
+                        return rank;
+                    }
+                }
+            }
+        }
+    }
+
     /// Resolves an `ArchivedBTreeMap` from the given length, resolver, and
     /// output place.
     pub fn resolve_from_len(
@@ -799,7 +1072,7 @@ mod verify {
     use bytecheck::{CheckBytes, Verify};
     use rancor::{fail, Fallible, Source};
 
-    use super::{ArchivedBTreeMap, InnerNode, Node};
+    use super::{child_index, ArchivedBTreeMap, InnerNode, Node, Shape};
     use crate::{
         collections::btree_map::{LeafNode, NodeKind},
         validation::{ArchiveContext, ArchiveContextExt as _},
@@ -825,6 +1098,21 @@ mod verify {
 
     impl Error for InvalidLength {}
 
+    #[derive(Debug)]
+    struct InvalidShape;
+
+    impl fmt::Display for InvalidShape {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "Invalid B-tree shape: the nodes of the B-tree did not match \
+                 the shape expected for its length"
+            )
+        }
+    }
+
+    impl Error for InvalidShape {}
+
     unsafe impl<C, K, V, const E: usize> Verify<C> for ArchivedBTreeMap<K, V, E>
     where
         C: Fallible + ArchiveContext + ?Sized,
@@ -839,12 +1127,25 @@ mod verify {
                 return Ok(());
             }
 
-            check_node_rel_ptr::<C, K, V, E>(&self.root, context)
+            check_node_rel_ptr::<C, K, V, E>(
+                &self.root,
+                Shape::new(len),
+                0,
+                0,
+                context,
+            )
         }
     }
 
+    // B-trees are always built with the same shape for a given length, and
+    // order-statistic queries rely on that. So in addition to checking each
+    // node, we also check that the node at each `depth` and `position` (from
+    // the left) of the tree has the expected kind and length.
     fn check_node_rel_ptr<C, K, V, const E: usize>(
         node_rel_ptr: &RelPtr<Node<K, V, E>>,
+        shape: Shape<E>,
+        depth: u32,
+        position: usize,
         context: &mut C,
     ) -> Result<(), C::Error>
     where
@@ -871,24 +1172,36 @@ mod verify {
         // and we just checked to make sure it pointed to a valid `NodeKind`.
         let kind = unsafe { kind_ptr.read() };
 
-        match kind {
-            NodeKind::Leaf => {
+        let expected_len = shape.subtree_len(depth, position);
+        match (kind, expected_len <= E) {
+            (NodeKind::Leaf, true) => {
                 // SAFETY:
                 // We checked to make sure that `node_ptr` is properly aligned,
                 // dereferenceable, and contained entirely within `context`'s
                 // buffer by calling `check_subtree_ptr`.
                 unsafe {
-                    check_leaf_node::<C, K, V, E>(node_ptr.cast(), context)?
+                    check_leaf_node::<C, K, V, E>(
+                        node_ptr.cast(),
+                        expected_len,
+                        context,
+                    )?
                 }
             }
-            NodeKind::Inner => {
+            (NodeKind::Inner, false) => {
                 // SAFETY:
                 // We checked to make sure that `node_ptr` is properly aligned
                 // and dereferenceable.
                 unsafe {
-                    check_inner_node::<C, K, V, E>(node_ptr.cast(), context)?
+                    check_inner_node::<C, K, V, E>(
+                        node_ptr.cast(),
+                        shape,
+                        depth,
+                        position,
+                        context,
+                    )?
                 }
             }
+            _ => fail!(InvalidShape),
         }
 
         Ok(())
@@ -900,6 +1213,7 @@ mod verify {
     /// within `context`'s buffer.
     unsafe fn check_leaf_node<C, K, V, const E: usize>(
         node_ptr: *const LeafNode<K, V, E>,
+        expected_len: usize,
         context: &mut C,
     ) -> Result<(), C::Error>
     where
@@ -926,6 +1240,9 @@ mod verify {
             if len > E {
                 fail!(InvalidLength { len, maximum: E });
             }
+            if len != expected_len {
+                fail!(InvalidShape);
+            }
 
             // SAFETY: We checked that `node_ptr` is properly-aligned and
             // dereferenceable.
@@ -984,6 +1301,9 @@ mod verify {
     /// - `len` must be less than or equal to `E`.
     unsafe fn check_inner_node<C, K, V, const E: usize>(
         node_ptr: *const InnerNode<K, V, E>,
+        shape: Shape<E>,
+        depth: u32,
+        position: usize,
         context: &mut C,
     ) -> Result<(), C::Error>
     where
@@ -1007,9 +1327,13 @@ mod verify {
                 // SAFETY: We just checked the `lesser_node_ptr` and it
                 // succeeded, so it's safe to dereference.
                 let lesser_node = unsafe { &*lesser_node_ptr };
-                if !lesser_node.is_invalid() {
-                    check_node_rel_ptr::<C, K, V, E>(lesser_node, context)?;
-                }
+                check_child::<C, K, V, E>(
+                    lesser_node,
+                    shape,
+                    depth + 1,
+                    child_index::<E>(position, i),
+                    context,
+                )?;
             }
             // SAFETY: We checked that `node_ptr` is properly aligned and
             // dereferenceable.
@@ -1024,9 +1348,13 @@ mod verify {
             // SAFETY: We just checked the `greater_node_ptr` and it succeeded,
             // so it's safe to dereference.
             let greater_node = unsafe { &*greater_node_ptr };
-            if !greater_node.is_invalid() {
-                check_node_rel_ptr::<C, K, V, E>(greater_node, context)?;
-            }
+            check_child::<C, K, V, E>(
+                greater_node,
+                shape,
+                depth + 1,
+                child_index::<E>(position, E),
+                context,
+            )?;
 
             // SAFETY: We checked that `node_ptr` is properly aligned and
             // dereferenceable.
@@ -1043,4 +1371,27 @@ mod verify {
             Ok(())
         })
     }
+
+    fn check_child<C, K, V, const E: usize>(
+        child: &RelPtr<Node<K, V, E>>,
+        shape: Shape<E>,
+        depth: u32,
+        position: usize,
+        context: &mut C,
+    ) -> Result<(), C::Error>
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+        K: CheckBytes<C>,
+        V: CheckBytes<C>,
+    {
+        let expected = shape.subtree_len(depth, position) != 0;
+        match (child.is_invalid(), expected) {
+            (true, false) => Ok(()),
+            (false, true) => check_node_rel_ptr::<C, K, V, E>(
+                child, shape, depth, position, context,
+            ),
+            _ => fail!(InvalidShape),
+        }
+    }
 }
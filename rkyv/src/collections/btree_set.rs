@@ -5,6 +5,11 @@ use core::{borrow::Borrow, fmt, ops::ControlFlow};
 use munge::munge;
 use rancor::{Fallible, Source};
 
+#[cfg(feature = "alloc")]
+use crate::collections::{
+    btree_map::Keys,
+    set_ops::{SortedDifference, SortedIntersection, SortedUnion},
+};
 use crate::{
     collections::btree_map::{ArchivedBTreeMap, BTreeMapResolver},
     ser::{Allocator, Writer},
@@ -100,6 +105,99 @@ impl<K, const E: usize> ArchivedBTreeSet<K, E> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<K, const E: usize> ArchivedBTreeSet<K, E> {
+    /// Gets an iterator over the keys of the set in ascending order.
+    pub fn iter(&self) -> Keys<'_, K, (), E> {
+        self.0.keys()
+    }
+
+    /// Returns an iterator over the keys which are in both this set and
+    /// `other`, in ascending order.
+    ///
+    /// `other` must yield its elements in ascending order, like another
+    /// archived B-tree set or a `BTreeSet`. The two sets are merged in time
+    /// linear in their combined length.
+    pub fn intersection<'a, 'b, I, T>(
+        &'a self,
+        other: I,
+    ) -> SortedIntersection<Keys<'a, K, (), E>, I::IntoIter>
+    where
+        I: IntoIterator<Item = &'b T>,
+        K: PartialOrd<T>,
+        T: 'b + ?Sized,
+    {
+        SortedIntersection::new(self.iter(), other.into_iter())
+    }
+
+    /// Returns an iterator over the keys which are in this set but not in
+    /// `other`, in ascending order.
+    ///
+    /// `other` must yield its elements in ascending order. The two sets are
+    /// merged in time linear in their combined length.
+    pub fn difference<'a, 'b, I, T>(
+        &'a self,
+        other: I,
+    ) -> SortedDifference<Keys<'a, K, (), E>, I::IntoIter>
+    where
+        I: IntoIterator<Item = &'b T>,
+        K: PartialOrd<T>,
+        T: 'b + ?Sized,
+    {
+        SortedDifference::new(self.iter(), other.into_iter())
+    }
+
+    /// Returns an iterator over the keys which are in this set or `other`, in
+    /// ascending order and without duplicates.
+    ///
+    /// `other` must yield its elements in ascending order. The two sets are
+    /// merged in time linear in their combined length.
+    pub fn union<'a, I>(
+        &'a self,
+        other: I,
+    ) -> SortedUnion<Keys<'a, K, (), E>, I::IntoIter>
+    where
+        I: IntoIterator<Item = &'a K>,
+        K: Ord,
+    {
+        SortedUnion::new(self.iter(), other.into_iter())
+    }
+
+    /// Returns whether every key in this set is also in `other`.
+    ///
+    /// `other` must yield its elements in ascending order.
+    pub fn is_subset<'b, I, T>(&self, other: I) -> bool
+    where
+        I: IntoIterator<Item = &'b T>,
+        K: PartialOrd<T>,
+        T: 'b + ?Sized,
+    {
+        self.difference(other).next().is_none()
+    }
+
+    /// Returns whether this set has no keys in common with `other`.
+    ///
+    /// `other` must yield its elements in ascending order.
+    pub fn is_disjoint<'b, I, T>(&self, other: I) -> bool
+    where
+        I: IntoIterator<Item = &'b T>,
+        K: PartialOrd<T>,
+        T: 'b + ?Sized,
+    {
+        self.intersection(other).next().is_none()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, const E: usize> IntoIterator for &'a ArchivedBTreeSet<K, E> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, (), E>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<K, const E: usize> fmt::Debug for ArchivedBTreeSet<K, E>
 where
     K: fmt::Debug,
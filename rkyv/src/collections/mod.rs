@@ -2,5 +2,9 @@
 
 pub mod btree_map;
 pub mod btree_set;
+pub mod perfect_hash;
+pub mod set_ops;
+pub mod sorted_vec;
 pub mod swiss_table;
+pub mod trie;
 pub mod util;
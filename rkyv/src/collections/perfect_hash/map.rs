@@ -0,0 +1,372 @@
+//! Archived hash map implementation using a perfect hash table.
+
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ops::Index,
+    slice,
+};
+
+use munge::munge;
+use rancor::{Fallible, Source};
+
+use crate::{
+    collections::{
+        perfect_hash::table::{
+            ArchivedPerfectHashTable, PerfectHashTableResolver,
+        },
+        util::{Entry, EntryAdapter},
+    },
+    hash::{hash_value, FxHasher64},
+    seal::Seal,
+    ser::{Allocator, Writer},
+    Place, Portable, Serialize,
+};
+
+/// An archived static hash map which uses perfect hashing.
+///
+/// Perfect hash maps are built once at serialization time and have no empty
+/// buckets. Every lookup hashes the key once and compares it against a single
+/// entry.
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+pub struct ArchivedPerfectHashMap<K, V, H = FxHasher64> {
+    table: ArchivedPerfectHashTable<Entry<K, V>>,
+    _phantom: PhantomData<H>,
+}
+
+impl<K, V, H> ArchivedPerfectHashMap<K, V, H> {
+    /// Returns whether the hash map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Returns the number of elements in the hash map.
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Returns an iterator over the key-value entries in the hash map.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.table.entries().iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys in the hash map.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            inner: self.table.entries().iter(),
+        }
+    }
+
+    /// Returns an iterator over the values in the hash map.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            inner: self.table.entries().iter(),
+        }
+    }
+}
+
+impl<K, V, H: Hasher + Default> ArchivedPerfectHashMap<K, V, H> {
+    /// Returns the key-value pair corresponding to the supplied key using the
+    /// given comparison function.
+    pub fn get_key_value_with<Q, C>(&self, key: &Q, cmp: C) -> Option<(&K, &V)>
+    where
+        Q: Hash + Eq + ?Sized,
+        C: Fn(&Q, &K) -> bool,
+    {
+        let entry = self
+            .table
+            .get_with(hash_value::<Q, H>(key), |e| cmp(key, &e.key))?;
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_key_value_with(key, |q, k| q == k.borrow())
+    }
+
+    /// Returns a reference to the value corresponding to the supplied key using
+    /// the given comparison function.
+    pub fn get_with<Q, C>(&self, key: &Q, cmp: C) -> Option<&V>
+    where
+        Q: Hash + Eq + ?Sized,
+        C: Fn(&Q, &K) -> bool,
+    {
+        Some(self.get_key_value_with(key, cmp)?.1)
+    }
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Some(self.get_key_value(key)?.1)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the supplied
+    /// key.
+    pub fn get_seal<'a, Q>(this: Seal<'a, Self>, key: &Q) -> Option<Seal<'a, V>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        munge!(let Self { table, .. } = this);
+        let entry = ArchivedPerfectHashTable::get_seal_with(
+            table,
+            hash_value::<Q, H>(key),
+            |e| key == e.key.borrow(),
+        )?;
+        munge!(let Entry { value, .. } = entry);
+        Some(value)
+    }
+
+    /// Returns whether the hash map contains the given key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Serializes an iterator of key-value pairs as a perfect hash map.
+    ///
+    /// The keys must be unique.
+    pub fn serialize_from_iter<I, BKU, BVU, KU, VU, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<PerfectHashMapResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator<Item = (BKU, BVU)>,
+        BKU: Borrow<KU>,
+        BVU: Borrow<VU>,
+        KU: Serialize<S, Archived = K> + Hash + Eq,
+        VU: Serialize<S, Archived = V>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        ArchivedPerfectHashTable::<Entry<K, V>>::serialize_from_iter(
+            iter.clone()
+                .map(|(key, value)| EntryAdapter::new(key, value)),
+            iter.map(|(key, _)| hash_value::<KU, H>(key.borrow())),
+            serializer,
+        )
+        .map(PerfectHashMapResolver)
+    }
+
+    /// Resolves an archived perfect hash map from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: PerfectHashMapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedPerfectHashMap { table, _phantom: _ } = out);
+        ArchivedPerfectHashTable::<Entry<K, V>>::resolve_from_len(
+            len, resolver.0, table,
+        )
+    }
+}
+
+impl<K, V, H> fmt::Debug for ArchivedPerfectHashMap<K, V, H>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, H> Eq for ArchivedPerfectHashMap<K, V, H>
+where
+    K: Hash + Eq,
+    V: Eq,
+    H: Default + Hasher,
+{
+}
+
+impl<K, V, H> PartialEq for ArchivedPerfectHashMap<K, V, H>
+where
+    K: Hash + Eq,
+    V: PartialEq,
+    H: Default + Hasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            false
+        } else {
+            self.iter().all(|(key, value)| {
+                other.get(key).is_some_and(|v| *value == *v)
+            })
+        }
+    }
+}
+
+impl<K, Q, V, H> Index<&'_ Q> for ArchivedPerfectHashMap<K, V, H>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: Eq + Hash + ?Sized,
+    H: Default + Hasher,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).unwrap()
+    }
+}
+
+/// The resolver for [`ArchivedPerfectHashMap`].
+pub struct PerfectHashMapResolver(PerfectHashTableResolver);
+
+/// An iterator over the key-value pairs of an [`ArchivedPerfectHashMap`].
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys of an [`ArchivedPerfectHashMap`].
+pub struct Keys<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| &entry.key)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of an [`ArchivedPerfectHashMap`].
+pub struct Values<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| &entry.value)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{
+        error::Error,
+        fmt,
+        hash::{Hash, Hasher},
+    };
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::ArchivedPerfectHashMap;
+    use crate::hash::hash_value;
+
+    #[derive(Debug)]
+    struct MisplacedEntry {
+        index: usize,
+    }
+
+    impl fmt::Display for MisplacedEntry {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "perfect hash map entry at index {} is not in the slot its \
+                 key hashes to",
+                self.index,
+            )
+        }
+    }
+
+    impl Error for MisplacedEntry {}
+
+    unsafe impl<C, K, V, H> Verify<C> for ArchivedPerfectHashMap<K, V, H>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+        K: Hash,
+        H: Hasher + Default,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            // Every key must be reachable through the displacement table. This
+            // also guarantees that keys are unique.
+            for (index, entry) in self.table.entries().iter().enumerate() {
+                let hash = hash_value::<K, H>(&entry.key);
+                if self.table.index(hash) != Some(index) {
+                    fail!(MisplacedEntry { index });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
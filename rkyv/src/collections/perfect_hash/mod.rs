@@ -0,0 +1,12 @@
+//! Static perfect hash tables for archived hash maps and hash sets.
+//!
+//! These collections are serialized with
+//! [`AsPerfectHash`](crate::with::AsPerfectHash).
+
+pub mod map;
+pub mod set;
+pub mod table;
+
+pub use map::{ArchivedPerfectHashMap, PerfectHashMapResolver};
+pub use set::{ArchivedPerfectHashSet, PerfectHashSetResolver};
+pub use table::{ArchivedPerfectHashTable, PerfectHashTableResolver};
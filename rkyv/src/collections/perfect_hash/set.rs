@@ -0,0 +1,121 @@
+//! Archived hash set implementation using a perfect hash table.
+
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use munge::munge;
+use rancor::{Fallible, Source};
+
+use crate::{
+    collections::perfect_hash::map::{
+        ArchivedPerfectHashMap, Keys, PerfectHashMapResolver,
+    },
+    hash::FxHasher64,
+    ser::{Allocator, Writer},
+    Place, Portable, Serialize,
+};
+
+/// An archived static hash set which uses perfect hashing. This is a wrapper
+/// around a perfect hash map with the same key and unit value.
+#[derive(Portable)]
+#[rkyv(crate)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(transparent)]
+pub struct ArchivedPerfectHashSet<K, H = FxHasher64> {
+    inner: ArchivedPerfectHashMap<K, (), H>,
+}
+
+impl<K, H> ArchivedPerfectHashSet<K, H> {
+    /// Gets the number of items in the hash set.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether there are no items in the hash set.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Gets an iterator over the keys of the underlying hash map.
+    pub fn iter(&self) -> Keys<'_, K, ()> {
+        self.inner.keys()
+    }
+}
+
+impl<K, H: Hasher + Default> ArchivedPerfectHashSet<K, H> {
+    /// Gets the key corresponding to the given key in the hash set.
+    pub fn get<Q>(&self, k: &Q) -> Option<&K>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.get_key_value(k).map(|(k, _)| k)
+    }
+
+    /// Returns whether the given key is in the hash set.
+    pub fn contains<Q>(&self, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.inner.contains_key(k)
+    }
+
+    /// Resolves an archived perfect hash set from the given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: PerfectHashSetResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedPerfectHashSet { inner } = out);
+        ArchivedPerfectHashMap::resolve_from_len(len, resolver.0, inner);
+    }
+
+    /// Serializes an iterator of keys as a perfect hash set.
+    ///
+    /// The keys must be unique.
+    pub fn serialize_from_iter<I, KU, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<PerfectHashSetResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator,
+        I::Item: Borrow<KU>,
+        KU: Serialize<S, Archived = K> + Hash + Eq,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        Ok(PerfectHashSetResolver(
+            ArchivedPerfectHashMap::<K, (), H>::serialize_from_iter::<
+                _,
+                _,
+                (),
+                _,
+                _,
+                _,
+            >(iter.map(|x| (x, ())), serializer)?,
+        ))
+    }
+}
+
+impl<K: fmt::Debug, H> fmt::Debug for ArchivedPerfectHashSet<K, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq, H: Hasher + Default> PartialEq
+    for ArchivedPerfectHashSet<K, H>
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<K: Hash + Eq, H: Hasher + Default> Eq for ArchivedPerfectHashSet<K, H> {}
+
+/// The resolver for archived perfect hash sets.
+pub struct PerfectHashSetResolver(PerfectHashMapResolver);
@@ -0,0 +1,618 @@
+//! An archived static hash table built with perfect hashing.
+//!
+//! Tables are constructed with the CHD ("compress, hash, and displace")
+//! algorithm:
+//!
+//! - Each key hash is mixed with a per-table seed and split into a bucket
+//!   selector and two slot hashes.
+//! - Keys are grouped into buckets of about `LAMBDA` keys each. Buckets are
+//!   placed from largest to smallest, and each one is assigned the first of up
+//!   to `MAX_DISPLACEMENTS` pairs of displacements which moves all of its keys
+//!   into free slots. Buckets with a single key are displaced directly into the
+//!   remaining free slots.
+//! - If a bucket can't be placed, construction is retried with a new seed. If
+//!   no seed works after `MAX_ATTEMPTS` tries, construction fails.
+//!
+//! Every slot holds exactly one entry, so the table has no empty buckets and a
+//! lookup takes one hash and one probe.
+
+use core::{borrow::Borrow, error::Error, fmt};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    collections::util::IteratorLengthMismatch,
+    primitive::{ArchivedU32, ArchivedU64},
+    seal::Seal,
+    ser::{Allocator, Writer, WriterExt as _},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Archive as _, Place, Portable, Serialize,
+};
+
+/// The average number of keys in each displacement bucket.
+const LAMBDA: usize = 5;
+
+/// The number of seeds to try before giving up on constructing a table.
+const MAX_ATTEMPTS: u64 = 32;
+
+/// The number of displacements to try for each bucket before retrying with a
+/// new seed.
+const MAX_DISPLACEMENTS: u64 = 1 << 20;
+
+const EMPTY: usize = usize::MAX;
+
+/// A low-level archived static hash table with explicit hashing.
+///
+/// Unlike an [`ArchivedHashTable`], this table has no empty buckets and never
+/// probes more than one entry.
+///
+/// [`ArchivedHashTable`]: crate::collections::swiss_table::ArchivedHashTable
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedPerfectHashTable<T> {
+    displacements: ArchivedVec<[ArchivedU32; 2]>,
+    entries: ArchivedVec<T>,
+    seed: ArchivedU64,
+}
+
+#[inline]
+fn mix(mut x: u64) -> u64 {
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    x ^= x >> 33;
+    x
+}
+
+struct Hashes {
+    g: u32,
+    f1: u32,
+    f2: u32,
+}
+
+#[inline]
+fn split(hash: u64, seed: u64) -> Hashes {
+    let a = mix(hash ^ seed);
+    let b = mix(a ^ 0x9e37_79b9_7f4a_7c15);
+    Hashes {
+        g: (a >> 32) as u32,
+        f1: a as u32,
+        f2: b as u32,
+    }
+}
+
+#[inline]
+fn displace(f1: u32, f2: u32, displacement: [u32; 2], len: usize) -> usize {
+    // This can't overflow because the product of two `u32`s is at most
+    // `u64::MAX - 2 * u32::MAX`.
+    let slot = u64::from(f1) * u64::from(displacement[0])
+        + u64::from(f2)
+        + u64::from(displacement[1]);
+    (slot % len as u64) as usize
+}
+
+fn bucket_count(len: usize) -> usize {
+    len.div_ceil(LAMBDA)
+}
+
+impl<T> ArchivedPerfectHashTable<T> {
+    /// Returns whether the hash table is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of elements in the hash table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the seed used to build the hash table.
+    pub fn seed(&self) -> u64 {
+        self.seed.to_native()
+    }
+
+    /// Returns the index of the only entry which may match the given hash.
+    pub fn index(&self, hash: u64) -> Option<usize> {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+
+        let hashes = split(hash, self.seed());
+        let displacements = self.displacements.as_slice();
+        let displacement =
+            &displacements[hashes.g as usize % displacements.len()];
+        Some(displace(
+            hashes.f1,
+            hashes.f2,
+            [displacement[0].to_native(), displacement[1].to_native()],
+            len,
+        ))
+    }
+
+    /// Returns the entry corresponding to the supplied hash if it matches the
+    /// given comparison function.
+    pub fn get_with<C>(&self, hash: u64, cmp: C) -> Option<&T>
+    where
+        C: Fn(&T) -> bool,
+    {
+        let entry = &self.entries[self.index(hash)?];
+        cmp(entry).then_some(entry)
+    }
+
+    /// Returns the mutable entry corresponding to the supplied hash if it
+    /// matches the given comparison function.
+    pub fn get_seal_with<C>(
+        this: Seal<'_, Self>,
+        hash: u64,
+        cmp: C,
+    ) -> Option<Seal<'_, T>>
+    where
+        C: Fn(&T) -> bool,
+    {
+        let index = this.index(hash)?;
+        if !cmp(&this.entries[index]) {
+            return None;
+        }
+        munge!(let Self { entries, .. } = this);
+        Some(ArchivedVec::as_slice_seal(entries).index(index))
+    }
+
+    /// Returns the entries of the hash table in slot order.
+    pub fn entries(&self) -> &[T] {
+        self.entries.as_slice()
+    }
+
+    /// Returns the sealed entries of the hash table in slot order.
+    pub fn entries_seal(this: Seal<'_, Self>) -> Seal<'_, [T]> {
+        munge!(let Self { entries, .. } = this);
+        ArchivedVec::as_slice_seal(entries)
+    }
+
+    /// Serializes an iterator of items as a perfect hash table.
+    ///
+    /// The hashes of the items must be unique, which is always the case for
+    /// unique keys and a well-behaved hasher. Otherwise, construction fails
+    /// with an error.
+    pub fn serialize_from_iter<I, U, H, S>(
+        items: I,
+        hashes: H,
+        serializer: &mut S,
+    ) -> Result<PerfectHashTableResolver, S::Error>
+    where
+        I: ExactSizeIterator,
+        I::Item: Borrow<U>,
+        U: Serialize<S, Archived = T>,
+        H: ExactSizeIterator<Item = u64>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        let len = items.len();
+        let bucket_count = bucket_count(len);
+
+        SerVec::<I::Item>::with_capacity(
+            serializer,
+            len,
+            |items_buf, serializer| {
+                for item in items {
+                    if items_buf.len() == len {
+                        fail!(IteratorLengthMismatch {
+                            expected: len,
+                            actual: len + 1,
+                        });
+                    }
+                    items_buf.push(item);
+                }
+                if items_buf.len() != len {
+                    fail!(IteratorLengthMismatch {
+                        expected: len,
+                        actual: items_buf.len(),
+                    });
+                }
+
+                SerVec::<[u32; 2]>::with_capacity(
+                    serializer,
+                    bucket_count,
+                    |displacements, serializer| {
+                        for _ in 0..bucket_count {
+                            displacements.push([0, 0]);
+                        }
+
+                        SerVec::<Slot>::with_capacity(
+                            serializer,
+                            len,
+                            |slots, serializer| {
+                                for _ in 0..len {
+                                    slots.push(Slot {
+                                        item: EMPTY,
+                                        generation: 0,
+                                    });
+                                }
+
+                                let seed = Self::build(
+                                    hashes,
+                                    displacements,
+                                    slots,
+                                    serializer,
+                                )?;
+
+                                let displacements =
+                                    ArchivedVec::serialize_from_slice(
+                                        displacements.as_slice(),
+                                        serializer,
+                                    )?;
+                                let entries = Self::serialize_entries(
+                                    items_buf, slots, serializer,
+                                )?;
+
+                                Ok(PerfectHashTableResolver {
+                                    displacements,
+                                    entries,
+                                    seed,
+                                })
+                            },
+                        )?
+                    },
+                )?
+            },
+        )?
+    }
+
+    fn build<H, S>(
+        hashes: H,
+        displacements: &mut [[u32; 2]],
+        slots: &mut [Slot],
+        serializer: &mut S,
+    ) -> Result<u64, S::Error>
+    where
+        H: ExactSizeIterator<Item = u64>,
+        S: Fallible + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        let len = slots.len();
+
+        SerVec::<Key>::with_capacity(serializer, len, |keys, serializer| {
+            for (item, hash) in hashes.enumerate() {
+                if item == len {
+                    fail!(IteratorLengthMismatch {
+                        expected: len,
+                        actual: len + 1,
+                    });
+                }
+                keys.push(Key {
+                    hash,
+                    item,
+                    bucket: 0,
+                    f1: 0,
+                    f2: 0,
+                });
+            }
+            if keys.len() != len {
+                fail!(IteratorLengthMismatch {
+                    expected: len,
+                    actual: keys.len(),
+                });
+            }
+
+            SerVec::<Bucket>::with_capacity(
+                serializer,
+                displacements.len(),
+                |buckets, serializer| {
+                    SerVec::<(usize, usize)>::with_capacity(
+                        serializer,
+                        len,
+                        |pending, _| {
+                            if u32::try_from(len).is_ok() {
+                                for attempt in 0..MAX_ATTEMPTS {
+                                    let seed = mix(attempt);
+                                    if try_build(
+                                        seed,
+                                        keys,
+                                        buckets,
+                                        displacements,
+                                        slots,
+                                        pending,
+                                    ) {
+                                        return Ok(seed);
+                                    }
+                                }
+                            }
+
+                            fail!(ConstructionFailed { len })
+                        },
+                    )?
+                },
+            )?
+        })?
+    }
+
+    fn serialize_entries<BU, U, S>(
+        items: &[BU],
+        slots: &[Slot],
+        serializer: &mut S,
+    ) -> Result<VecResolver, S::Error>
+    where
+        BU: Borrow<U>,
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Writer + Allocator + ?Sized,
+    {
+        SerVec::with_capacity(
+            serializer,
+            slots.len(),
+            |resolvers, serializer| {
+                for slot in slots.iter() {
+                    resolvers
+                        .push(items[slot.item].borrow().serialize(serializer)?);
+                }
+
+                let pos = serializer.align_for::<T>()?;
+                for (slot, resolver) in slots.iter().zip(resolvers.drain()) {
+                    unsafe {
+                        serializer.resolve_aligned(
+                            items[slot.item].borrow(),
+                            resolver,
+                        )?;
+                    }
+                }
+
+                Ok(VecResolver::from_pos(pos))
+            },
+        )?
+    }
+
+    /// Resolves an archived perfect hash table from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: PerfectHashTableResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let Self { displacements, entries, seed } = out);
+        ArchivedVec::resolve_from_len(
+            bucket_count(len),
+            resolver.displacements,
+            displacements,
+        );
+        ArchivedVec::resolve_from_len(len, resolver.entries, entries);
+        resolver.seed.resolve((), seed);
+    }
+}
+
+/// The resolver for [`ArchivedPerfectHashTable`].
+pub struct PerfectHashTableResolver {
+    displacements: VecResolver,
+    entries: VecResolver,
+    seed: u64,
+}
+
+#[derive(Debug)]
+struct ConstructionFailed {
+    len: usize,
+}
+
+impl fmt::Display for ConstructionFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to construct a perfect hash table with {} entries, the \
+             keys may not be unique",
+            self.len,
+        )
+    }
+}
+
+impl Error for ConstructionFailed {}
+
+struct Key {
+    hash: u64,
+    item: usize,
+    bucket: u32,
+    f1: u32,
+    f2: u32,
+}
+
+struct Bucket {
+    index: u32,
+    start: usize,
+    end: usize,
+}
+
+struct Slot {
+    item: usize,
+    generation: u64,
+}
+
+fn try_build(
+    seed: u64,
+    keys: &mut [Key],
+    buckets: &mut SerVec<Bucket>,
+    displacements: &mut [[u32; 2]],
+    slots: &mut [Slot],
+    pending: &mut SerVec<(usize, usize)>,
+) -> bool {
+    let len = slots.len();
+    let bucket_count = displacements.len() as u32;
+
+    for key in keys.iter_mut() {
+        let hashes = split(key.hash, seed);
+        key.bucket = hashes.g % bucket_count;
+        key.f1 = hashes.f1;
+        key.f2 = hashes.f2;
+    }
+    keys.sort_unstable_by_key(|key| (key.bucket, key.f1, key.f2));
+
+    // Keys in the same bucket with the same slot hashes can never be separated
+    // by any displacement.
+    if keys.windows(2).any(|pair| {
+        (pair[0].bucket, pair[0].f1, pair[0].f2)
+            == (pair[1].bucket, pair[1].f1, pair[1].f2)
+    }) {
+        return false;
+    }
+
+    buckets.clear();
+    let mut start = 0;
+    for i in 1..=len {
+        if i == len || keys[i].bucket != keys[start].bucket {
+            buckets.push(Bucket {
+                index: keys[start].bucket,
+                start,
+                end: i,
+            });
+            start = i;
+        }
+    }
+    buckets.sort_unstable_by(|a, b| {
+        (b.end - b.start)
+            .cmp(&(a.end - a.start))
+            .then(a.index.cmp(&b.index))
+    });
+
+    displacements.fill([0, 0]);
+    for slot in slots.iter_mut() {
+        slot.item = EMPTY;
+        slot.generation = 0;
+    }
+
+    // Multi-key buckets are placed first, so the singleton buckets at the end
+    // fill exactly the slots which remain free.
+    let mut generation = 0;
+    let mut free = 0;
+    for bucket in buckets.iter() {
+        let displacement = match &keys[bucket.start..bucket.end] {
+            [key] => {
+                while slots[free].item != EMPTY {
+                    free += 1;
+                }
+                slots[free].item = key.item;
+                let f2 = key.f2 as usize % len;
+                [0, ((free + len - f2) % len) as u32]
+            }
+            bucket_keys => match find_displacement(
+                bucket_keys,
+                slots,
+                pending,
+                &mut generation,
+            ) {
+                Some(displacement) => displacement,
+                None => return false,
+            },
+        };
+        displacements[bucket.index as usize] = displacement;
+    }
+
+    true
+}
+
+/// Finds a pair of displacements which moves all of the given keys into free
+/// slots, and fills those slots.
+fn find_displacement(
+    keys: &[Key],
+    slots: &mut [Slot],
+    pending: &mut SerVec<(usize, usize)>,
+    generation: &mut u64,
+) -> Option<[u32; 2]> {
+    let len = slots.len();
+    let tries = u64::min(MAX_DISPLACEMENTS, (len as u64).pow(2));
+
+    'displace: for i in 0..tries {
+        let displacement = [(i / len as u64) as u32, (i % len as u64) as u32];
+        *generation += 1;
+        pending.clear();
+
+        for key in keys.iter() {
+            let index = displace(key.f1, key.f2, displacement, len);
+            let slot = &mut slots[index];
+            if slot.item != EMPTY || slot.generation == *generation {
+                continue 'displace;
+            }
+            slot.generation = *generation;
+            pending.push((index, key.item));
+        }
+
+        for &(index, item) in pending.iter() {
+            slots[index].item = item;
+        }
+        return Some(displacement);
+    }
+
+    None
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::{bucket_count, ArchivedPerfectHashTable};
+
+    #[derive(Debug)]
+    struct InvalidDisplacementCount {
+        expected: usize,
+        actual: usize,
+    }
+
+    impl fmt::Display for InvalidDisplacementCount {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "perfect hash table has {} displacements but expected {}",
+                self.actual, self.expected,
+            )
+        }
+    }
+
+    impl Error for InvalidDisplacementCount {}
+
+    #[derive(Debug)]
+    struct InvalidDisplacement {
+        index: usize,
+        len: usize,
+    }
+
+    impl fmt::Display for InvalidDisplacement {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "displacement at index {} is out of range for a perfect hash \
+                 table with {} entries",
+                self.index, self.len,
+            )
+        }
+    }
+
+    impl Error for InvalidDisplacement {}
+
+    unsafe impl<C, T> Verify<C> for ArchivedPerfectHashTable<T>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let len = self.len();
+            let expected = bucket_count(len);
+            let actual = self.displacements.len();
+            if actual != expected {
+                fail!(InvalidDisplacementCount { expected, actual });
+            }
+
+            for (index, displacement) in self.displacements.iter().enumerate() {
+                if displacement.iter().any(|d| d.to_native() as usize >= len) {
+                    fail!(InvalidDisplacement { index, len });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
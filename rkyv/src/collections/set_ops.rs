@@ -0,0 +1,403 @@
+//! Set operations for archived sets.
+//!
+//! Hash sets compute intersections and differences by checking each element of
+//! one set against the other with [`Contains`]. B-tree sets walk both sets in
+//! ascending order and merge them in linear time.
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    iter::{FusedIterator, Peekable},
+};
+
+use crate::{
+    collections::{
+        btree_set::ArchivedBTreeSet,
+        swiss_table::{ArchivedHashSet, ArchivedIndexSet},
+    },
+    hash::TableHasher,
+};
+
+/// A set which can be checked for a value.
+///
+/// This is implemented for archived sets, standard library sets, and closures
+/// which return whether a value is in a set. A closure can check archived
+/// values against a set with a different element type:
+///
+/// ```
+/// use std::collections::HashSet;
+///
+/// use rkyv::{rancor::Error, string::ArchivedString, Archived};
+///
+/// let tags = HashSet::from(["admin".to_string(), "user".to_string()]);
+/// let bytes = rkyv::to_bytes::<Error>(&tags).unwrap();
+/// let archived =
+///     rkyv::access::<Archived<HashSet<String>>, Error>(&bytes).unwrap();
+///
+/// let required = HashSet::from(["admin".to_string(), "owner".to_string()]);
+/// let is_required = |tag: &ArchivedString| required.contains(tag.as_str());
+/// let granted = archived.intersection(&is_required).collect::<Vec<_>>();
+/// assert_eq!(granted, ["admin"]);
+/// ```
+pub trait Contains<Q: ?Sized> {
+    /// Returns whether the set contains the given value.
+    fn contains(&self, value: &Q) -> bool;
+}
+
+impl<Q, F> Contains<Q> for F
+where
+    Q: ?Sized,
+    F: Fn(&Q) -> bool,
+{
+    fn contains(&self, value: &Q) -> bool {
+        self(value)
+    }
+}
+
+impl<K, H, Q> Contains<Q> for ArchivedHashSet<K, H>
+where
+    K: Borrow<Q>,
+    H: TableHasher,
+    Q: Hash + Eq + ?Sized,
+{
+    fn contains(&self, value: &Q) -> bool {
+        ArchivedHashSet::contains(self, value)
+    }
+}
+
+impl<K, H, Q> Contains<Q> for ArchivedIndexSet<K, H>
+where
+    K: Borrow<Q>,
+    H: Default + Hasher,
+    Q: Hash + Eq + ?Sized,
+{
+    fn contains(&self, value: &Q) -> bool {
+        ArchivedIndexSet::contains(self, value)
+    }
+}
+
+impl<K, Q, const E: usize> Contains<Q> for ArchivedBTreeSet<K, E>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    fn contains(&self, value: &Q) -> bool {
+        self.contains_key(value)
+    }
+}
+
+/// An iterator over the elements of a set which are also in another set.
+///
+/// This struct is created by the `intersection` methods on archived hash sets.
+pub struct Intersection<'a, I, O: ?Sized> {
+    iter: I,
+    other: &'a O,
+}
+
+impl<'a, I, O: ?Sized> Intersection<'a, I, O> {
+    pub(crate) fn new(iter: I, other: &'a O) -> Self {
+        Self { iter, other }
+    }
+}
+
+impl<'a, K, I, O> Iterator for Intersection<'a, I, O>
+where
+    K: 'a + ?Sized,
+    I: Iterator<Item = &'a K>,
+    O: Contains<K> + ?Sized,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|value| other.contains(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, K, I, O> FusedIterator for Intersection<'a, I, O>
+where
+    K: 'a + ?Sized,
+    I: FusedIterator<Item = &'a K>,
+    O: Contains<K> + ?Sized,
+{
+}
+
+/// An iterator over the elements of a set which are not in another set.
+///
+/// This struct is created by the `difference` methods on archived hash sets.
+pub struct Difference<'a, I, O: ?Sized> {
+    iter: I,
+    other: &'a O,
+}
+
+impl<'a, I, O: ?Sized> Difference<'a, I, O> {
+    pub(crate) fn new(iter: I, other: &'a O) -> Self {
+        Self { iter, other }
+    }
+}
+
+impl<'a, K, I, O> Iterator for Difference<'a, I, O>
+where
+    K: 'a + ?Sized,
+    I: Iterator<Item = &'a K>,
+    O: Contains<K> + ?Sized,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|value| !other.contains(value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<'a, K, I, O> FusedIterator for Difference<'a, I, O>
+where
+    K: 'a + ?Sized,
+    I: FusedIterator<Item = &'a K>,
+    O: Contains<K> + ?Sized,
+{
+}
+
+/// An iterator over the elements of a set followed by the elements of another
+/// set which are not in the first.
+///
+/// This struct is created by the `union` methods on archived hash sets.
+pub struct Union<'a, I, J, O: ?Sized> {
+    first: I,
+    second: Difference<'a, J, O>,
+}
+
+impl<'a, I, J, O: ?Sized> Union<'a, I, J, O> {
+    pub(crate) fn new(first: I, second: J, set: &'a O) -> Self {
+        Self {
+            first,
+            second: Difference::new(second, set),
+        }
+    }
+}
+
+impl<'a, K, I, J, O> Iterator for Union<'a, I, J, O>
+where
+    K: 'a + ?Sized,
+    I: Iterator<Item = &'a K>,
+    J: Iterator<Item = &'a K>,
+    O: Contains<K> + ?Sized,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.first.next().or_else(|| self.second.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (min, first_max) = self.first.size_hint();
+        let (_, second_max) = self.second.size_hint();
+        let max = first_max
+            .zip(second_max)
+            .and_then(|(a, b)| a.checked_add(b));
+        (min, max)
+    }
+}
+
+impl<'a, K, I, J, O> FusedIterator for Union<'a, I, J, O>
+where
+    K: 'a + ?Sized,
+    I: FusedIterator<Item = &'a K>,
+    J: FusedIterator<Item = &'a K>,
+    O: Contains<K> + ?Sized,
+{
+}
+
+/// An iterator over the elements of a sorted set which are also in another
+/// sorted set.
+///
+/// This struct is created by the `intersection` method on archived B-tree
+/// sets. Each step advances one of the two sets, so a full iteration takes
+/// linear time.
+pub struct SortedIntersection<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A: Iterator, B: Iterator> SortedIntersection<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            a: a.peekable(),
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<'a, 'b, K, T, A, B> Iterator for SortedIntersection<A, B>
+where
+    K: PartialOrd<T> + 'a + ?Sized,
+    T: 'b + ?Sized,
+    A: Iterator<Item = &'a K>,
+    B: Iterator<Item = &'b T>,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let a = *self.a.peek()?;
+            let b = *self.b.peek()?;
+            match a.partial_cmp(b) {
+                Some(Ordering::Greater) => {
+                    self.b.next();
+                }
+                Some(Ordering::Equal) => {
+                    self.b.next();
+                    return self.a.next();
+                }
+                Some(Ordering::Less) | None => {
+                    self.a.next();
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let a = self.a.size_hint().1;
+        let b = self.b.size_hint().1;
+        (0, a.zip(b).map(|(a, b)| usize::min(a, b)).or(a).or(b))
+    }
+}
+
+impl<'a, 'b, K, T, A, B> FusedIterator for SortedIntersection<A, B>
+where
+    K: PartialOrd<T> + 'a + ?Sized,
+    T: 'b + ?Sized,
+    A: FusedIterator<Item = &'a K>,
+    B: FusedIterator<Item = &'b T>,
+{
+}
+
+/// An iterator over the elements of a sorted set which are not in another
+/// sorted set.
+///
+/// This struct is created by the `difference` method on archived B-tree sets.
+/// Each step advances one of the two sets, so a full iteration takes linear
+/// time.
+pub struct SortedDifference<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A: Iterator, B: Iterator> SortedDifference<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            a: a.peekable(),
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<'a, 'b, K, T, A, B> Iterator for SortedDifference<A, B>
+where
+    K: PartialOrd<T> + 'a + ?Sized,
+    T: 'b + ?Sized,
+    A: Iterator<Item = &'a K>,
+    B: Iterator<Item = &'b T>,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let a = *self.a.peek()?;
+            let Some(&b) = self.b.peek() else {
+                return self.a.next();
+            };
+            match a.partial_cmp(b) {
+                Some(Ordering::Greater) => {
+                    self.b.next();
+                }
+                Some(Ordering::Equal) => {
+                    self.a.next();
+                    self.b.next();
+                }
+                Some(Ordering::Less) | None => return self.a.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.a.size_hint().1)
+    }
+}
+
+impl<'a, 'b, K, T, A, B> FusedIterator for SortedDifference<A, B>
+where
+    K: PartialOrd<T> + 'a + ?Sized,
+    T: 'b + ?Sized,
+    A: FusedIterator<Item = &'a K>,
+    B: FusedIterator<Item = &'b T>,
+{
+}
+
+/// An iterator over the elements of two sorted sets in ascending order without
+/// duplicates.
+///
+/// This struct is created by the `union` method on archived B-tree sets. Each
+/// step advances at least one of the two sets, so a full iteration takes
+/// linear time.
+pub struct SortedUnion<A: Iterator, B: Iterator> {
+    a: Peekable<A>,
+    b: Peekable<B>,
+}
+
+impl<A: Iterator, B: Iterator> SortedUnion<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            a: a.peekable(),
+            b: b.peekable(),
+        }
+    }
+}
+
+impl<'a, K, A, B> Iterator for SortedUnion<A, B>
+where
+    K: Ord + 'a + ?Sized,
+    A: Iterator<Item = &'a K>,
+    B: Iterator<Item = &'a K>,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.peek().copied(), self.b.peek().copied()) {
+            (Some(a), Some(b)) => match a.cmp(b) {
+                Ordering::Less => self.a.next(),
+                Ordering::Greater => self.b.next(),
+                Ordering::Equal => {
+                    self.b.next();
+                    self.a.next()
+                }
+            },
+            (Some(_), None) => self.a.next(),
+            (None, _) => self.b.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_min, a_max) = self.a.size_hint();
+        let (b_min, b_max) = self.b.size_hint();
+        let max = a_max.zip(b_max).and_then(|(a, b)| a.checked_add(b));
+        (usize::max(a_min, b_min), max)
+    }
+}
+
+impl<'a, K, A, B> FusedIterator for SortedUnion<A, B>
+where
+    K: Ord + 'a + ?Sized,
+    A: FusedIterator<Item = &'a K>,
+    B: FusedIterator<Item = &'a K>,
+{
+}
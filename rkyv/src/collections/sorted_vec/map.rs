@@ -0,0 +1,479 @@
+//! An archived map stored as a vector of entries sorted by key.
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    iter::FusedIterator,
+    ops::{Bound, Index, RangeBounds},
+    slice,
+};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    collections::{
+        sorted_vec::vec::{lower_bound, upper_bound},
+        util::{Entry, EntryAdapter},
+    },
+    seal::Seal,
+    ser::{Allocator, Writer},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+/// An archived map whose entries are stored in a single array sorted by key.
+///
+/// Lookups use binary search over the keys. For small to medium read-only
+/// maps, this is denser and faster to scan than
+/// [`ArchivedBTreeMap`](crate::collections::btree_map::ArchivedBTreeMap) nodes
+/// or [`ArchivedHashMap`](crate::collections::swiss_table::ArchivedHashMap)
+/// buckets.
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+pub struct ArchivedSortedMap<K, V> {
+    entries: ArchivedVec<Entry<K, V>>,
+}
+
+impl<K, V> ArchivedSortedMap<K, V> {
+    /// Returns the number of entries in the sorted map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether the sorted map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the entries of the sorted map, sorted by key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys of the sorted map in ascending order.
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Returns an iterator over the values of the sorted map, sorted by key.
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values {
+            inner: self.entries.iter(),
+        }
+    }
+
+    /// Returns the key-value pair at the given index in key order, or `None`
+    /// if the index is out of bounds.
+    pub fn nth(&self, index: usize) -> Option<(&K, &V)> {
+        let entry = self.entries.get(index)?;
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the first key-value pair in the sorted map, or `None` if it is
+    /// empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.nth(0)
+    }
+
+    /// Returns the last key-value pair in the sorted map, or `None` if it is
+    /// empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.nth(self.len().checked_sub(1)?)
+    }
+
+    /// Resolves an archived sorted map from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: SortedMapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedSortedMap { entries } = out);
+        ArchivedVec::resolve_from_len(len, resolver.0, entries);
+    }
+
+    /// Serializes an iterator of key-value pairs which are already sorted by
+    /// key as a sorted map.
+    ///
+    /// The keys must be unique.
+    pub fn serialize_from_sorted_iter<I, BKU, BVU, KU, VU, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (BKU, BVU)> + Clone,
+        BKU: Borrow<KU>,
+        BVU: Borrow<VU>,
+        KU: Serialize<S, Archived = K>,
+        VU: Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        ArchivedVec::<Entry<K, V>>::serialize_from_iter::<
+            EntryAdapter<BKU, BVU, KU, VU>,
+            _,
+            _,
+        >(
+            iter.map(|(key, value)| EntryAdapter::new(key, value)),
+            serializer,
+        )
+        .map(SortedMapResolver)
+    }
+
+    /// Serializes an iterator of key-value pairs in any order as a sorted map.
+    ///
+    /// The entries are sorted by key before serializing them. The keys must be
+    /// unique, and the ordering of `KU` must match the ordering of `K`.
+    pub fn serialize_from_iter<I, BKU, BVU, KU, VU, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (BKU, BVU)>,
+        BKU: Borrow<KU>,
+        BVU: Borrow<VU>,
+        KU: Serialize<S, Archived = K> + Ord,
+        VU: Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        SerVec::with_capacity(serializer, iter.len(), |sorted, serializer| {
+            for entry in iter {
+                sorted.push(entry);
+            }
+            sorted
+                .sort_unstable_by(|(a, _), (b, _)| a.borrow().cmp(b.borrow()));
+
+            Self::serialize_from_sorted_iter::<_, _, _, KU, VU, _>(
+                sorted
+                    .iter()
+                    .map(|(key, value)| (key.borrow(), value.borrow())),
+                serializer,
+            )
+        })?
+    }
+}
+
+impl<K: Ord, V> ArchivedSortedMap<K, V> {
+    fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.entries
+            .binary_search_by(|entry| entry.key.borrow().cmp(key))
+            .ok()
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.nth(self.find(key)?)
+    }
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        Some(self.get_key_value(key)?.1)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the supplied
+    /// key.
+    pub fn get_seal<'a, Q>(this: Seal<'a, Self>, key: &Q) -> Option<Seal<'a, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = this.find(key)?;
+        munge!(let Self { entries } = this);
+        let entries = ArchivedVec::as_slice_seal(entries);
+        let entry = Seal::index(entries, index);
+        munge!(let Entry { value, .. } = entry);
+        Some(value)
+    }
+
+    /// Returns whether the sorted map contains the given key.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Returns the index of the first key which is above the given bound.
+    ///
+    /// - `Bound::Included(x)` returns the index of the first key greater than
+    ///   or equal to `x`.
+    /// - `Bound::Excluded(x)` returns the index of the first key greater than
+    ///   `x`.
+    /// - `Bound::Unbounded` returns 0.
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        lower_bound(&self.entries, bound, |entry| entry.key.borrow())
+    }
+
+    /// Returns the index after the last key which is below the given bound.
+    ///
+    /// - `Bound::Included(x)` returns the index after the last key less than or
+    ///   equal to `x`.
+    /// - `Bound::Excluded(x)` returns the index after the last key less than
+    ///   `x`.
+    /// - `Bound::Unbounded` returns the length of the sorted map.
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        upper_bound(&self.entries, bound, |entry| entry.key.borrow())
+    }
+
+    /// Returns an iterator over the entries of the sorted map whose keys are
+    /// in the given range, sorted by key.
+    ///
+    /// If the start of the range is greater than the end of the range, the
+    /// returned iterator is empty.
+    pub fn range<Q, R>(&self, range: R) -> Iter<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = self.lower_bound(range.start_bound());
+        let end = self.upper_bound(range.end_bound());
+        Iter {
+            inner: self.entries.get(start..end).unwrap_or(&[]).iter(),
+        }
+    }
+
+    /// Returns an iterator over the keys which are in both this sorted map and
+    /// `other`, along with their values in each map.
+    ///
+    /// Both sorted maps are scanned once in ascending key order.
+    pub fn merge_join<'a, W>(
+        &'a self,
+        other: &'a ArchivedSortedMap<K, W>,
+    ) -> MergeJoin<'a, K, V, W> {
+        MergeJoin {
+            left: &self.entries,
+            right: &other.entries,
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for ArchivedSortedMap<K, V>
+where
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, Q> Index<&Q> for ArchivedSortedMap<K, V>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).unwrap()
+    }
+}
+
+impl<K: PartialEq, V: PartialEq> PartialEq for ArchivedSortedMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for ArchivedSortedMap<K, V> {}
+
+/// The resolver for [`ArchivedSortedMap`].
+pub struct SortedMapResolver(VecResolver);
+
+/// An iterator over the key-value pairs of an [`ArchivedSortedMap`].
+pub struct Iter<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| (&entry.key, &entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|entry| (&entry.key, &entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// An iterator over the keys of an [`ArchivedSortedMap`].
+pub struct Keys<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| &entry.key)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// An iterator over the values of an [`ArchivedSortedMap`].
+pub struct Values<'a, K, V> {
+    inner: slice::Iter<'a, Entry<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|entry| &entry.value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|entry| &entry.value)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+/// An iterator over the keys which are in two [`ArchivedSortedMap`]s, along
+/// with their values in each map.
+///
+/// This struct is created by [`ArchivedSortedMap::merge_join`].
+pub struct MergeJoin<'a, K, V, W> {
+    left: &'a [Entry<K, V>],
+    right: &'a [Entry<K, W>],
+}
+
+impl<'a, K: Ord, V, W> Iterator for MergeJoin<'a, K, V, W> {
+    type Item = (&'a K, &'a V, &'a W);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (l, left) = self.left.split_first()?;
+            let (r, right) = self.right.split_first()?;
+            match l.key.cmp(&r.key) {
+                Ordering::Less => self.left = left,
+                Ordering::Greater => self.right = right,
+                Ordering::Equal => {
+                    self.left = left;
+                    self.right = right;
+                    return Some((&l.key, &l.value, &r.value));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(usize::min(self.left.len(), self.right.len())))
+    }
+}
+
+impl<K: Ord, V, W> FusedIterator for MergeJoin<'_, K, V, W> {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::ArchivedSortedMap;
+
+    #[derive(Debug)]
+    struct UnsortedKey {
+        index: usize,
+    }
+
+    impl fmt::Display for UnsortedKey {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "sorted map key at index {} is not greater than the key \
+                 before it",
+                self.index,
+            )
+        }
+    }
+
+    impl Error for UnsortedKey {}
+
+    unsafe impl<C, K, V> Verify<C> for ArchivedSortedMap<K, V>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+        K: Ord,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            // Keys must be strictly increasing so that they are also unique.
+            let entries = self.entries.as_slice();
+            for index in 1..entries.len() {
+                if entries[index].key <= entries[index - 1].key {
+                    fail!(UnsortedKey { index });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
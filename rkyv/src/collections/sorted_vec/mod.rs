@@ -0,0 +1,10 @@
+//! Archived sorted vectors and maps stored as contiguous sorted arrays.
+//!
+//! These collections are serialized with
+//! [`AsSortedVec`](crate::with::AsSortedVec).
+
+pub mod map;
+pub mod vec;
+
+pub use map::{ArchivedSortedMap, SortedMapResolver};
+pub use vec::{ArchivedSortedVec, SortedVecResolver};
@@ -0,0 +1,378 @@
+//! An archived vector which keeps its elements sorted.
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt, hash,
+    iter::FusedIterator,
+    ops::{Bound, Deref, RangeBounds},
+    slice,
+};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    ser::{Allocator, Writer},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+/// An archived vector whose elements are sorted in ascending order.
+///
+/// Lookups use binary search over a single contiguous array. For small to
+/// medium read-only sets, this is denser and faster to scan than
+/// [`ArchivedBTreeSet`](crate::collections::btree_set::ArchivedBTreeSet).
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+pub struct ArchivedSortedVec<T> {
+    inner: ArchivedVec<T>,
+}
+
+impl<T> ArchivedSortedVec<T> {
+    /// Returns the number of elements in the sorted vec.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether the sorted vec is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Gets the elements of the sorted vec as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+
+    /// Returns an iterator over the elements of the sorted vec in ascending
+    /// order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns the first element of the sorted vec, or `None` if it is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    /// Returns the last element of the sorted vec, or `None` if it is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.as_slice().last()
+    }
+
+    /// Resolves an archived sorted vec from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: SortedVecResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedSortedVec { inner } = out);
+        ArchivedVec::resolve_from_len(len, resolver.0, inner);
+    }
+
+    /// Serializes an iterator of elements which are already sorted in
+    /// ascending order as a sorted vec.
+    pub fn serialize_from_sorted_iter<U, I, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        I: ExactSizeIterator + Clone,
+        I::Item: Borrow<U>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        ArchivedVec::serialize_from_iter::<U, _, _>(iter, serializer)
+            .map(SortedVecResolver)
+    }
+
+    /// Serializes an iterator of elements in any order as a sorted vec.
+    ///
+    /// The elements are sorted before serializing them. The ordering of `U`
+    /// must match the ordering of `T`.
+    pub fn serialize_from_iter<U, I, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<SortedVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T> + Ord,
+        I: ExactSizeIterator,
+        I::Item: Borrow<U>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        SerVec::with_capacity(serializer, iter.len(), |sorted, serializer| {
+            for value in iter {
+                sorted.push(value);
+            }
+            sorted.sort_unstable_by(|a, b| a.borrow().cmp(b.borrow()));
+
+            Self::serialize_from_sorted_iter::<U, _, _>(
+                sorted.iter().map(|value| value.borrow()),
+                serializer,
+            )
+        })?
+    }
+}
+
+impl<T: Ord> ArchivedSortedVec<T> {
+    /// Binary searches the sorted vec for the given value.
+    ///
+    /// See [`slice::binary_search`] for details.
+    pub fn binary_search<Q>(&self, value: &Q) -> Result<usize, usize>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.as_slice().binary_search_by(|x| x.borrow().cmp(value))
+    }
+
+    /// Returns the first element equal to the given value, if any.
+    pub fn get<Q>(&self, value: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = self.lower_bound(Bound::Included(value));
+        self.as_slice()
+            .get(index)
+            .filter(|x| Borrow::<Q>::borrow(*x) == value)
+    }
+
+    /// Returns whether the sorted vec contains the given value.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.binary_search(value).is_ok()
+    }
+
+    /// Returns the index of the first element which is above the given
+    /// bound.
+    ///
+    /// - `Bound::Included(x)` returns the index of the first element greater
+    ///   than or equal to `x`.
+    /// - `Bound::Excluded(x)` returns the index of the first element greater
+    ///   than `x`.
+    /// - `Bound::Unbounded` returns 0.
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        lower_bound(self.as_slice(), bound, |x| x.borrow())
+    }
+
+    /// Returns the index after the last element which is below the given
+    /// bound.
+    ///
+    /// - `Bound::Included(x)` returns the index after the last element less
+    ///   than or equal to `x`.
+    /// - `Bound::Excluded(x)` returns the index after the last element less
+    ///   than `x`.
+    /// - `Bound::Unbounded` returns the length of the sorted vec.
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        upper_bound(self.as_slice(), bound, |x| x.borrow())
+    }
+
+    /// Returns the elements of the sorted vec which are in the given range.
+    ///
+    /// If the start of the range is greater than the end of the range, the
+    /// returned slice is empty.
+    pub fn range<Q, R>(&self, range: R) -> &[T]
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = self.lower_bound(range.start_bound());
+        let end = self.upper_bound(range.end_bound());
+        self.as_slice().get(start..end).unwrap_or(&[])
+    }
+
+    /// Returns an iterator over the pairs of equal elements in this sorted vec
+    /// and `other`.
+    ///
+    /// Both sorted vecs are scanned once in ascending order. Equal elements
+    /// are paired up in order, and elements without an equal partner are
+    /// skipped.
+    pub fn merge_join<'a>(
+        &'a self,
+        other: &'a ArchivedSortedVec<T>,
+    ) -> MergeJoin<'a, T> {
+        MergeJoin {
+            left: self.as_slice(),
+            right: other.as_slice(),
+        }
+    }
+}
+
+/// Returns the index of the first element of `slice` which is above `bound`.
+pub(super) fn lower_bound<E, Q>(
+    slice: &[E],
+    bound: Bound<&Q>,
+    key: impl Fn(&E) -> &Q,
+) -> usize
+where
+    Q: Ord + ?Sized,
+{
+    match bound {
+        Bound::Included(value) => slice.partition_point(|x| key(x) < value),
+        Bound::Excluded(value) => slice.partition_point(|x| key(x) <= value),
+        Bound::Unbounded => 0,
+    }
+}
+
+/// Returns the index after the last element of `slice` which is below `bound`.
+pub(super) fn upper_bound<E, Q>(
+    slice: &[E],
+    bound: Bound<&Q>,
+    key: impl Fn(&E) -> &Q,
+) -> usize
+where
+    Q: Ord + ?Sized,
+{
+    match bound {
+        Bound::Included(value) => slice.partition_point(|x| key(x) <= value),
+        Bound::Excluded(value) => slice.partition_point(|x| key(x) < value),
+        Bound::Unbounded => slice.len(),
+    }
+}
+
+impl<T> AsRef<[T]> for ArchivedSortedVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Deref for ArchivedSortedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedSortedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: hash::Hash> hash::Hash for ArchivedSortedVec<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<ArchivedSortedVec<U>>
+    for ArchivedSortedVec<T>
+{
+    fn eq(&self, other: &ArchivedSortedVec<U>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T: Eq> Eq for ArchivedSortedVec<T> {}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for ArchivedSortedVec<T> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+/// The resolver for [`ArchivedSortedVec`].
+pub struct SortedVecResolver(VecResolver);
+
+/// An iterator over the pairs of equal elements in two
+/// [`ArchivedSortedVec`]s.
+///
+/// This struct is created by [`ArchivedSortedVec::merge_join`].
+pub struct MergeJoin<'a, T> {
+    left: &'a [T],
+    right: &'a [T],
+}
+
+impl<'a, T: Ord> Iterator for MergeJoin<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (l, left) = self.left.split_first()?;
+            let (r, right) = self.right.split_first()?;
+            match l.cmp(r) {
+                Ordering::Less => self.left = left,
+                Ordering::Greater => self.right = right,
+                Ordering::Equal => {
+                    self.left = left;
+                    self.right = right;
+                    return Some((l, r));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(usize::min(self.left.len(), self.right.len())))
+    }
+}
+
+impl<T: Ord> FusedIterator for MergeJoin<'_, T> {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::ArchivedSortedVec;
+
+    #[derive(Debug)]
+    struct UnsortedElement {
+        index: usize,
+    }
+
+    impl fmt::Display for UnsortedElement {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "sorted vec element at index {} is less than the element \
+                 before it",
+                self.index,
+            )
+        }
+    }
+
+    impl Error for UnsortedElement {}
+
+    unsafe impl<C, T> Verify<C> for ArchivedSortedVec<T>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+        T: Ord,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let slice = self.as_slice();
+            for index in 1..slice.len() {
+                if slice[index] < slice[index - 1] {
+                    fail!(UnsortedElement { index });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
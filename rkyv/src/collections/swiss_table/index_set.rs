@@ -11,8 +11,9 @@ use munge::munge;
 use rancor::{Fallible, Source};
 
 use crate::{
-    collections::swiss_table::{
-        index_map::Keys, ArchivedIndexMap, IndexMapResolver,
+    collections::{
+        set_ops::{Contains, Difference, Intersection, Union},
+        swiss_table::{index_map::Keys, ArchivedIndexMap, IndexMapResolver},
     },
     hash::FxHasher64,
     ser::{Allocator, Writer},
@@ -87,6 +88,62 @@ impl<K, H: Default + Hasher> ArchivedIndexSet<K, H> {
         self.inner.get_index_of(key)
     }
 
+    /// Returns an iterator over the keys which are in both this set and
+    /// `other`, in this set's order.
+    ///
+    /// `other` may be an archived set, a standard library set, or a closure
+    /// which returns whether a key is in the other set.
+    pub fn intersection<'a, O>(
+        &'a self,
+        other: &'a O,
+    ) -> Intersection<'a, Keys<'a, K, ()>, O>
+    where
+        O: Contains<K> + ?Sized,
+    {
+        Intersection::new(self.iter(), other)
+    }
+
+    /// Returns an iterator over the keys which are in this set but not in
+    /// `other`, in this set's order.
+    pub fn difference<'a, O>(
+        &'a self,
+        other: &'a O,
+    ) -> Difference<'a, Keys<'a, K, ()>, O>
+    where
+        O: Contains<K> + ?Sized,
+    {
+        Difference::new(self.iter(), other)
+    }
+
+    /// Returns an iterator over the keys in this set, followed by the keys
+    /// yielded by `other` which are not in this set.
+    pub fn union<'a, I>(
+        &'a self,
+        other: I,
+    ) -> Union<'a, Keys<'a, K, ()>, I::IntoIter, Self>
+    where
+        I: IntoIterator<Item = &'a K>,
+        K: Hash + Eq,
+    {
+        Union::new(self.iter(), other.into_iter(), self)
+    }
+
+    /// Returns whether every key in this set is also in `other`.
+    pub fn is_subset<O>(&self, other: &O) -> bool
+    where
+        O: Contains<K> + ?Sized,
+    {
+        self.iter().all(|k| other.contains(k))
+    }
+
+    /// Returns whether this set has no keys in common with `other`.
+    pub fn is_disjoint<O>(&self, other: &O) -> bool
+    where
+        O: Contains<K> + ?Sized,
+    {
+        self.intersection(other).next().is_none()
+    }
+
     /// Resolves an archived index map from a given length and parameters.
     pub fn resolve_from_len(
         len: usize,
@@ -130,6 +187,15 @@ impl<K: fmt::Debug, H> fmt::Debug for ArchivedIndexSet<K, H> {
     }
 }
 
+impl<'a, K, H> IntoIterator for &'a ArchivedIndexSet<K, H> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<K: PartialEq, H> PartialEq for ArchivedIndexSet<K, H> {
     fn eq(&self, other: &Self) -> bool {
         self.iter().eq(other.iter())
@@ -1,11 +1,7 @@
 //! Archived hash map implementation using an archived SwissTable.
 
 use core::{
-    borrow::Borrow,
-    fmt,
-    hash::{Hash, Hasher},
-    iter::FusedIterator,
-    marker::PhantomData,
+    borrow::Borrow, fmt, hash::Hash, iter::FusedIterator, marker::PhantomData,
     ops::Index,
 };
 
@@ -17,23 +13,31 @@ use crate::{
         swiss_table::table::{ArchivedHashTable, HashTableResolver, RawIter},
         util::{Entry, EntryAdapter},
     },
-    hash::{hash_value, FxHasher64},
+    hash::{hash_value_with_seed, FxHasher64, TableHasher},
     seal::Seal,
     ser::{Allocator, Writer},
     Place, Portable, Serialize,
 };
 
 /// An archived SwissTable hash map.
+///
+/// Keys are hashed with the table hasher `H`. If `H` is keyed, its seed is
+/// stored after the hash table.
 #[derive(Portable)]
 #[rkyv(crate)]
-#[repr(transparent)]
-#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
-pub struct ArchivedHashMap<K, V, H = FxHasher64> {
+#[repr(C)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+pub struct ArchivedHashMap<K, V, H: TableHasher = FxHasher64> {
     table: ArchivedHashTable<Entry<K, V>>,
+    seed: H::Seed,
     _phantom: PhantomData<H>,
 }
 
-impl<K, V, H> ArchivedHashMap<K, V, H> {
+impl<K, V, H: TableHasher> ArchivedHashMap<K, V, H> {
     /// Returns whether the hash map is empty.
     pub const fn is_empty(&self) -> bool {
         self.table.is_empty()
@@ -90,9 +94,11 @@ impl<K, V, H> ArchivedHashMap<K, V, H> {
             _phantom: PhantomData,
         }
     }
-}
 
-impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
+    fn hash<Q: Hash + ?Sized>(&self, key: &Q) -> u64 {
+        hash_value_with_seed::<Q, H>(key, H::seed(&self.seed))
+    }
+
     /// Returns the key-value pair corresponding to the supplied key using the
     /// given comparison function.
     pub fn get_key_value_with<Q, C>(&self, key: &Q, cmp: C) -> Option<(&K, &V)>
@@ -100,9 +106,8 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         Q: Hash + Eq + ?Sized,
         C: Fn(&Q, &K) -> bool,
     {
-        let entry = self
-            .table
-            .get_with(hash_value::<Q, H>(key), |e| cmp(key, &e.key))?;
+        let entry =
+            self.table.get_with(self.hash(key), |e| cmp(key, &e.key))?;
         Some((&entry.key, &entry.value))
     }
 
@@ -146,12 +151,11 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         Q: Hash + Eq + ?Sized,
         C: Fn(&Q, &K) -> bool,
     {
+        let hash = this.hash(key);
         munge!(let Self { table, .. } = this);
-        let entry = ArchivedHashTable::get_seal_with(
-            table,
-            hash_value::<Q, H>(key),
-            |e| cmp(key, &e.key),
-        )?;
+        let entry = ArchivedHashTable::get_seal_with(table, hash, |e| {
+            cmp(key, &e.key)
+        })?;
         munge!(let Entry { key, value } = entry);
         Some((key.unseal_ref(), value))
     }
@@ -193,6 +197,34 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         Some(Self::get_key_value_seal(this, key)?.1)
     }
 
+    /// Removes the entry corresponding to the supplied key using the given
+    /// comparison function. Returns whether an entry was removed.
+    ///
+    /// The entry is marked as deleted in place and no other entries are moved,
+    /// so the capacity of the hash map is unchanged.
+    pub fn remove_seal_with<Q, C>(this: Seal<'_, Self>, key: &Q, cmp: C) -> bool
+    where
+        Q: Hash + Eq + ?Sized,
+        C: Fn(&Q, &K) -> bool,
+    {
+        let hash = this.hash(key);
+        munge!(let Self { table, .. } = this);
+        ArchivedHashTable::remove_seal_with(table, hash, |e| cmp(key, &e.key))
+    }
+
+    /// Removes the entry corresponding to the supplied key. Returns whether an
+    /// entry was removed.
+    ///
+    /// The entry is marked as deleted in place and no other entries are moved,
+    /// so the capacity of the hash map is unchanged.
+    pub fn remove_seal<Q>(this: Seal<'_, Self>, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        Self::remove_seal_with(this, key, |q, k| q == k.borrow())
+    }
+
     /// Returns whether the hash map contains the given key.
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
@@ -217,14 +249,51 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         S: Fallible + Writer + Allocator + ?Sized,
         S::Error: Source,
     {
-        ArchivedHashTable::<Entry<K, V>>::serialize_from_iter(
+        let seed = H::new_seed();
+        let table = ArchivedHashTable::<Entry<K, V>>::serialize_from_iter(
             iter.clone()
                 .map(|(key, value)| EntryAdapter::new(key, value)),
-            iter.map(|(key, _)| hash_value::<KU, H>(key.borrow())),
+            iter.map(|(key, _)| {
+                hash_value_with_seed::<KU, H>(key.borrow(), seed)
+            }),
             load_factor,
             serializer,
-        )
-        .map(HashMapResolver)
+        )?;
+        Ok(HashMapResolver { table, seed })
+    }
+
+    /// Serializes an iterator of key-value pairs as a hash map by partitioning
+    /// them into chunks of `chunk_len` buckets.
+    ///
+    /// See [`ArchivedHashTable::serialize_from_iter_chunked`] for details.
+    pub fn serialize_from_iter_chunked<I, BKU, BVU, KU, VU, S>(
+        iter: I,
+        load_factor: (usize, usize),
+        chunk_len: usize,
+        serializer: &mut S,
+    ) -> Result<HashMapResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator<Item = (BKU, BVU)>,
+        BKU: Borrow<KU>,
+        BVU: Borrow<VU>,
+        KU: Serialize<S, Archived = K> + Hash + Eq,
+        VU: Serialize<S, Archived = V>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        let seed = H::new_seed();
+        let table =
+            ArchivedHashTable::<Entry<K, V>>::serialize_from_iter_chunked(
+                iter.clone()
+                    .map(|(key, value)| EntryAdapter::new(key, value)),
+                iter.map(|(key, _)| {
+                    hash_value_with_seed::<KU, H>(key.borrow(), seed)
+                }),
+                load_factor,
+                chunk_len,
+                serializer,
+            )?;
+        Ok(HashMapResolver { table, seed })
     }
 
     /// Resolves an archived hash map from a given length and parameters.
@@ -234,17 +303,18 @@ impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
         resolver: HashMapResolver,
         out: Place<Self>,
     ) {
-        munge!(let ArchivedHashMap { table, _phantom: _ } = out);
+        munge!(let ArchivedHashMap { table, seed, _phantom: _ } = out);
         ArchivedHashTable::<Entry<K, V>>::resolve_from_len(
             len,
             load_factor,
-            resolver.0,
+            resolver.table,
             table,
-        )
+        );
+        H::resolve_seed(resolver.seed, seed);
     }
 }
 
-impl<K, V, H> fmt::Debug for ArchivedHashMap<K, V, H>
+impl<K, V, H: TableHasher> fmt::Debug for ArchivedHashMap<K, V, H>
 where
     K: fmt::Debug,
     V: fmt::Debug,
@@ -258,7 +328,7 @@ impl<K, V, H> Eq for ArchivedHashMap<K, V, H>
 where
     K: Hash + Eq,
     V: Eq,
-    H: Default + Hasher,
+    H: TableHasher,
 {
 }
 
@@ -266,7 +336,7 @@ impl<K, V, H> PartialEq for ArchivedHashMap<K, V, H>
 where
     K: Hash + Eq,
     V: PartialEq,
-    H: Default + Hasher,
+    H: TableHasher,
 {
     fn eq(&self, other: &Self) -> bool {
         if self.len() != other.len() {
@@ -283,7 +353,7 @@ impl<K, Q, V, H> Index<&'_ Q> for ArchivedHashMap<K, V, H>
 where
     K: Eq + Hash + Borrow<Q>,
     Q: Eq + Hash + ?Sized,
-    H: Default + Hasher,
+    H: TableHasher,
 {
     type Output = V;
 
@@ -293,12 +363,15 @@ where
 }
 
 /// The resolver for [`ArchivedHashMap`].
-pub struct HashMapResolver(HashTableResolver);
+pub struct HashMapResolver {
+    table: HashTableResolver,
+    seed: u64,
+}
 
 /// An iterator over the key-value pairs of an [`ArchivedHashMap`].
 pub struct Iter<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for Iter<'a, K, V, H> {
@@ -323,7 +396,7 @@ impl<K, V, H> FusedIterator for Iter<'_, K, V, H> {}
 /// An iterator over the mutable key-value pairs of an [`ArchivedHashMap`].
 pub struct IterMut<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for IterMut<'a, K, V, H> {
@@ -348,7 +421,7 @@ impl<K, V, H> FusedIterator for IterMut<'_, K, V, H> {}
 /// An iterator over the keys of an [`ArchivedHashMap`].
 pub struct Keys<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for Keys<'a, K, V, H> {
@@ -373,7 +446,7 @@ impl<K, V, H> FusedIterator for Keys<'_, K, V, H> {}
 /// An iterator over the values of an [`ArchivedHashMap`].
 pub struct Values<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for Values<'a, K, V, H> {
@@ -398,7 +471,7 @@ impl<K, V, H> FusedIterator for Values<'_, K, V, H> {}
 /// An iterator over the mutable values of an [`ArchivedHashMap`].
 pub struct ValuesMut<'a, K, V, H> {
     raw: RawIter<Entry<K, V>>,
-    _phantom: PhantomData<&'a ArchivedHashMap<K, V, H>>,
+    _phantom: PhantomData<(&'a K, &'a V, H)>,
 }
 
 impl<'a, K, V, H> Iterator for ValuesMut<'a, K, V, H> {
@@ -419,3 +492,48 @@ impl<K, V, H> ExactSizeIterator for ValuesMut<'_, K, V, H> {
 }
 
 impl<K, V, H> FusedIterator for ValuesMut<'_, K, V, H> {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::ArchivedHashMap;
+    use crate::hash::TableHasher;
+
+    #[derive(Debug)]
+    struct ProbeLimitExceeded {
+        limit: usize,
+    }
+
+    impl fmt::Display for ProbeLimitExceeded {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "hash map lookups may take more than {} probes",
+                self.limit,
+            )
+        }
+    }
+
+    impl Error for ProbeLimitExceeded {}
+
+    unsafe impl<C, K, V, H> Verify<C> for ArchivedHashMap<K, V, H>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+        H: TableHasher,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            if let Some(limit) = H::PROBE_LIMIT {
+                if !self.table.probes_within(limit) {
+                    fail!(ProbeLimitExceeded { limit });
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
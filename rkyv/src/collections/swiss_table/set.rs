@@ -1,17 +1,17 @@
 //! Archived hash set implementation using an archived SwissTable.
 
-use core::{
-    borrow::Borrow,
-    fmt,
-    hash::{Hash, Hasher},
-};
+use core::{borrow::Borrow, fmt, hash::Hash};
 
 use munge::munge;
 use rancor::{Fallible, Source};
 
 use crate::{
-    collections::swiss_table::map::{ArchivedHashMap, HashMapResolver, Keys},
-    hash::FxHasher64,
+    collections::{
+        set_ops::{Contains, Difference, Intersection, Union},
+        swiss_table::map::{ArchivedHashMap, HashMapResolver, Keys},
+    },
+    hash::{FxHasher64, TableHasher},
+    seal::Seal,
     ser::{Allocator, Writer},
     Place, Portable, Serialize,
 };
@@ -22,11 +22,11 @@ use crate::{
 #[rkyv(crate)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
 #[repr(transparent)]
-pub struct ArchivedHashSet<K, H = FxHasher64> {
+pub struct ArchivedHashSet<K, H: TableHasher = FxHasher64> {
     inner: ArchivedHashMap<K, (), H>,
 }
 
-impl<K, H> ArchivedHashSet<K, H> {
+impl<K, H: TableHasher> ArchivedHashSet<K, H> {
     /// Gets the number of items in the hash set.
     pub const fn len(&self) -> usize {
         self.inner.len()
@@ -41,9 +41,7 @@ impl<K, H> ArchivedHashSet<K, H> {
     pub fn iter(&self) -> Keys<K, (), H> {
         self.inner.keys()
     }
-}
 
-impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
     /// Gets the key corresponding to the given key in the hash set.
     pub fn get<Q>(&self, k: &Q) -> Option<&K>
     where
@@ -62,6 +60,76 @@ impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
         self.inner.contains_key(k)
     }
 
+    /// Removes the given key from the hash set. Returns whether the key was
+    /// removed.
+    ///
+    /// The key is marked as deleted in place and no other keys are moved, so
+    /// the capacity of the hash set is unchanged.
+    pub fn remove_seal<Q>(this: Seal<'_, Self>, k: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        munge!(let Self { inner } = this);
+        ArchivedHashMap::remove_seal(inner, k)
+    }
+
+    /// Returns an iterator over the keys which are in both this set and
+    /// `other`.
+    ///
+    /// `other` may be an archived set, a standard library set, or a closure
+    /// which returns whether a key is in the other set.
+    pub fn intersection<'a, O>(
+        &'a self,
+        other: &'a O,
+    ) -> Intersection<'a, Keys<'a, K, (), H>, O>
+    where
+        O: Contains<K> + ?Sized,
+    {
+        Intersection::new(self.iter(), other)
+    }
+
+    /// Returns an iterator over the keys which are in this set but not in
+    /// `other`.
+    pub fn difference<'a, O>(
+        &'a self,
+        other: &'a O,
+    ) -> Difference<'a, Keys<'a, K, (), H>, O>
+    where
+        O: Contains<K> + ?Sized,
+    {
+        Difference::new(self.iter(), other)
+    }
+
+    /// Returns an iterator over the keys in this set, followed by the keys
+    /// yielded by `other` which are not in this set.
+    pub fn union<'a, I>(
+        &'a self,
+        other: I,
+    ) -> Union<'a, Keys<'a, K, (), H>, I::IntoIter, Self>
+    where
+        I: IntoIterator<Item = &'a K>,
+        K: Hash + Eq,
+    {
+        Union::new(self.iter(), other.into_iter(), self)
+    }
+
+    /// Returns whether every key in this set is also in `other`.
+    pub fn is_subset<O>(&self, other: &O) -> bool
+    where
+        O: Contains<K> + ?Sized,
+    {
+        self.iter().all(|k| other.contains(k))
+    }
+
+    /// Returns whether this set has no keys in common with `other`.
+    pub fn is_disjoint<O>(&self, other: &O) -> bool
+    where
+        O: Contains<K> + ?Sized,
+    {
+        self.intersection(other).next().is_none()
+    }
+
     /// Resolves an archived hash set from the given length and parameters.
     pub fn resolve_from_len(
         len: usize,
@@ -97,21 +165,61 @@ impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
             >(iter.map(|x| (x, ())), load_factor, serializer)?,
         ))
     }
+
+    /// Serializes an iterator of keys as a hash set by partitioning them into
+    /// chunks of `chunk_len` buckets.
+    ///
+    /// See [`ArchivedHashMap::serialize_from_iter_chunked`] for details.
+    pub fn serialize_from_iter_chunked<I, KU, S>(
+        iter: I,
+        load_factor: (usize, usize),
+        chunk_len: usize,
+        serializer: &mut S,
+    ) -> Result<HashSetResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator,
+        I::Item: Borrow<KU>,
+        KU: Serialize<S, Archived = K> + Hash + Eq,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        Ok(HashSetResolver(
+            ArchivedHashMap::<K, (), H>::serialize_from_iter_chunked::<
+                _,
+                _,
+                (),
+                _,
+                _,
+                _,
+            >(
+                iter.map(|x| (x, ())), load_factor, chunk_len, serializer
+            )?,
+        ))
+    }
 }
 
-impl<K: fmt::Debug, H> fmt::Debug for ArchivedHashSet<K, H> {
+impl<K: fmt::Debug, H: TableHasher> fmt::Debug for ArchivedHashSet<K, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
     }
 }
 
-impl<K: Hash + Eq, H: Hasher + Default> PartialEq for ArchivedHashSet<K, H> {
+impl<'a, K, H: TableHasher> IntoIterator for &'a ArchivedHashSet<K, H> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, (), H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: Hash + Eq, H: TableHasher> PartialEq for ArchivedHashSet<K, H> {
     fn eq(&self, other: &Self) -> bool {
         self.inner == other.inner
     }
 }
 
-impl<K: Hash + Eq, H: Hasher + Default> Eq for ArchivedHashSet<K, H> {}
+impl<K: Hash + Eq, H: TableHasher> Eq for ArchivedHashSet<K, H> {}
 
 /// The resolver for archived hash sets.
 pub struct HashSetResolver(HashMapResolver);
@@ -3,19 +3,22 @@
 //!
 //! Notable differences from other implementations:
 //!
-//! - The number of control bytes is rounded up to a maximum group width (16)
+//! - The number of control bytes is rounded up to the group width of the table
 //!   instead of the next power of two. This reduces the number of empty buckets
 //!   on the wire. Since this collection is immutable after writing, we'll never
 //!   benefit from having more buckets than we need.
+//! - The group width is stored in the table and may be 16, 32, or 64. Tables
+//!   are written with the widest group the serializing target can probe with a
+//!   single SIMD read, but can be read on any target.
 //! - Because the bucket count is not a power of two, the triangular probing
 //!   sequence simply skips any indices larger than the actual size of the
 //!   buckets array.
 //! - Instead of the final control bytes always being marked EMPTY, the last
 //!   control bytes repeat the first few. This helps reduce the number of
 //!   lookups when probing at the end of the control bytes.
-//! - Because the available SIMD group width may be less than the maximum group
-//!   width, each probe reads N groups before striding where N is the maximum
-//!   group width divided by the SIMD group width.
+//! - Because the available SIMD group width may be less than the group width of
+//!   the table, each probe reads N groups before striding where N is the group
+//!   width of the table divided by the SIMD group width.
 
 use core::{
     alloc::Layout,
@@ -36,7 +39,10 @@ use crate::{
     primitive::{ArchivedUsize, FixedUsize},
     seal::Seal,
     ser::{Allocator, Writer, WriterExt},
-    simd::{Bitmask, Group, MAX_GROUP_WIDTH},
+    simd::{
+        Bitmask, Group, ProbeGroup, WideGroup, MAX_GROUP_WIDTH,
+        MIN_GROUP_WIDTH, NATIVE_GROUP_WIDTH,
+    },
     util::SerVec,
     Archive as _, Place, Portable, RawRelPtr, Serialize,
 };
@@ -54,9 +60,20 @@ pub struct ArchivedHashTable<T> {
     ptr: RawRelPtr,
     len: ArchivedUsize,
     cap: ArchivedUsize,
+    group_width: u8,
     _phantom: PhantomData<T>,
 }
 
+/// The control byte of a bucket which has never been filled.
+///
+/// Lookups stop probing once they reach a group with an EMPTY bucket.
+const EMPTY: u8 = 0xff;
+
+/// The control byte of a bucket which has been removed.
+///
+/// Unlike EMPTY buckets, DELETED buckets do not end a probe sequence.
+const DELETED: u8 = 0x80;
+
 #[inline]
 fn h1(hash: u64) -> usize {
     hash as usize
@@ -74,8 +91,8 @@ struct ProbeSeq {
 
 impl ProbeSeq {
     #[inline]
-    fn move_next(&mut self, bucket_mask: usize) {
-        self.stride += MAX_GROUP_WIDTH;
+    fn move_next(&mut self, group_width: usize, bucket_mask: usize) {
+        self.stride += group_width;
         self.pos += self.stride;
         self.pos &= bucket_mask;
     }
@@ -129,11 +146,7 @@ impl<T> ArchivedHashTable<T> {
     /// # Safety
     ///
     /// `this` must point to a valid `ArchivedHashTable`
-    unsafe fn get_entry_raw<C>(
-        this: *mut Self,
-        hash: u64,
-        cmp: C,
-    ) -> Option<NonNull<T>>
+    unsafe fn find_raw<C>(this: *mut Self, hash: u64, cmp: C) -> Option<usize>
     where
         C: Fn(&T) -> bool,
     {
@@ -142,9 +155,31 @@ impl<T> ArchivedHashTable<T> {
             return None;
         }
 
+        let group_width = unsafe { (*this).group_width() };
+        if WideGroup::WIDTH > Group::WIDTH && group_width >= WideGroup::WIDTH {
+            unsafe { Self::probe::<WideGroup, C>(this, hash, cmp) }
+        } else {
+            unsafe { Self::probe::<Group, C>(this, hash, cmp) }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// - `this` must point to a valid `ArchivedHashTable`
+    /// - The hash table must not be empty
+    /// - `G::WIDTH` must not be greater than the group width of the table
+    unsafe fn probe<G: ProbeGroup, C>(
+        this: *mut Self,
+        hash: u64,
+        cmp: C,
+    ) -> Option<usize>
+    where
+        C: Fn(&T) -> bool,
+    {
         let capacity = unsafe { (*this).capacity() };
-        let probe_cap = Self::probe_cap(capacity);
-        let control_count = Self::control_count(probe_cap);
+        let group_width = unsafe { (*this).group_width() };
+        let probe_cap = Self::probe_cap(capacity, group_width);
+        let control_count = Self::control_count(probe_cap, group_width);
 
         let h2_hash = h2(hash);
         let mut probe_seq = Self::probe_seq(hash, capacity);
@@ -154,11 +189,10 @@ impl<T> ArchivedHashTable<T> {
         loop {
             let mut any_empty = false;
 
-            for i in 0..MAX_GROUP_WIDTH / Group::WIDTH {
-                let pos = probe_seq.pos + i * Group::WIDTH;
+            for i in 0..group_width / G::WIDTH {
+                let pos = probe_seq.pos + i * G::WIDTH;
 
-                let group =
-                    unsafe { Group::read(Self::control_raw(this, pos)) };
+                let group = unsafe { G::read(Self::control_raw(this, pos)) };
 
                 for bit in group.match_byte(h2_hash) {
                     let index = (pos + bit) % capacity;
@@ -167,12 +201,12 @@ impl<T> ArchivedHashTable<T> {
 
                     // Opt: These can be marked as likely true on nightly.
                     if cmp(bucket) {
-                        return Some(bucket_ptr);
+                        return Some(index);
                     }
                 }
 
                 // Opt: These can be marked as likely true on nightly.
-                any_empty = any_empty || group.match_empty().any_bit_set();
+                any_empty = any_empty || group.any_empty();
             }
 
             if any_empty {
@@ -180,7 +214,7 @@ impl<T> ArchivedHashTable<T> {
             }
 
             loop {
-                probe_seq.move_next(bucket_mask);
+                probe_seq.move_next(group_width, bucket_mask);
                 if probe_seq.pos < probe_cap {
                     break;
                 }
@@ -194,8 +228,8 @@ impl<T> ArchivedHashTable<T> {
         C: Fn(&T) -> bool,
     {
         let this = (self as *const Self).cast_mut();
-        let ptr = unsafe { Self::get_entry_raw(this, hash, |e| cmp(e))? };
-        Some(unsafe { ptr.as_ref() })
+        let index = unsafe { Self::find_raw(this, hash, |e| cmp(e))? };
+        Some(unsafe { Self::bucket_raw(this, index).as_ref() })
     }
 
     /// Returns the mutable key-value pair corresponding to the supplied key.
@@ -207,10 +241,100 @@ impl<T> ArchivedHashTable<T> {
     where
         C: Fn(&T) -> bool,
     {
-        let mut ptr = unsafe {
-            Self::get_entry_raw(this.unseal_unchecked(), hash, |e| cmp(e))?
+        let this = unsafe { this.unseal_unchecked() };
+        let index = unsafe { Self::find_raw(this, hash, |e| cmp(e))? };
+        Some(Seal::new(unsafe { Self::bucket_raw(this, index).as_mut() }))
+    }
+
+    /// Removes the entry corresponding to the supplied hash and comparison
+    /// function by marking its bucket as deleted. Returns whether an entry was
+    /// removed.
+    ///
+    /// This only rewrites the control bytes and length of the hash table. The
+    /// removed entry is left in place, and no other entries are moved.
+    pub fn remove_seal_with<C>(this: Seal<'_, Self>, hash: u64, cmp: C) -> bool
+    where
+        C: Fn(&T) -> bool,
+    {
+        let this = unsafe { this.unseal_unchecked() };
+        let Some(index) = (unsafe { Self::find_raw(this, hash, |e| cmp(e)) })
+        else {
+            return false;
         };
-        Some(Seal::new(unsafe { ptr.as_mut() }))
+
+        let capacity = this.capacity();
+        let group_width = this.group_width();
+        let control_count = Self::control_count(
+            Self::probe_cap(capacity, group_width),
+            group_width,
+        );
+
+        // SAFETY: `find_raw` only returns indices of full buckets, which are
+        // less than the capacity of the table. The wrapped control byte for
+        // `index` is within the control bytes if it exists.
+        unsafe {
+            Self::control_raw(this, index).cast_mut().write(DELETED);
+            if index < control_count - capacity {
+                Self::control_raw(this, capacity + index)
+                    .cast_mut()
+                    .write(DELETED);
+            }
+        }
+        this.len = ArchivedUsize::from_native(this.len() as FixedUsize - 1);
+
+        true
+    }
+
+    /// Returns whether every lookup in the hash table takes at most `limit`
+    /// probes.
+    ///
+    /// Lookups stop probing once they reach a group with an empty bucket, so
+    /// this checks every possible starting position for a probe sequence.
+    pub fn probes_within(&self, limit: usize) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let this = (self as *const Self).cast_mut();
+        let capacity = self.capacity();
+        let group_width = self.group_width();
+        let probe_cap = Self::probe_cap(capacity, group_width);
+        let bucket_mask =
+            Self::bucket_mask(Self::control_count(probe_cap, group_width));
+
+        for start in 0..capacity {
+            let mut probe_seq = ProbeSeq {
+                pos: start,
+                stride: 0,
+            };
+
+            let mut probes = 0;
+            loop {
+                probes += 1;
+                if probes > limit {
+                    return false;
+                }
+
+                let any_empty = (0..group_width / Group::WIDTH).any(|i| {
+                    let pos = probe_seq.pos + i * Group::WIDTH;
+                    let group =
+                        unsafe { Group::read(Self::control_raw(this, pos)) };
+                    group.match_empty().any_bit_set()
+                });
+                if any_empty {
+                    break;
+                }
+
+                loop {
+                    probe_seq.move_next(group_width, bucket_mask);
+                    if probe_seq.pos < probe_cap {
+                        break;
+                    }
+                }
+            }
+        }
+
+        true
     }
 
     /// Returns whether the hash table is empty.
@@ -228,6 +352,12 @@ impl<T> ArchivedHashTable<T> {
         self.cap.to_native() as usize
     }
 
+    /// Returns the number of control bytes read by each probe of the hash
+    /// table.
+    pub fn group_width(&self) -> usize {
+        self.group_width as usize
+    }
+
     /// # Safety
     ///
     /// This hash table must not be empty.
@@ -285,12 +415,27 @@ impl<T> ArchivedHashTable<T> {
         }
     }
 
-    fn probe_cap(capacity: usize) -> usize {
-        capacity.next_multiple_of(MAX_GROUP_WIDTH)
+    fn probe_cap(capacity: usize, group_width: usize) -> usize {
+        capacity.next_multiple_of(group_width)
+    }
+
+    fn control_count(probe_cap: usize, group_width: usize) -> usize {
+        probe_cap + group_width - 1
     }
 
-    fn control_count(probe_cap: usize) -> usize {
-        probe_cap + MAX_GROUP_WIDTH - 1
+    fn is_valid_group_width(group_width: usize) -> bool {
+        group_width.is_power_of_two()
+            && (MIN_GROUP_WIDTH..=MAX_GROUP_WIDTH).contains(&group_width)
+    }
+
+    /// Returns the widest native group width which keeps the number of
+    /// buckets added by rounding up the capacity under an eighth of `len`.
+    fn default_group_width(len: usize) -> usize {
+        let mut group_width = NATIVE_GROUP_WIDTH;
+        while group_width > MIN_GROUP_WIDTH && group_width * 8 > len {
+            group_width /= 2;
+        }
+        group_width
     }
 
     #[allow(dead_code)]
@@ -304,6 +449,10 @@ impl<T> ArchivedHashTable<T> {
     }
 
     /// Serializes an iterator of items as a hash table.
+    ///
+    /// The hash table is written with the widest group width that the target
+    /// can probe with a single SIMD read, unless the table is small enough
+    /// that a narrower group width would save space.
     pub fn serialize_from_iter<I, U, H, S>(
         items: I,
         hashes: H,
@@ -318,52 +467,46 @@ impl<T> ArchivedHashTable<T> {
         S: Fallible + Writer + Allocator + ?Sized,
         S::Error: Source,
     {
-        #[derive(Debug)]
-        struct InvalidLoadFactor {
-            numerator: usize,
-            denominator: usize,
-        }
-
-        impl fmt::Display for InvalidLoadFactor {
-            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                write!(
-                    f,
-                    "invalid load factor {} / {}, load factor must be a \
-                     fraction in the range (0, 1]",
-                    self.numerator, self.denominator
-                )
-            }
-        }
-
-        impl Error for InvalidLoadFactor {}
+        let group_width = Self::default_group_width(items.len());
+        Self::serialize_from_iter_with_group_width(
+            items,
+            hashes,
+            load_factor,
+            group_width,
+            serializer,
+        )
+    }
 
-        if load_factor.0 == 0
-            || load_factor.1 == 0
-            || load_factor.0 > load_factor.1
-        {
-            fail!(InvalidLoadFactor {
-                numerator: load_factor.0,
-                denominator: load_factor.1,
-            });
-        }
+    /// Serializes an iterator of items as a hash table with the given group
+    /// width.
+    ///
+    /// The group width must be 16, 32, or 64.
+    pub fn serialize_from_iter_with_group_width<I, U, H, S>(
+        items: I,
+        hashes: H,
+        load_factor: (usize, usize),
+        group_width: usize,
+        serializer: &mut S,
+    ) -> Result<HashTableResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator,
+        I::Item: Borrow<U>,
+        U: Serialize<S, Archived = T>,
+        H: ExactSizeIterator<Item = u64>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        Self::check_parameters(load_factor, group_width)?;
 
         let len = items.len();
 
         if len == 0 {
-            let count = items.count();
-            if count != 0 {
-                fail!(IteratorLengthMismatch {
-                    expected: 0,
-                    actual: count,
-                });
-            }
-
-            return Ok(HashTableResolver { pos: 0 });
+            return Self::serialize_empty(items);
         }
 
         let capacity = Self::capacity_from_len(len, load_factor);
-        let probe_cap = Self::probe_cap(capacity);
-        let control_count = Self::control_count(probe_cap);
+        let probe_cap = Self::probe_cap(capacity, group_width);
+        let control_count = Self::control_count(probe_cap, group_width);
 
         // Determine hash locations for all items
         SerVec::with_capacity(
@@ -380,56 +523,16 @@ impl<T> ArchivedHashTable<T> {
                     serializer,
                     control_count,
                     |control_bytes, serializer| {
-                        // Initialize all control bytes to EMPTY (0xFF)
-                        unsafe {
-                            control_bytes
-                                .as_mut_ptr()
-                                .write_bytes(0xff, control_bytes.capacity());
-                            control_bytes.set_len(control_bytes.capacity());
-                        }
-
-                        let bucket_mask = Self::bucket_mask(control_count);
+                        Self::init_control_bytes(control_bytes);
 
                         for (item, hash) in items.zip(hashes) {
-                            let h2_hash = h2(hash);
-                            let mut probe_seq = Self::probe_seq(hash, capacity);
-
-                            'insert: loop {
-                                for i in 0..MAX_GROUP_WIDTH / Group::WIDTH {
-                                    let pos = probe_seq.pos + i * Group::WIDTH;
-                                    let group = unsafe {
-                                        Group::read(
-                                            control_bytes.as_ptr().add(pos),
-                                        )
-                                    };
-
-                                    if let Some(bit) =
-                                        group.match_empty().lowest_set_bit()
-                                    {
-                                        let index = (pos + bit) % capacity;
-
-                                        // Update control byte
-                                        control_bytes[index] = h2_hash;
-                                        // If it's near the beginning of the
-                                        // control bytes,
-                                        // update the wraparound control byte
-                                        if index < (control_count - capacity) {
-                                            control_bytes[capacity + index] =
-                                                h2_hash;
-                                        }
-
-                                        ordered_items[index] = Some(item);
-                                        break 'insert;
-                                    }
-                                }
-
-                                loop {
-                                    probe_seq.move_next(bucket_mask);
-                                    if probe_seq.pos < probe_cap {
-                                        break;
-                                    }
-                                }
-                            }
+                            let index = Self::insert_control_byte(
+                                control_bytes,
+                                capacity,
+                                group_width,
+                                hash,
+                            );
+                            ordered_items[index] = Some(item);
                         }
 
                         let mut zeros = MaybeUninit::<T>::uninit();
@@ -476,6 +579,7 @@ impl<T> ArchivedHashTable<T> {
 
                                 Ok(HashTableResolver {
                                     pos: pos as FixedUsize,
+                                    group_width: group_width as u8,
                                 })
                             },
                         )?
@@ -485,6 +589,337 @@ impl<T> ArchivedHashTable<T> {
         )?
     }
 
+    /// Serializes an iterator of items as a hash table by partitioning them
+    /// into chunks of `chunk_len` buckets.
+    ///
+    /// [`serialize_from_iter`](Self::serialize_from_iter) places the items in a
+    /// buffer with a slot for every bucket of the hash table. This instead
+    /// partitions the items into one run per chunk of `chunk_len` buckets in a
+    /// single pass, then orders each run within a buffer of `chunk_len` slots.
+    /// The items and their resolvers are still buffered until their entries are
+    /// written.
+    ///
+    /// The archived hash table is identical to the one written by
+    /// `serialize_from_iter`.
+    pub fn serialize_from_iter_chunked<I, U, H, S>(
+        items: I,
+        hashes: H,
+        load_factor: (usize, usize),
+        chunk_len: usize,
+        serializer: &mut S,
+    ) -> Result<HashTableResolver, S::Error>
+    where
+        I: Clone + ExactSizeIterator,
+        I::Item: Borrow<U>,
+        U: Serialize<S, Archived = T>,
+        H: ExactSizeIterator<Item = u64>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        let len = items.len();
+        let group_width = Self::default_group_width(len);
+        Self::check_parameters(load_factor, group_width)?;
+
+        if len == 0 {
+            return Self::serialize_empty(items);
+        }
+
+        let capacity = Self::capacity_from_len(len, load_factor);
+        let probe_cap = Self::probe_cap(capacity, group_width);
+        let control_count = Self::control_count(probe_cap, group_width);
+        let chunk_len = chunk_len.clamp(1, capacity);
+        let chunk_count = capacity.div_ceil(chunk_len);
+
+        SerVec::<u8>::with_capacity(
+            serializer,
+            control_count,
+            |control_bytes, serializer| {
+                Self::init_control_bytes(control_bytes);
+
+                SerVec::<FixedUsize>::with_capacity(
+                    serializer,
+                    len,
+                    |item_buckets, serializer| {
+                        SerVec::<usize>::with_capacity(
+                            serializer,
+                            chunk_count + 1,
+                            |run_ends, serializer| {
+                                for _ in 0..=chunk_count {
+                                    unsafe {
+                                        run_ends.push_unchecked(0);
+                                    }
+                                }
+
+                                // Determine hash locations for all items and
+                                // count the items in each chunk
+                                for hash in hashes.take(len) {
+                                    let index = Self::insert_control_byte(
+                                        control_bytes,
+                                        capacity,
+                                        group_width,
+                                        hash,
+                                    );
+                                    item_buckets.push(index as FixedUsize);
+                                    run_ends[index / chunk_len + 1] += 1;
+                                }
+
+                                // Each run starts where the previous one ends
+                                for i in 1..=chunk_count {
+                                    run_ends[i] += run_ends[i - 1];
+                                }
+
+                                SerVec::with_capacity(
+                                    serializer,
+                                    len,
+                                    |runs, serializer| {
+                                        // SAFETY: `MaybeUninit` does not
+                                        // require initialization.
+                                        unsafe {
+                                            runs.set_len(len);
+                                        }
+
+                                        // Partition the items into runs
+                                        let mut placed = 0;
+                                        for (item, &bucket) in
+                                            items.zip(item_buckets.iter())
+                                        {
+                                            let bucket = bucket as usize;
+                                            let end = &mut run_ends
+                                                [bucket / chunk_len];
+                                            runs[*end] = MaybeUninit::new((
+                                                bucket, item,
+                                            ));
+                                            *end += 1;
+                                            placed += 1;
+                                        }
+                                        if placed != len {
+                                            fail!(IteratorLengthMismatch {
+                                                expected: len,
+                                                actual: placed,
+                                            });
+                                        }
+
+                                        Self::serialize_runs(
+                                            runs,
+                                            &run_ends[..chunk_count],
+                                            capacity,
+                                            chunk_len,
+                                            serializer,
+                                        )?;
+
+                                        let pos = serializer.pos();
+                                        serializer.write(control_bytes)?;
+
+                                        Ok(HashTableResolver {
+                                            pos: pos as FixedUsize,
+                                            group_width: group_width as u8,
+                                        })
+                                    },
+                                )?
+                            },
+                        )?
+                    },
+                )?
+            },
+        )?
+    }
+
+    /// Serializes the items in the partitioned `runs` in bucket order, then
+    /// writes their entries.
+    ///
+    /// Every element of `runs` must be initialized, and each run must only
+    /// contain items in the buckets of its chunk.
+    fn serialize_runs<B, U, S>(
+        runs: &[MaybeUninit<(usize, B)>],
+        run_ends: &[usize],
+        capacity: usize,
+        chunk_len: usize,
+        serializer: &mut S,
+    ) -> Result<(), S::Error>
+    where
+        B: Borrow<U>,
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Writer + Allocator + ?Sized,
+        S::Error: Source,
+    {
+        SerVec::with_capacity(serializer, chunk_len, |slots, serializer| {
+            SerVec::with_capacity(
+                serializer,
+                runs.len(),
+                |resolvers, serializer| {
+                    for chunk in 0..run_ends.len() {
+                        Self::fill_slots(
+                            slots, runs, run_ends, chunk, capacity, chunk_len,
+                        );
+                        for &i in slots.iter().flatten() {
+                            let (_, item) =
+                                unsafe { runs[i].assume_init_ref() };
+                            resolvers
+                                .push(item.borrow().serialize(serializer)?);
+                        }
+                    }
+
+                    serializer.align_for::<T>()?;
+
+                    let mut zeros = MaybeUninit::<T>::uninit();
+                    unsafe {
+                        zeros.as_mut_ptr().write_bytes(0, 1);
+                    }
+                    let zeros = unsafe {
+                        from_raw_parts(
+                            zeros.as_ptr().cast::<u8>(),
+                            size_of::<T>(),
+                        )
+                    };
+
+                    let mut resolvers = resolvers.drain().rev();
+                    for chunk in (0..run_ends.len()).rev() {
+                        Self::fill_slots(
+                            slots, runs, run_ends, chunk, capacity, chunk_len,
+                        );
+                        for slot in slots.iter().rev() {
+                            if let Some(i) = slot {
+                                // SAFETY: Every item is in exactly one slot,
+                                // so each item is only read once.
+                                let (_, item) =
+                                    unsafe { runs[*i].assume_init_read() };
+                                unsafe {
+                                    serializer.resolve_aligned(
+                                        item.borrow(),
+                                        resolvers.next().unwrap(),
+                                    )?;
+                                }
+                            } else {
+                                serializer.write(zeros)?;
+                            }
+                        }
+                    }
+
+                    Ok(())
+                },
+            )?
+        })?
+    }
+
+    /// Fills `slots` with the index in `runs` of the item in each bucket of
+    /// `chunk`.
+    fn fill_slots<B>(
+        slots: &mut SerVec<Option<usize>>,
+        runs: &[MaybeUninit<(usize, B)>],
+        run_ends: &[usize],
+        chunk: usize,
+        capacity: usize,
+        chunk_len: usize,
+    ) {
+        let start = chunk * chunk_len;
+        let end = usize::min(start + chunk_len, capacity);
+        let run_start = if chunk == 0 { 0 } else { run_ends[chunk - 1] };
+
+        slots.clear();
+        for _ in start..end {
+            unsafe {
+                slots.push_unchecked(None);
+            }
+        }
+        for (i, entry) in runs[run_start..run_ends[chunk]].iter().enumerate() {
+            let (bucket, _) = unsafe { entry.assume_init_ref() };
+            slots[bucket - start] = Some(run_start + i);
+        }
+    }
+
+    fn check_parameters<E: Source>(
+        load_factor: (usize, usize),
+        group_width: usize,
+    ) -> Result<(), E> {
+        if load_factor.0 == 0
+            || load_factor.1 == 0
+            || load_factor.0 > load_factor.1
+        {
+            fail!(InvalidLoadFactor {
+                numerator: load_factor.0,
+                denominator: load_factor.1,
+            });
+        }
+
+        if !Self::is_valid_group_width(group_width) {
+            fail!(InvalidGroupWidth { group_width });
+        }
+
+        Ok(())
+    }
+
+    fn serialize_empty<I: Iterator, E: Source>(
+        items: I,
+    ) -> Result<HashTableResolver, E> {
+        let count = items.count();
+        if count != 0 {
+            fail!(IteratorLengthMismatch {
+                expected: 0,
+                actual: count,
+            });
+        }
+
+        Ok(HashTableResolver {
+            pos: 0,
+            group_width: MIN_GROUP_WIDTH as u8,
+        })
+    }
+
+    fn init_control_bytes(control_bytes: &mut SerVec<u8>) {
+        // Initialize all control bytes to EMPTY (0xFF)
+        unsafe {
+            control_bytes
+                .as_mut_ptr()
+                .write_bytes(EMPTY, control_bytes.capacity());
+            control_bytes.set_len(control_bytes.capacity());
+        }
+    }
+
+    /// Marks the first empty bucket in the probe sequence of `hash` as full
+    /// and returns its index.
+    fn insert_control_byte(
+        control_bytes: &mut [u8],
+        capacity: usize,
+        group_width: usize,
+        hash: u64,
+    ) -> usize {
+        let control_count = control_bytes.len();
+        let probe_cap = Self::probe_cap(capacity, group_width);
+        let bucket_mask = Self::bucket_mask(control_count);
+
+        let h2_hash = h2(hash);
+        let mut probe_seq = Self::probe_seq(hash, capacity);
+
+        loop {
+            for i in 0..group_width / Group::WIDTH {
+                let pos = probe_seq.pos + i * Group::WIDTH;
+                let group =
+                    unsafe { Group::read(control_bytes.as_ptr().add(pos)) };
+
+                if let Some(bit) = group.match_empty().lowest_set_bit() {
+                    let index = (pos + bit) % capacity;
+
+                    // Update control byte
+                    control_bytes[index] = h2_hash;
+                    // If it's near the beginning of the control bytes, update
+                    // the wraparound control byte
+                    if index < (control_count - capacity) {
+                        control_bytes[capacity + index] = h2_hash;
+                    }
+
+                    return index;
+                }
+            }
+
+            loop {
+                probe_seq.move_next(group_width, bucket_mask);
+                if probe_seq.pos < probe_cap {
+                    break;
+                }
+            }
+        }
+    }
+
     /// Resolves an archived hash table from a given length and parameters.
     pub fn resolve_from_len(
         len: usize,
@@ -492,7 +927,9 @@ impl<T> ArchivedHashTable<T> {
         resolver: HashTableResolver,
         out: Place<Self>,
     ) {
-        munge!(let Self { ptr, len: out_len, cap, _phantom: _ } = out);
+        munge!(
+            let Self { ptr, len: out_len, cap, group_width, _phantom: _ } = out
+        );
 
         if len == 0 {
             RawRelPtr::emplace_invalid(ptr);
@@ -505,6 +942,8 @@ impl<T> ArchivedHashTable<T> {
         let capacity = Self::capacity_from_len(len, load_factor);
         capacity.resolve((), cap);
 
+        resolver.group_width.resolve((), group_width);
+
         // PhantomData doesn't need to be initialized
     }
 }
@@ -512,8 +951,46 @@ impl<T> ArchivedHashTable<T> {
 /// The resolver for [`ArchivedHashTable`].
 pub struct HashTableResolver {
     pos: FixedUsize,
+    group_width: u8,
+}
+
+#[derive(Debug)]
+struct InvalidLoadFactor {
+    numerator: usize,
+    denominator: usize,
+}
+
+impl fmt::Display for InvalidLoadFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid load factor {} / {}, load factor must be a fraction in \
+             the range (0, 1]",
+            self.numerator, self.denominator
+        )
+    }
+}
+
+impl Error for InvalidLoadFactor {}
+
+#[derive(Debug)]
+struct InvalidGroupWidth {
+    group_width: usize,
 }
 
+impl fmt::Display for InvalidGroupWidth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid group width {}, group width must be a power of two from \
+             {} to {}",
+            self.group_width, MIN_GROUP_WIDTH, MAX_GROUP_WIDTH,
+        )
+    }
+}
+
+impl Error for InvalidGroupWidth {}
+
 struct ControlIter {
     current_mask: Bitmask,
     next_group: *const u8,
@@ -603,7 +1080,7 @@ mod verify {
     use bytecheck::{CheckBytes, Verify};
     use rancor::{fail, Fallible, Source};
 
-    use super::ArchivedHashTable;
+    use super::{ArchivedHashTable, InvalidGroupWidth, DELETED, EMPTY};
     use crate::{
         simd::Group,
         validation::{ArchiveContext, ArchiveContextExt as _},
@@ -628,6 +1105,24 @@ mod verify {
 
     impl Error for InvalidLength {}
 
+    #[derive(Debug)]
+    struct MismatchedFullBuckets {
+        len: usize,
+        full: usize,
+    }
+
+    impl fmt::Display for MismatchedFullBuckets {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "hash table has {} full buckets but its length is {}",
+                self.full, self.len,
+            )
+        }
+    }
+
+    impl Error for MismatchedFullBuckets {}
+
     #[derive(Debug)]
     struct UnwrappedControlByte {
         index: usize,
@@ -641,6 +1136,35 @@ mod verify {
 
     impl Error for UnwrappedControlByte {}
 
+    #[derive(Debug)]
+    struct InvalidControlByte {
+        index: usize,
+        byte: u8,
+    }
+
+    impl fmt::Display for InvalidControlByte {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "invalid control byte {:#04x} at index {}",
+                self.byte, self.index,
+            )
+        }
+    }
+
+    impl Error for InvalidControlByte {}
+
+    #[derive(Debug)]
+    struct NoEmptyBuckets;
+
+    impl fmt::Display for NoEmptyBuckets {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "hash table has no empty buckets to end probing")
+        }
+    }
+
+    impl Error for NoEmptyBuckets {}
+
     unsafe impl<C, T> Verify<C> for ArchivedHashTable<T>
     where
         C: Fallible + ArchiveContext + ?Sized,
@@ -659,9 +1183,14 @@ mod verify {
                 fail!(InvalidLength { len, cap });
             }
 
+            let group_width = self.group_width();
+            if !Self::is_valid_group_width(group_width) {
+                fail!(InvalidGroupWidth { group_width });
+            }
+
             // Check memory allocation
-            let probe_cap = Self::probe_cap(cap);
-            let control_count = Self::control_count(probe_cap);
+            let probe_cap = Self::probe_cap(cap, group_width);
+            let control_count = Self::control_count(probe_cap, group_width);
             let (layout, control_offset) =
                 Self::memory_layout(cap, control_count)?;
             let ptr = self
@@ -677,12 +1206,14 @@ mod verify {
                 // SAFETY: We have checked that `self` is not empty.
                 let mut controls = unsafe { Self::control_iter(this) };
                 let mut base_index = 0;
+                let mut full = 0;
                 'outer: while base_index < cap {
                     while let Some(bit) = controls.next_full() {
                         let index = base_index + bit;
                         if index >= cap {
                             break 'outer;
                         }
+                        full += 1;
 
                         unsafe {
                             T::check_bytes(
@@ -696,13 +1227,38 @@ mod verify {
                     base_index += Group::WIDTH;
                 }
 
-                // Verify that wrapped bytes are set correctly
-                for i in cap..usize::min(2 * cap, control_count - cap) {
+                if full != len {
+                    fail!(MismatchedFullBuckets { len, full });
+                }
+
+                // Every control byte must be full, EMPTY, or DELETED. The
+                // control bytes past the capacity must mirror the first
+                // buckets, and any which don't fit in the mirror must be
+                // EMPTY.
+                let mut any_empty = false;
+                for i in 0..control_count {
                     let byte = unsafe { *Self::control_raw(this, i) };
-                    let wrapped = unsafe { *Self::control_raw(this, i % cap) };
-                    if wrapped != byte {
-                        fail!(UnwrappedControlByte { index: i })
+                    if byte & 0x80 != 0 && byte != EMPTY && byte != DELETED {
+                        fail!(InvalidControlByte { index: i, byte });
+                    }
+                    if i >= cap {
+                        let expected = if i < 2 * cap {
+                            unsafe { *Self::control_raw(this, i - cap) }
+                        } else {
+                            EMPTY
+                        };
+                        if byte != expected {
+                            fail!(UnwrappedControlByte { index: i });
+                        }
                     }
+                    any_empty |= byte == EMPTY;
+                }
+
+                // DELETED buckets don't end a probe sequence, so lookups only
+                // terminate if there is at least one EMPTY bucket. Probe
+                // sequences visit every group, so one is enough.
+                if !any_empty {
+                    fail!(NoEmptyBuckets);
                 }
 
                 Ok(())
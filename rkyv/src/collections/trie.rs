@@ -0,0 +1,872 @@
+//! An archived map keyed by strings and stored as a radix trie.
+//!
+//! The trie is laid out as three flat arrays:
+//!
+//! - The nodes, in depth-first order with children sorted by their labels. Each
+//!   node stores the start of its label, its parent, the end of its subtree,
+//!   and the index of its first value.
+//! - The labels of all nodes concatenated in node order. Labels always split
+//!   keys on `char` boundaries, so every label is valid UTF-8.
+//! - The values, in key order.
+//!
+//! Because the nodes are in depth-first order, the nodes below any prefix form
+//! a contiguous range and the values for those nodes form a contiguous slice.
+
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    iter::FusedIterator,
+    ops::{Bound, Index, RangeBounds},
+    slice, str,
+};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    primitive::ArchivedU32,
+    seal::Seal,
+    ser::{Allocator, Writer},
+    util::SerVec,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+const LABEL_START: usize = 0;
+const PARENT: usize = 1;
+const SUBTREE_END: usize = 2;
+const VALUE_START: usize = 3;
+
+/// An archived map keyed by strings and stored as a radix trie.
+///
+/// Keys which share a prefix share the trie nodes for that prefix, so
+/// dictionaries with many common prefixes take less space than an
+/// [`ArchivedBTreeMap`] of archived strings. In addition to exact lookups, the
+/// trie supports iterating over all keys with a prefix, range queries, and
+/// longest-prefix matching.
+///
+/// Keys are not stored contiguously. Iterators yield [`TrieKey`]s, which
+/// reassemble their keys from the labels of their nodes.
+///
+/// [`ArchivedBTreeMap`]: crate::collections::btree_map::ArchivedBTreeMap
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(C)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+pub struct ArchivedTrieMap<V> {
+    nodes: ArchivedVec<[ArchivedU32; 4]>,
+    labels: ArchivedVec<u8>,
+    values: ArchivedVec<V>,
+}
+
+/// The nodes and labels of a trie.
+#[derive(Clone, Copy)]
+struct Nodes<'a> {
+    nodes: &'a [[ArchivedU32; 4]],
+    labels: &'a str,
+}
+
+impl<'a> Nodes<'a> {
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn field(&self, index: usize, field: usize) -> usize {
+        self.nodes[index][field].to_native() as usize
+    }
+
+    fn parent(&self, index: usize) -> usize {
+        self.field(index, PARENT)
+    }
+
+    fn subtree_end(&self, index: usize) -> usize {
+        self.field(index, SUBTREE_END)
+    }
+
+    fn label_start(&self, index: usize) -> usize {
+        if index < self.len() {
+            self.field(index, LABEL_START)
+        } else {
+            self.labels.len()
+        }
+    }
+
+    fn label(&self, index: usize) -> &'a str {
+        let start = self.label_start(index);
+        let end = self.label_start(index + 1);
+        &self.labels[start..end]
+    }
+
+    fn children(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let end = self.subtree_end(index);
+        let mut next = index + 1;
+        core::iter::from_fn(move || {
+            let child = next;
+            (child < end).then(|| {
+                next = self.subtree_end(child);
+                child
+            })
+        })
+    }
+
+    /// Returns the child of `index` whose label is a prefix of `key`.
+    fn child_with_prefix_of(&self, index: usize, key: &str) -> Option<usize> {
+        self.children(index)
+            .find(|&child| key.starts_with(self.label(child)))
+    }
+
+    /// Returns the first node whose key is greater than (or equal to, if
+    /// `inclusive`) the given key.
+    fn seek(&self, key: &str, inclusive: bool) -> usize {
+        let mut node = 0;
+        let mut rest = key.as_bytes();
+        'descend: loop {
+            if rest.is_empty() {
+                return if inclusive { node } else { node + 1 };
+            }
+
+            for child in self.children(node) {
+                let label = self.label(child).as_bytes();
+                let len = usize::min(label.len(), rest.len());
+                match label[..len].cmp(&rest[..len]) {
+                    Ordering::Less => (),
+                    Ordering::Greater => return child,
+                    Ordering::Equal => {
+                        if label.len() > rest.len() {
+                            return child;
+                        }
+                        node = child;
+                        rest = &rest[label.len()..];
+                        continue 'descend;
+                    }
+                }
+            }
+
+            return self.subtree_end(node);
+        }
+    }
+}
+
+impl<V> ArchivedTrieMap<V> {
+    fn raw(&self) -> Nodes<'_> {
+        Nodes {
+            nodes: self.nodes.as_slice(),
+            // SAFETY: Labels are always valid UTF-8. They are built by
+            // splitting strings on `char` boundaries, and validation checks
+            // that they are valid UTF-8.
+            labels: unsafe { str::from_utf8_unchecked(self.labels.as_slice()) },
+        }
+    }
+
+    /// Returns the index of the first value at or after the given node.
+    fn value_start(&self, index: usize) -> usize {
+        if index < self.nodes.len() {
+            self.raw().field(index, VALUE_START)
+        } else {
+            self.values.len()
+        }
+    }
+
+    /// Returns the index of the value for the given node, if it has one.
+    fn value_index(&self, index: usize) -> Option<usize> {
+        let start = self.value_start(index);
+        (start < self.value_start(index + 1)).then_some(start)
+    }
+
+    fn find(&self, key: &str) -> Option<usize> {
+        let raw = self.raw();
+        let mut node = 0;
+        let mut rest = key;
+        while !rest.is_empty() {
+            node = raw.child_with_prefix_of(node, rest)?;
+            rest = &rest[raw.label(node).len()..];
+        }
+        self.value_index(node)
+    }
+
+    fn iter_nodes(&self, start: usize, end: usize) -> Iter<'_, V> {
+        let end = usize::max(start, end);
+        Iter {
+            raw: self.raw(),
+            values: &self.values
+                [self.value_start(start)..self.value_start(end)],
+            node: start,
+            end,
+        }
+    }
+
+    /// Returns the number of entries in the trie.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether the trie is empty.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the number of nodes in the trie.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    pub fn get(&self, key: &str) -> Option<&V> {
+        Some(&self.values[self.find(key)?])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the supplied
+    /// key.
+    pub fn get_seal<'a>(
+        this: Seal<'a, Self>,
+        key: &str,
+    ) -> Option<Seal<'a, V>> {
+        let index = this.find(key)?;
+        munge!(let Self { values, .. } = this);
+        Some(ArchivedVec::as_slice_seal(values).index(index))
+    }
+
+    /// Returns whether the trie contains the given key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Returns the entry with the longest key which is a prefix of the given
+    /// string.
+    pub fn longest_prefix_of(&self, key: &str) -> Option<(TrieKey<'_>, &V)> {
+        let raw = self.raw();
+        let mut node = 0;
+        let mut rest = key;
+        let mut longest = self.value_index(node).map(|value| (node, value));
+        while let Some(child) = raw.child_with_prefix_of(node, rest) {
+            node = child;
+            rest = &rest[raw.label(node).len()..];
+            if let Some(value) = self.value_index(node) {
+                longest = Some((node, value));
+            }
+        }
+        longest
+            .map(|(index, value)| (TrieKey { raw, index }, &self.values[value]))
+    }
+
+    /// Returns an iterator over the entries of the trie, sorted by key.
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.iter_nodes(0, self.nodes.len())
+    }
+
+    /// Returns an iterator over the keys of the trie in ascending order.
+    pub fn keys(&self) -> Keys<'_, V> {
+        Keys { inner: self.iter() }
+    }
+
+    /// Returns an iterator over the values of the trie, sorted by key.
+    pub fn values(&self) -> slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Returns an iterator over the entries of the trie whose keys start with
+    /// the given prefix, sorted by key.
+    pub fn prefix(&self, prefix: &str) -> Iter<'_, V> {
+        let raw = self.raw();
+        let mut node = 0;
+        let mut rest = prefix;
+        while !rest.is_empty() {
+            let child = raw.children(node).find(|&child| {
+                let label = raw.label(child);
+                rest.starts_with(label) || label.starts_with(rest)
+            });
+            let Some(child) = child else {
+                return self.iter_nodes(0, 0);
+            };
+            node = child;
+            rest = rest.get(raw.label(node).len()..).unwrap_or("");
+        }
+        self.iter_nodes(node, raw.subtree_end(node))
+    }
+
+    /// Returns an iterator over the entries of the trie whose keys are in the
+    /// given range, sorted by key.
+    ///
+    /// If the start of the range is greater than the end of the range, the
+    /// returned iterator is empty.
+    pub fn range<'a, R>(&self, range: R) -> Iter<'_, V>
+    where
+        R: RangeBounds<&'a str>,
+    {
+        let raw = self.raw();
+        let start = match range.start_bound() {
+            Bound::Included(key) => raw.seek(key, true),
+            Bound::Excluded(key) => raw.seek(key, false),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => raw.seek(key, false),
+            Bound::Excluded(key) => raw.seek(key, true),
+            Bound::Unbounded => raw.len(),
+        };
+        self.iter_nodes(start, end)
+    }
+
+    /// Resolves an archived trie map from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: TrieMapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedTrieMap { nodes, labels, values } = out);
+        ArchivedVec::resolve_from_len(
+            resolver.node_count,
+            resolver.nodes,
+            nodes,
+        );
+        ArchivedVec::resolve_from_len(
+            resolver.label_len,
+            resolver.labels,
+            labels,
+        );
+        ArchivedVec::resolve_from_len(len, resolver.values, values);
+    }
+
+    /// Serializes an iterator of key-value pairs which are already sorted by
+    /// key as a trie map.
+    ///
+    /// The keys must be unique and sorted in ascending order. Otherwise,
+    /// serialization fails with an error.
+    pub fn serialize_from_sorted_iter<I, BK, BVU, VU, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<TrieMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (BK, BVU)>,
+        BK: AsRef<str>,
+        BVU: Borrow<VU>,
+        VU: Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        SerVec::with_capacity(serializer, iter.len(), |entries, serializer| {
+            for entry in iter {
+                entries.push(entry);
+            }
+            Self::serialize_from_sorted_slice(entries, serializer)
+        })?
+    }
+
+    /// Serializes an iterator of key-value pairs in any order as a trie map.
+    ///
+    /// The keys must be unique. Otherwise, serialization fails with an error.
+    pub fn serialize_from_iter<I, BK, BVU, VU, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<TrieMapResolver, S::Error>
+    where
+        I: ExactSizeIterator<Item = (BK, BVU)>,
+        BK: AsRef<str>,
+        BVU: Borrow<VU>,
+        VU: Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        SerVec::with_capacity(serializer, iter.len(), |entries, serializer| {
+            for entry in iter {
+                entries.push(entry);
+            }
+            entries
+                .sort_unstable_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+            Self::serialize_from_sorted_slice(entries, serializer)
+        })?
+    }
+
+    fn serialize_from_sorted_slice<BK, BVU, VU, S>(
+        entries: &[(BK, BVU)],
+        serializer: &mut S,
+    ) -> Result<TrieMapResolver, S::Error>
+    where
+        BK: AsRef<str>,
+        BVU: Borrow<VU>,
+        VU: Serialize<S, Archived = V>,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        let key = |index: usize| entries[index].0.as_ref();
+        for index in 1..entries.len() {
+            if key(index - 1) >= key(index) {
+                fail!(UnsortedTrieKeys { index });
+            }
+        }
+
+        let len = entries.len();
+        let max_nodes = 2 * len + 1;
+        let max_label_len =
+            (0..len).map(|index| key(index).len()).sum::<usize>();
+        if u32::try_from(max_nodes).is_err()
+            || u32::try_from(max_label_len).is_err()
+        {
+            fail!(TrieTooLarge);
+        }
+
+        SerVec::<[u32; 4]>::with_capacity(
+            serializer,
+            max_nodes,
+            |nodes, serializer| {
+                SerVec::<u8>::with_capacity(
+                    serializer,
+                    max_label_len,
+                    |labels, serializer| {
+                        SerVec::<Frame>::with_capacity(
+                            serializer,
+                            len + 1,
+                            |stack, _| build(entries, nodes, labels, stack),
+                        )?;
+
+                        let nodes_resolver = ArchivedVec::serialize_from_slice(
+                            nodes.as_slice(),
+                            serializer,
+                        )?;
+                        let labels_resolver =
+                            ArchivedVec::serialize_from_slice(
+                                labels.as_slice(),
+                                serializer,
+                            )?;
+                        let values_resolver =
+                            ArchivedVec::serialize_from_iter::<VU, _, _>(
+                                entries.iter().map(|(_, value)| value.borrow()),
+                                serializer,
+                            )?;
+
+                        Ok(TrieMapResolver {
+                            nodes: nodes_resolver,
+                            labels: labels_resolver,
+                            values: values_resolver,
+                            node_count: nodes.len(),
+                            label_len: labels.len(),
+                        })
+                    },
+                )?
+            },
+        )?
+    }
+}
+
+/// A pending range of keys below a trie node.
+struct Frame {
+    node: usize,
+    start: usize,
+    end: usize,
+    depth: usize,
+}
+
+/// Builds the nodes and labels of a trie from entries with sorted, unique
+/// keys.
+///
+/// All of the keys in a frame share the first `depth` bytes, which is the key
+/// of the frame's node. Keys are grouped by their next `char`, and each group
+/// becomes a child whose label is the longest common prefix of the group.
+fn build<BK: AsRef<str>, BV>(
+    entries: &[(BK, BV)],
+    nodes: &mut SerVec<[u32; 4]>,
+    labels: &mut SerVec<u8>,
+    stack: &mut SerVec<Frame>,
+) {
+    let key = |index: usize| entries[index].0.as_ref();
+
+    let root_has_value =
+        entries.first().is_some_and(|(k, _)| k.as_ref() == "");
+    nodes.push([0, 0, 0, 0]);
+    stack.push(Frame {
+        node: 0,
+        start: root_has_value as usize,
+        end: entries.len(),
+        depth: 0,
+    });
+
+    while let Some(frame) = stack.last_mut() {
+        let Frame {
+            node: parent,
+            start,
+            end,
+            depth,
+        } = *frame;
+
+        if start == end {
+            stack.pop();
+            nodes[parent][SUBTREE_END] = nodes.len() as u32;
+            continue;
+        }
+
+        let first = key(start);
+        let next = first[depth..].chars().next().unwrap();
+        let group_end = start
+            + entries[start..end].partition_point(|(k, _)| {
+                k.as_ref()[depth..].starts_with(next)
+            });
+        frame.start = group_end;
+
+        // Keys are sorted, so the longest common prefix of the group is the
+        // longest common prefix of its first and last keys.
+        let last = key(group_end - 1);
+        let mut common = depth
+            + first.as_bytes()[depth..]
+                .iter()
+                .zip(&last.as_bytes()[depth..])
+                .take_while(|(a, b)| a == b)
+                .count();
+        while !first.is_char_boundary(common) {
+            common -= 1;
+        }
+
+        let child = nodes.len();
+        nodes.push([labels.len() as u32, parent as u32, 0, start as u32]);
+        labels.extend_from_slice(&first.as_bytes()[depth..common]);
+
+        let child_start = if first.len() == common {
+            start + 1
+        } else {
+            start
+        };
+        stack.push(Frame {
+            node: child,
+            start: child_start,
+            end: group_end,
+            depth: common,
+        });
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for ArchivedTrieMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V> Index<&str> for ArchivedTrieMap<V> {
+    type Output = V;
+
+    fn index(&self, key: &str) -> &V {
+        self.get(key).unwrap()
+    }
+}
+
+/// The resolver for [`ArchivedTrieMap`].
+pub struct TrieMapResolver {
+    nodes: VecResolver,
+    labels: VecResolver,
+    values: VecResolver,
+    node_count: usize,
+    label_len: usize,
+}
+
+/// A key in an [`ArchivedTrieMap`].
+///
+/// The key is reassembled from the labels of its node and that node's
+/// ancestors. It can be compared to strings and formatted without allocating.
+#[derive(Clone, Copy)]
+pub struct TrieKey<'a> {
+    raw: Nodes<'a>,
+    index: usize,
+}
+
+impl<'a> TrieKey<'a> {
+    fn ancestors(&self) -> impl Iterator<Item = usize> + '_ {
+        core::iter::successors(Some(self.index), |&node| {
+            (node != 0).then(|| self.raw.parent(node))
+        })
+    }
+
+    /// Returns the length of the key in bytes.
+    pub fn len(&self) -> usize {
+        self.ancestors()
+            .map(|node| self.raw.label(node).len())
+            .sum()
+    }
+
+    /// Returns whether the key is empty.
+    pub fn is_empty(&self) -> bool {
+        self.index == 0
+    }
+
+    /// Returns the pieces of the key in reverse order.
+    ///
+    /// Concatenating the pieces in reverse order yields the key.
+    pub fn rev_pieces(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.ancestors().map(|node| self.raw.label(node))
+    }
+
+    fn write_pieces(
+        &self,
+        mut write: impl FnMut(&str) -> fmt::Result,
+    ) -> fmt::Result {
+        // Each piece is found by walking up from the key's node, which avoids
+        // allocating a buffer for the path.
+        let depth = self.ancestors().count();
+        for piece in (0..depth).rev() {
+            let node = self.ancestors().nth(piece).unwrap();
+            write(self.raw.label(node))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for TrieKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        self.write_pieces(|piece| {
+            piece
+                .chars()
+                .try_for_each(|c| fmt::Display::fmt(&c.escape_debug(), f))
+        })?;
+        f.write_str("\"")
+    }
+}
+
+impl fmt::Display for TrieKey<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_pieces(|piece| f.write_str(piece))
+    }
+}
+
+impl PartialEq<str> for TrieKey<'_> {
+    fn eq(&self, other: &str) -> bool {
+        let mut rest = other;
+        for piece in self.rev_pieces() {
+            match rest.strip_suffix(piece) {
+                Some(prefix) => rest = prefix,
+                None => return false,
+            }
+        }
+        rest.is_empty()
+    }
+}
+
+impl PartialEq<&str> for TrieKey<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.eq(*other)
+    }
+}
+
+impl PartialEq<TrieKey<'_>> for str {
+    fn eq(&self, other: &TrieKey<'_>) -> bool {
+        other.eq(self)
+    }
+}
+
+/// An iterator over the key-value pairs of an [`ArchivedTrieMap`].
+pub struct Iter<'a, V> {
+    raw: Nodes<'a>,
+    values: &'a [V],
+    node: usize,
+    end: usize,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (TrieKey<'a>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (value, values) = self.values.split_first()?;
+        // Skip nodes without values. The value belongs to the last node
+        // whose first value index is the value's index.
+        let first = self.raw.field(self.node, VALUE_START);
+        while self.node + 1 < self.end
+            && self.raw.field(self.node + 1, VALUE_START) == first
+        {
+            self.node += 1;
+        }
+        let index = self.node;
+        self.node += 1;
+        self.values = values;
+        Some((
+            TrieKey {
+                raw: self.raw,
+                index,
+            },
+            value,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.values.len(), Some(self.values.len()))
+    }
+}
+
+impl<V> ExactSizeIterator for Iter<'_, V> {}
+
+impl<V> FusedIterator for Iter<'_, V> {}
+
+/// An iterator over the keys of an [`ArchivedTrieMap`].
+pub struct Keys<'a, V> {
+    inner: Iter<'a, V>,
+}
+
+impl<'a, V> Iterator for Keys<'a, V> {
+    type Item = TrieKey<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<V> ExactSizeIterator for Keys<'_, V> {}
+
+impl<V> FusedIterator for Keys<'_, V> {}
+
+#[derive(Debug)]
+struct UnsortedTrieKeys {
+    index: usize,
+}
+
+impl fmt::Display for UnsortedTrieKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trie key at index {} is not greater than the key before it",
+            self.index,
+        )
+    }
+}
+
+impl Error for UnsortedTrieKeys {}
+
+#[derive(Debug)]
+struct TrieTooLarge;
+
+impl fmt::Display for TrieTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "trie nodes or labels do not fit in 32-bit indices")
+    }
+}
+
+impl Error for TrieTooLarge {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt, str};
+
+    use bytecheck::Verify;
+    use rancor::{fail, Fallible, Source};
+
+    use super::{ArchivedTrieMap, Nodes, LABEL_START, PARENT, VALUE_START};
+
+    #[derive(Debug)]
+    struct InvalidTrieLabels;
+
+    impl fmt::Display for InvalidTrieLabels {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "trie labels are not valid UTF-8")
+        }
+    }
+
+    impl Error for InvalidTrieLabels {}
+
+    #[derive(Debug)]
+    struct InvalidTrieNode {
+        index: usize,
+        reason: &'static str,
+    }
+
+    impl fmt::Display for InvalidTrieNode {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "invalid trie node at index {}: {}",
+                self.index, self.reason
+            )
+        }
+    }
+
+    impl Error for InvalidTrieNode {}
+
+    unsafe impl<C, V> Verify<C> for ArchivedTrieMap<V>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let Ok(labels) = str::from_utf8(self.labels.as_slice()) else {
+                fail!(InvalidTrieLabels);
+            };
+            let raw = Nodes {
+                nodes: self.nodes.as_slice(),
+                labels,
+            };
+            let len = raw.len();
+            let invalid = |index, reason| InvalidTrieNode { index, reason };
+
+            if len == 0 {
+                fail!(invalid(0, "the trie has no root node"));
+            }
+            if raw.field(0, LABEL_START) != 0
+                || raw.field(0, VALUE_START) != 0
+                || raw.subtree_end(0) != len
+            {
+                fail!(invalid(0, "the root node does not span the trie"));
+            }
+
+            for index in 0..len {
+                let label_start = raw.field(index, LABEL_START);
+                let label_end = raw.label_start(index + 1);
+                if label_start > label_end || label_end > labels.len() {
+                    fail!(invalid(index, "label is out of bounds"));
+                }
+                if !labels.is_char_boundary(label_start) {
+                    fail!(invalid(index, "label is not on a char boundary"));
+                }
+                if index != 0 && label_start == label_end {
+                    fail!(invalid(index, "label is empty"));
+                }
+
+                let value_start = self.value_start(index);
+                let value_end = self.value_start(index + 1);
+                if value_start > value_end
+                    || value_end - value_start > 1
+                    || value_end > self.values.len()
+                {
+                    fail!(invalid(index, "value index is out of bounds"));
+                }
+
+                if index == 0 {
+                    continue;
+                }
+
+                let subtree_end = raw.subtree_end(index);
+                if subtree_end <= index || subtree_end > len {
+                    fail!(invalid(index, "subtree end is out of bounds"));
+                }
+
+                // The parent of a node is the closest node before it whose
+                // subtree contains it. Every other node walked past on the
+                // way there is closed, so this walk is linear overall.
+                let mut parent = index - 1;
+                let mut previous_sibling = None;
+                while raw.subtree_end(parent) <= index {
+                    previous_sibling = Some(parent);
+                    parent = raw.parent(parent);
+                }
+                if raw.field(index, PARENT) != parent {
+                    fail!(invalid(index, "parent does not contain the node"));
+                }
+                if subtree_end > raw.subtree_end(parent) {
+                    fail!(invalid(index, "subtree extends past its parent"));
+                }
+                if let Some(sibling) = previous_sibling {
+                    let sibling = raw.label(sibling).chars().next();
+                    let label = raw.label(index).chars().next();
+                    if sibling >= label {
+                        fail!(invalid(index, "siblings are not sorted"));
+                    }
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
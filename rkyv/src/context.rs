@@ -0,0 +1,194 @@
+//! User-provided context for serializers and deserializers.
+//!
+//! Custom [`SerializeWith`](crate::with::SerializeWith) and
+//! [`DeserializeWith`](crate::with::DeserializeWith) implementations sometimes
+//! need external state, like a string interner or a table of shared assets.
+//! Wrapping a serializer or deserializer in a [`Contextual`] makes that state
+//! available to them through a [`Context`] bound, alongside the other
+//! capabilities of the wrapped value.
+
+use core::{alloc::Layout, hash::Hash, ptr::NonNull};
+
+use rancor::Strategy;
+
+use crate::{
+    de::{ErasedPtr, Pooling, PoolingState},
+    ser::{
+        sharing::SharingState, writer::Scope, Allocator, Interning, Positional,
+        Sharing, WriteAt, Writer,
+    },
+};
+
+/// A serializer or deserializer which provides a context of type `C`.
+pub trait Context<C: ?Sized> {
+    /// Returns a reference to the context.
+    fn context(&self) -> &C;
+
+    /// Returns a mutable reference to the context.
+    fn context_mut(&mut self) -> &mut C;
+}
+
+impl<T, C> Context<C> for &mut T
+where
+    T: Context<C> + ?Sized,
+    C: ?Sized,
+{
+    fn context(&self) -> &C {
+        T::context(*self)
+    }
+
+    fn context_mut(&mut self) -> &mut C {
+        T::context_mut(*self)
+    }
+}
+
+impl<T, C, E> Context<C> for Strategy<T, E>
+where
+    T: Context<C> + ?Sized,
+    C: ?Sized,
+{
+    fn context(&self) -> &C {
+        T::context(self)
+    }
+
+    fn context_mut(&mut self) -> &mut C {
+        T::context_mut(self)
+    }
+}
+
+/// An adapter which adds a context to a serializer or deserializer.
+///
+/// All of the serializer and deserializer traits implemented by the wrapped
+/// value are forwarded to it.
+#[derive(Debug)]
+pub struct Contextual<'c, T, C: ?Sized> {
+    inner: T,
+    context: &'c mut C,
+}
+
+impl<'c, T, C: ?Sized> Contextual<'c, T, C> {
+    /// Wraps the given serializer or deserializer with a context.
+    pub fn new(inner: T, context: &'c mut C) -> Self {
+        Self { inner, context }
+    }
+
+    /// Returns a reference to the wrapped serializer or deserializer.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped serializer or deserializer.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the adapter and returns the wrapped serializer or
+    /// deserializer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T, C: ?Sized> Context<C> for Contextual<'_, T, C> {
+    fn context(&self) -> &C {
+        self.context
+    }
+
+    fn context_mut(&mut self) -> &mut C {
+        self.context
+    }
+}
+
+impl<T: Positional, C: ?Sized> Positional for Contextual<'_, T, C> {
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+}
+
+impl<T: Writer<E>, C: ?Sized, E> Writer<E> for Contextual<'_, T, C> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.inner.write(bytes)
+    }
+
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        self.inner.write_padding(len)
+    }
+
+    fn enter_scope(&mut self, scope: Scope) {
+        self.inner.enter_scope(scope)
+    }
+
+    fn exit_scope(&mut self) {
+        self.inner.exit_scope()
+    }
+}
+
+impl<T: WriteAt<E>, C: ?Sized, E> WriteAt<E> for Contextual<'_, T, C> {
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        self.inner.write_at(pos, bytes)
+    }
+}
+
+unsafe impl<T: Allocator<E>, C: ?Sized, E> Allocator<E>
+    for Contextual<'_, T, C>
+{
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, E> {
+        // SAFETY: The safety requirements for `T::push_alloc()` are the same as
+        // the safety requirements for `push_alloc()`.
+        unsafe { self.inner.push_alloc(layout) }
+    }
+
+    unsafe fn pop_alloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `T::pop_alloc()` are the same as
+        // the safety requirements for `pop_alloc()`.
+        unsafe { self.inner.pop_alloc(ptr, layout) }
+    }
+}
+
+impl<T: Sharing<E>, C: ?Sized, E> Sharing<E> for Contextual<'_, T, C> {
+    fn start_sharing(&mut self, address: usize) -> SharingState {
+        self.inner.start_sharing(address)
+    }
+
+    fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
+        self.inner.finish_sharing(address, pos)
+    }
+}
+
+impl<T: Interning, C: ?Sized> Interning for Contextual<'_, T, C> {
+    fn interned_pos<U: Hash + Eq + 'static>(&self, value: &U) -> Option<usize> {
+        self.inner.interned_pos(value)
+    }
+
+    fn finish_interning<U: Hash + Eq + Clone + 'static>(
+        &mut self,
+        value: &U,
+        pos: usize,
+    ) {
+        self.inner.finish_interning(value, pos)
+    }
+}
+
+impl<T: Pooling<E>, C: ?Sized, E> Pooling<E> for Contextual<'_, T, C> {
+    fn start_pooling(&mut self, address: usize) -> PoolingState {
+        self.inner.start_pooling(address)
+    }
+
+    unsafe fn finish_pooling(
+        &mut self,
+        address: usize,
+        ptr: ErasedPtr,
+        drop: unsafe fn(ErasedPtr),
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `finish_pooling` are the same as
+        // the requirements for calling this function.
+        unsafe { self.inner.finish_pooling(address, ptr, drop) }
+    }
+}
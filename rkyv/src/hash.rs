@@ -5,7 +5,45 @@ use core::{
     ops::BitXor as _,
 };
 
-use crate::primitive::{FixedIsize, FixedUsize};
+use crate::{
+    primitive::{ArchivedU64, FixedIsize, FixedUsize},
+    Archive as _, Place, Portable,
+};
+
+/// A hasher which archived hash tables can use to hash their keys.
+///
+/// Table hashers may be keyed with a seed which is chosen when the table is
+/// serialized and stored in the archived table header. For a given seed, a
+/// table hasher must produce the same hashes on every platform.
+///
+/// Every [`Hasher`] which implements [`Default`] is an unkeyed table hasher, so
+/// keyed table hashers must not implement `Default`.
+pub trait TableHasher: Hasher {
+    /// The archived seed stored in the table header.
+    ///
+    /// Unkeyed hashers should use `()` so that the seed takes up no space.
+    type Seed: Portable;
+
+    /// The maximum number of probes a lookup may take in a validated table, or
+    /// `None` if probe lengths are not checked during validation.
+    ///
+    /// Only keyed hashers should set a limit. Unkeyed hashers can produce long
+    /// probe sequences for keys chosen by an attacker, and tables built from
+    /// those keys would then fail validation.
+    const PROBE_LIMIT: Option<usize> = None;
+
+    /// Returns a seed for a new table.
+    fn new_seed() -> u64;
+
+    /// Resolves the given seed into its archived form.
+    fn resolve_seed(seed: u64, out: Place<Self::Seed>);
+
+    /// Returns the seed stored in the given archived seed.
+    fn seed(seed: &Self::Seed) -> u64;
+
+    /// Returns a new hasher keyed with the given seed.
+    fn with_seed(seed: u64) -> Self;
+}
 
 /// A cross-platform 64-bit implementation of fxhash.
 #[derive(Default)]
@@ -114,6 +152,198 @@ impl Hasher for FxHasher64 {
     }
 }
 
+/// Unkeyed hashers which can be created with [`Default`] are table hashers with
+/// no seed. This includes [`FxHasher64`].
+impl<H: Hasher + Default> TableHasher for H {
+    type Seed = ();
+
+    fn new_seed() -> u64 {
+        0
+    }
+
+    fn resolve_seed(_: u64, _: Place<Self::Seed>) {}
+
+    fn seed(_: &Self::Seed) -> u64 {
+        0
+    }
+
+    fn with_seed(_: u64) -> Self {
+        Self::default()
+    }
+}
+
+/// A cross-platform implementation of SipHash-1-3 which is keyed with a random
+/// seed.
+///
+/// Unlike [`FxHasher64`], an attacker who doesn't know the seed can't choose
+/// keys which collide. A new seed is chosen each time a table is serialized,
+/// and validated tables must answer every lookup within
+/// [`PROBE_LIMIT`](TableHasher::PROBE_LIMIT) probes.
+///
+/// Seeds are drawn from the standard library's random hasher state.
+///
+/// # `no_std`
+///
+/// Without the `std` feature there is no source of randomness, so every table
+/// uses the same fixed, publicly known seed. An attacker can then choose keys
+/// which collide just as easily as with [`FxHasher64`], and the only remaining
+/// protection is that validation rejects tables with probe sequences longer
+/// than [`PROBE_LIMIT`](TableHasher::PROBE_LIMIT). Serializing a table from
+/// untrusted keys in a `no_std` build can therefore fail or produce a table
+/// which fails validation. Enable the `std` feature to choose random seeds.
+#[derive(Clone, Copy, Debug)]
+pub struct SipHasher13 {
+    v0: u64,
+    v1: u64,
+    v2: u64,
+    v3: u64,
+    tail: u64,
+    ntail: usize,
+    length: usize,
+}
+
+impl SipHasher13 {
+    /// Returns a new hasher with the given keys.
+    pub const fn new_with_keys(key0: u64, key1: u64) -> Self {
+        Self {
+            v0: key0 ^ 0x736f_6d65_7073_6575,
+            v1: key1 ^ 0x646f_7261_6e64_6f6d,
+            v2: key0 ^ 0x6c79_6765_6e65_7261,
+            v3: key1 ^ 0x7465_6462_7974_6573,
+            tail: 0,
+            ntail: 0,
+            length: 0,
+        }
+    }
+
+    #[inline]
+    fn round(&mut self) {
+        self.v0 = self.v0.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(13) ^ self.v0;
+        self.v0 = self.v0.rotate_left(32);
+        self.v2 = self.v2.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(16) ^ self.v2;
+        self.v0 = self.v0.wrapping_add(self.v3);
+        self.v3 = self.v3.rotate_left(21) ^ self.v0;
+        self.v2 = self.v2.wrapping_add(self.v1);
+        self.v1 = self.v1.rotate_left(17) ^ self.v2;
+        self.v2 = self.v2.rotate_left(32);
+    }
+
+    #[inline]
+    fn compress(&mut self, word: u64) {
+        self.v3 ^= word;
+        self.round();
+        self.v0 ^= word;
+    }
+}
+
+impl Hasher for SipHasher13 {
+    fn write(&mut self, mut bytes: &[u8]) {
+        self.length = self.length.wrapping_add(bytes.len());
+
+        if self.ntail != 0 {
+            let needed = usize::min(8 - self.ntail, bytes.len());
+            for (i, byte) in bytes[..needed].iter().enumerate() {
+                self.tail |= (*byte as u64) << (8 * (self.ntail + i));
+            }
+            self.ntail += needed;
+            bytes = &bytes[needed..];
+
+            if self.ntail < 8 {
+                return;
+            }
+            self.compress(self.tail);
+            self.tail = 0;
+            self.ntail = 0;
+        }
+
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.compress(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+
+        for (i, byte) in chunks.remainder().iter().enumerate() {
+            self.tail |= (*byte as u64) << (8 * i);
+        }
+        self.ntail = chunks.remainder().len();
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = *self;
+        let last = ((self.length as u64 & 0xff) << 56) | self.tail;
+        state.compress(last);
+        state.v2 ^= 0xff;
+        state.round();
+        state.round();
+        state.round();
+        state.v0 ^ state.v1 ^ state.v2 ^ state.v3
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write(&(i as FixedUsize).to_le_bytes());
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write(&(i as FixedIsize).to_le_bytes());
+    }
+}
+
+impl TableHasher for SipHasher13 {
+    type Seed = ArchivedU64;
+
+    // With a random seed, a probe sequence only grows this long if dozens of
+    // groups in a row are completely full.
+    const PROBE_LIMIT: Option<usize> = Some(32);
+
+    fn new_seed() -> u64 {
+        #[cfg(feature = "std")]
+        {
+            use std::{collections::hash_map::RandomState, hash::BuildHasher};
+
+            RandomState::new().hash_one(0u64)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            0x243f_6a88_85a3_08d3
+        }
+    }
+
+    fn resolve_seed(seed: u64, out: Place<Self::Seed>) {
+        seed.resolve((), out);
+    }
+
+    fn seed(seed: &Self::Seed) -> u64 {
+        seed.to_native()
+    }
+
+    fn with_seed(seed: u64) -> Self {
+        Self::new_with_keys(seed, seed.rotate_left(32) ^ 0x9e37_79b9_7f4a_7c15)
+    }
+}
+
 /// Hashes the given value with the default value of the specified `Hasher`.
 pub fn hash_value<Q, H: Hasher + Default>(value: &Q) -> u64
 where
@@ -123,3 +353,62 @@ where
     value.hash(&mut state);
     state.finish()
 }
+
+/// Hashes the given value with a table hasher keyed with the given seed.
+pub fn hash_value_with_seed<Q, H: TableHasher>(value: &Q, seed: u64) -> u64
+where
+    Q: Hash + ?Sized,
+{
+    let mut state = H::with_seed(seed);
+    value.hash(&mut state);
+    state.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::hash::Hasher as _;
+
+    use super::{SipHasher13, TableHasher};
+
+    #[test]
+    fn sip_hasher_13() {
+        let mut hasher =
+            SipHasher13::new_with_keys(0x0706050403020100, 0x0f0e0d0c0b0a0908);
+        hasher.write(b"hello");
+        assert_eq!(hasher.finish(), 0xb6be_2b8c_d613_85b7);
+
+        let mut hasher = SipHasher13::new_with_keys(0, 0);
+        hasher.write_u32(7);
+        assert_eq!(hasher.finish(), 0xc123_1798_07bd_2fea);
+    }
+
+    #[test]
+    fn sip_hasher_13_chunked() {
+        let bytes: [u8; 100] = core::array::from_fn(|i| i as u8);
+        for len in [0, 1, 7, 8, 9, 17, 63, 100] {
+            let mut whole = SipHasher13::new_with_keys(1, 2);
+            whole.write(&bytes[..len]);
+
+            let mut chunked = SipHasher13::new_with_keys(1, 2);
+            for chunk in bytes[..len].chunks(3) {
+                chunked.write(chunk);
+            }
+
+            assert_eq!(whole.finish(), chunked.finish());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn default_hashers_are_table_hashers() {
+        use std::collections::hash_map::DefaultHasher;
+
+        use crate::collections::swiss_table::ArchivedHashMap;
+
+        fn assert_unkeyed<H: TableHasher<Seed = ()>>() {}
+        assert_unkeyed::<DefaultHasher>();
+
+        // Archived hash maps can still name any default-constructible hasher.
+        let _: Option<&ArchivedHashMap<u32, u32, DefaultHasher>> = None;
+    }
+}
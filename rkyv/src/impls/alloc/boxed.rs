@@ -6,7 +6,7 @@ use crate::{
     alloc::{alloc::alloc, boxed::Box},
     boxed::{ArchivedBox, BoxResolver},
     niche::option_box::ArchivedOptionBox,
-    traits::{ArchivePointee, LayoutRaw},
+    traits::{ArchivePointee, LayoutRaw, SizeHint, SizeHintUnsized},
     Archive, ArchiveUnsized, Deserialize, DeserializeUnsized, Place, Serialize,
     SerializeUnsized,
 };
@@ -33,6 +33,12 @@ where
     }
 }
 
+impl<T: SizeHintUnsized + ?Sized> SizeHint for Box<T> {
+    fn size_hint(&self) -> usize {
+        T::size_hint_unsized(self)
+    }
+}
+
 impl<T, D> Deserialize<Box<T>, D> for ArchivedBox<T::Archived>
 where
     T: ArchiveUnsized + LayoutRaw + ?Sized,
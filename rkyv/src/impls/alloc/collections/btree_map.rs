@@ -109,7 +109,7 @@ mod tests {
         },
         api::test::{roundtrip, to_archived},
         collections::btree_map::ArchivedBTreeMap,
-        primitive::ArchivedI32,
+        primitive::{ArchivedI32, ArchivedU32},
         seal::Seal,
         Archive, Deserialize, Serialize,
     };
@@ -272,4 +272,249 @@ mod tests {
             assert_eq!(i.next(), None);
         });
     }
+
+    // Keys are the even numbers so that odd numbers fall between them.
+    fn ordered_map(size: u32) -> BTreeMap<u32, u32> {
+        (0..size).map(|i| (i * 2, i)).collect()
+    }
+
+    fn native((k, v): (&ArchivedU32, &ArchivedU32)) -> (u32, u32) {
+        (k.to_native(), v.to_native())
+    }
+
+    fn check_ordered_iteration(size: u32) {
+        let value = ordered_map(size);
+        let expected = value.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>();
+
+        to_archived(&value, |archived| {
+            assert_eq!(
+                archived.first_key_value().map(native),
+                expected.first().copied()
+            );
+            assert_eq!(
+                archived.last_key_value().map(native),
+                expected.last().copied()
+            );
+
+            for (i, &(key, value)) in expected.iter().enumerate() {
+                assert_eq!(archived.nth(i).map(native), Some((key, value)));
+                assert_eq!(archived.rank(&ArchivedU32::from_native(key)), i);
+                assert_eq!(
+                    archived.rank(&ArchivedU32::from_native(key + 1)),
+                    i + 1
+                );
+            }
+            assert_eq!(archived.nth(expected.len()), None);
+
+            assert_eq!(archived.iter().len(), expected.len());
+            assert!(archived.iter().map(native).eq(expected.iter().copied()));
+            assert!(archived
+                .iter()
+                .rev()
+                .map(native)
+                .eq(expected.iter().rev().copied()));
+            assert!(archived
+                .keys()
+                .rev()
+                .map(|k| k.to_native())
+                .eq(value.keys().rev().copied()));
+            assert!(archived
+                .values()
+                .rev()
+                .map(|v| v.to_native())
+                .eq(value.values().rev().copied()));
+
+            // Alternate between the front and back of the iterator
+            let mut iter = archived.iter().map(native);
+            let mut front = expected.iter().copied();
+            let mut back = Vec::new();
+            while let Some(entry) = iter.next() {
+                assert_eq!(Some(entry), front.next());
+                if let Some(entry) = iter.next_back() {
+                    back.push(entry);
+                }
+            }
+            assert!(back.into_iter().rev().eq(front));
+
+            // Walk a cursor to the end and back again
+            let mut cursor = archived
+                .lower_bound::<ArchivedU32>(core::ops::Bound::Unbounded);
+            let forward = core::iter::from_fn(|| cursor.move_next())
+                .map(native)
+                .collect::<Vec<_>>();
+            assert_eq!(forward, expected);
+            assert_eq!(cursor.index(), expected.len());
+            let backward = core::iter::from_fn(|| cursor.move_prev())
+                .map(native)
+                .collect::<Vec<_>>();
+            assert!(backward.into_iter().eq(expected.iter().rev().copied()));
+            assert_eq!(cursor.index(), 0);
+        });
+    }
+
+    fn check_ordered_ranges(size: u32) {
+        use core::ops::{Bound, RangeBounds as _};
+
+        let value = ordered_map(size);
+        let bound = |key: u32, kind: u32| match kind {
+            0 => Bound::Included(key),
+            1 => Bound::Excluded(key),
+            _ => Bound::Unbounded,
+        };
+        // Check the bounds at a handful of keys, both present and absent
+        let step = (size as usize / 3).max(1) * 2 + 1;
+
+        to_archived(&value, |archived| {
+            for start in (0..size * 2 + 2).step_by(step) {
+                for end in (0..size * 2 + 2).step_by(step) {
+                    for kinds in 0..9 {
+                        let start = bound(start, kinds / 3);
+                        let end = bound(end, kinds % 3);
+                        let archived_start =
+                            start.map(ArchivedU32::from_native);
+                        let archived_end = end.map(ArchivedU32::from_native);
+
+                        // `BTreeMap::range` panics on some ranges where the
+                        // start is after the end, but `ArchivedBTreeMap::range`
+                        // returns an empty iterator.
+                        let expected = value
+                            .iter()
+                            .filter(|(k, _)| (start, end).contains(*k))
+                            .map(|(k, v)| (*k, *v))
+                            .collect::<Vec<_>>();
+                        let actual = archived
+                            .range::<ArchivedU32, _>((
+                                archived_start.as_ref(),
+                                archived_end.as_ref(),
+                            ))
+                            .map(native)
+                            .collect::<Vec<_>>();
+                        assert_eq!(actual, expected);
+
+                        if expected.is_empty() {
+                            continue;
+                        }
+
+                        let mut lower =
+                            archived.lower_bound(archived_start.as_ref());
+                        let mut upper =
+                            archived.upper_bound(archived_end.as_ref());
+                        assert_eq!(
+                            upper.index() - lower.index(),
+                            expected.len()
+                        );
+                        assert_eq!(
+                            lower.peek_next().map(native),
+                            expected.first().copied()
+                        );
+                        assert_eq!(
+                            upper.move_prev().map(native),
+                            expected.last().copied()
+                        );
+                        let forward = (0..expected.len())
+                            .map(|_| native(lower.move_next().unwrap()))
+                            .collect::<Vec<_>>();
+                        assert_eq!(forward, expected);
+                    }
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn btree_map_ordered_queries() {
+        // See `roundtrip_btree_map_increasing_sizes` for why these sizes were
+        // chosen.
+        const SIZES: &[u32] = &[0, 1, 2, 3, 4, 5, 6, 17, 35, 36, 112, 215];
+        for &size in SIZES {
+            check_ordered_iteration(size);
+            check_ordered_ranges(size);
+        }
+    }
+
+    #[cfg(not(miri))]
+    #[test]
+    fn large_btree_map_ordered_queries() {
+        check_ordered_iteration(1000);
+    }
+
+    #[test]
+    fn btree_map_cursor_ends() {
+        let value = ordered_map(10);
+
+        to_archived(&value, |archived| {
+            let mut cursor = archived
+                .lower_bound::<ArchivedU32>(core::ops::Bound::Unbounded);
+            assert_eq!(cursor.index(), 0);
+            assert_eq!(cursor.peek_prev(), None);
+            assert_eq!(cursor.move_prev(), None);
+            assert_eq!(cursor.move_next().map(native), Some((0, 0)));
+            assert_eq!(cursor.peek_prev().map(native), Some((0, 0)));
+
+            let mut cursor = archived
+                .upper_bound::<ArchivedU32>(core::ops::Bound::Unbounded);
+            assert_eq!(cursor.index(), 10);
+            assert_eq!(cursor.peek_next(), None);
+            assert_eq!(cursor.move_next(), None);
+            assert_eq!(cursor.move_prev().map(native), Some((18, 9)));
+        });
+    }
+
+    #[test]
+    fn btree_map_archived_range() {
+        use core::ops::{Bound, Range};
+
+        let value = (
+            ordered_map(20),
+            Range {
+                start: 5u32,
+                end: 13,
+            },
+            (Bound::Excluded(6u32), Bound::Included(12u32)),
+        );
+
+        to_archived(&value, |archived| {
+            let map = &archived.0;
+
+            let keys = map
+                .range(&archived.1)
+                .map(|(k, _)| k.to_native())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, [6, 8, 10, 12]);
+
+            let (start, end) = (&archived.2 .0, &archived.2 .1);
+            let keys = map
+                .range::<ArchivedU32, _>((start.as_ref(), end.as_ref()))
+                .map(|(k, _)| k.to_native())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, [8, 10, 12]);
+            assert_eq!(map.lower_bound(start.as_ref()).index(), 4);
+            assert_eq!(map.upper_bound(end.as_ref()).index(), 7);
+        });
+    }
+
+    #[cfg(all(
+        not(feature = "pointer_width_16"),
+        not(feature = "pointer_width_64"),
+        not(feature = "big_endian"),
+        feature = "bytecheck",
+    ))]
+    #[test]
+    fn invalid_btree_map_shape() {
+        use rancor::Failure;
+
+        use crate::{access, to_bytes};
+
+        let value = ordered_map(6);
+        let mut bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<ArchivedBTreeMap<ArchivedU32, ArchivedU32>, Failure>(&bytes)
+            .unwrap();
+
+        // Six entries require an inner root node, but five entries fit in a
+        // single leaf node. Shortening the length makes the shape invalid.
+        let len = bytes.len();
+        bytes[len - 4..].copy_from_slice(&5u32.to_le_bytes());
+        access::<ArchivedBTreeMap<ArchivedU32, ArchivedU32>, Failure>(&bytes)
+            .unwrap_err();
+    }
 }
@@ -1,10 +1,13 @@
-use core::ops::ControlFlow;
+use core::{borrow::Borrow, ops::ControlFlow};
 
 use rancor::{Fallible, Source};
 
 use crate::{
     alloc::collections::BTreeSet,
-    collections::btree_set::{ArchivedBTreeSet, BTreeSetResolver},
+    collections::{
+        btree_set::{ArchivedBTreeSet, BTreeSetResolver},
+        set_ops::Contains,
+    },
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -88,11 +91,22 @@ impl<K, AK: PartialEq<K>> PartialEq<BTreeSet<K>> for ArchivedBTreeSet<AK> {
     }
 }
 
+impl<K, Q> Contains<Q> for BTreeSet<K>
+where
+    K: Ord + Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    fn contains(&self, value: &Q) -> bool {
+        BTreeSet::contains(self, value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        alloc::{collections::BTreeSet, string::ToString},
-        api::test::roundtrip,
+        alloc::{collections::BTreeSet, string::ToString, vec::Vec},
+        api::test::{roundtrip, to_archived},
+        string::ArchivedString,
     };
 
     #[test]
@@ -113,4 +127,54 @@ mod tests {
 
         roundtrip(&value);
     }
+
+    fn strs<'a>(
+        iter: impl Iterator<Item = &'a ArchivedString>,
+    ) -> Vec<&'a str> {
+        iter.map(ArchivedString::as_str).collect()
+    }
+
+    #[test]
+    fn btree_set_ops() {
+        let a = BTreeSet::from(["a", "b", "c", "e"].map(str::to_string));
+        let b = BTreeSet::from(["b", "c", "d", "f"].map(str::to_string));
+
+        to_archived(&(a, b.clone()), |archived| {
+            let (x, y) = (&archived.0, &archived.1);
+            assert_eq!(strs(x.iter()), ["a", "b", "c", "e"]);
+            assert_eq!(strs(x.intersection(y)), ["b", "c"]);
+            assert_eq!(strs(x.difference(y)), ["a", "e"]);
+            assert_eq!(strs(y.difference(x)), ["d", "f"]);
+            assert_eq!(strs(x.union(y)), ["a", "b", "c", "d", "e", "f"],);
+            assert!(!x.is_subset(y));
+            assert!(!x.is_disjoint(y));
+            assert!(x.is_subset(x));
+
+            assert_eq!(strs(x.intersection(&b)), ["b", "c"]);
+            assert_eq!(strs(x.difference(&b)), ["a", "e"]);
+            assert!(!x.is_subset(&b));
+            assert!(x.is_disjoint(&BTreeSet::from(["d".to_string()])));
+        });
+    }
+
+    #[test]
+    fn btree_set_ops_native() {
+        let value = (0u32..100).step_by(3).collect::<BTreeSet<_>>();
+        let native = (0u32..100).step_by(5).collect::<BTreeSet<_>>();
+
+        to_archived(&value, |archived| {
+            let common = archived
+                .intersection(&native)
+                .map(|x| x.to_native())
+                .collect::<Vec<_>>();
+            assert_eq!(common, [0, 15, 30, 45, 60, 75, 90]);
+
+            let only = archived.difference(&native).count();
+            assert_eq!(only, value.difference(&native).count());
+
+            let multiples = (0u32..100).collect::<BTreeSet<_>>();
+            assert!(archived.is_subset(&multiples));
+            assert!(archived.is_disjoint(&BTreeSet::from([1u32, 2, 100])));
+        });
+    }
 }
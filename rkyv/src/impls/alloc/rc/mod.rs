@@ -11,7 +11,7 @@ use crate::{
     de::{FromMetadata, Metadata, Pooling, PoolingExt as _, SharedPointer},
     rc::{ArchivedRc, ArchivedRcWeak, RcFlavor, RcResolver, RcWeakResolver},
     ser::{Sharing, Writer},
-    traits::{ArchivePointee, LayoutRaw},
+    traits::{ArchivePointee, LayoutRaw, SizeHint, SizeHintUnsized},
     Archive, ArchiveUnsized, Deserialize, DeserializeUnsized, Place, Serialize,
     SerializeUnsized,
 };
@@ -44,6 +44,12 @@ where
     }
 }
 
+impl<T: SizeHintUnsized + ?Sized> SizeHint for rc::Rc<T> {
+    fn size_hint(&self) -> usize {
+        T::size_hint_unsized(self).max(1)
+    }
+}
+
 unsafe impl<T: LayoutRaw + Pointee + ?Sized> SharedPointer<T> for rc::Rc<T> {
     fn alloc(metadata: T::Metadata) -> Result<*mut T, LayoutError> {
         let layout = T::layout_raw(metadata)?;
@@ -4,7 +4,15 @@ use rancor::{Fallible, Source};
 
 use crate::{
     alloc::string::{String, ToString},
-    string::{ArchivedString, StringResolver},
+    string::{
+        compact::{
+            ArchivedInlineString, ArchivedPlainString, ArchivedPrefixedString,
+            LengthPrefix,
+        },
+        repr::INLINE_CAPACITY,
+        ArchivedString, StringResolver,
+    },
+    traits::SizeHint,
     Archive, Deserialize, DeserializeUnsized, Place, Serialize,
     SerializeUnsized,
 };
@@ -32,6 +40,16 @@ where
     }
 }
 
+impl SizeHint for String {
+    fn size_hint(&self) -> usize {
+        if self.len() <= INLINE_CAPACITY {
+            0
+        } else {
+            self.len()
+        }
+    }
+}
+
 impl<D: Fallible + ?Sized> Deserialize<String, D> for ArchivedString
 where
     str: DeserializeUnsized<str, D>,
@@ -69,9 +87,36 @@ impl PartialOrd<ArchivedString> for String {
     }
 }
 
+impl<L: LengthPrefix> PartialEq<String> for ArchivedPrefixedString<L> {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        PartialEq::eq(self.as_str(), other.as_str())
+    }
+}
+
+impl<const N: usize> PartialEq<String> for ArchivedInlineString<N> {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        PartialEq::eq(self.as_str(), other.as_str())
+    }
+}
+
+impl PartialEq<String> for ArchivedPlainString {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        PartialEq::eq(self.as_str(), other.as_str())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{alloc::string::ToString, api::test::roundtrip};
+    use rancor::Failure;
+
+    use crate::{
+        alloc::string::ToString,
+        api::test::{roundtrip, to_archived},
+        string::ArchivedString,
+    };
 
     #[test]
     fn roundtrip_string() {
@@ -93,4 +138,55 @@ mod tests {
         roundtrip(&Err::<(), _>("".to_string()));
         roundtrip(&Err::<(), _>("hello world".to_string()));
     }
+
+    #[test]
+    fn string_overwrite_seal() {
+        to_archived(&"hi".to_string(), |mut archived| {
+            let capacity = archived.capacity();
+            assert!(capacity >= 2);
+
+            let value = "x".repeat(capacity);
+            ArchivedString::try_overwrite_seal::<Failure>(
+                archived.as_mut(),
+                &value,
+            )
+            .unwrap();
+            assert_eq!(archived.as_str(), value);
+
+            let value = "x".repeat(capacity + 1);
+            ArchivedString::try_overwrite_seal::<Failure>(
+                archived.as_mut(),
+                &value,
+            )
+            .unwrap_err();
+        });
+
+        let long = "a fairly long string which will not be inlined";
+        to_archived(&long.to_string(), |mut archived| {
+            assert_eq!(archived.capacity(), long.len());
+
+            let shorter = "a fairly long string which is shorter";
+            ArchivedString::try_overwrite_seal::<Failure>(
+                archived.as_mut(),
+                shorter,
+            )
+            .unwrap();
+            assert_eq!(archived.as_str(), shorter);
+            assert_eq!(archived.capacity(), shorter.len());
+
+            ArchivedString::try_overwrite_seal::<Failure>(
+                archived.as_mut(),
+                long,
+            )
+            .unwrap_err();
+            assert_eq!(archived.as_str(), shorter);
+
+            ArchivedString::try_overwrite_seal::<Failure>(
+                archived.as_mut(),
+                "",
+            )
+            .unwrap();
+            assert_eq!(archived.as_str(), "");
+        });
+    }
 }
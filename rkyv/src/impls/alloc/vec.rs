@@ -3,8 +3,12 @@ use rancor::{Fallible, ResultExt as _, Source};
 use crate::{
     alloc::{alloc::alloc, boxed::Box, vec::Vec},
     ser::{Allocator, Writer},
-    traits::LayoutRaw,
-    vec::{ArchivedVec, VecResolver},
+    traits::{LayoutRaw, SizeHint, SizeHintUnsized as _},
+    vec::{
+        bits::{ArchivedBitVec, BitPackable},
+        small::ArchivedSmallVec,
+        ArchivedVec, VecResolver,
+    },
     Archive, Deserialize, DeserializeUnsized, Place, Serialize,
 };
 
@@ -31,6 +35,12 @@ impl<T: Serialize<S>, S: Fallible + Allocator + Writer + ?Sized> Serialize<S>
     }
 }
 
+impl<T: SizeHint + Archive> SizeHint for Vec<T> {
+    fn size_hint(&self) -> usize {
+        self.as_slice().size_hint_unsized()
+    }
+}
+
 impl<T, D> Deserialize<Vec<T>, D> for ArchivedVec<T::Archived>
 where
     T: Archive,
@@ -69,11 +79,29 @@ impl<T: PartialOrd<U>, U> PartialOrd<Vec<U>> for ArchivedVec<T> {
     }
 }
 
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<Vec<U>>
+    for ArchivedSmallVec<T, N>
+{
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T, const BITS: u32> PartialEq<Vec<T>> for ArchivedBitVec<T, BITS>
+where
+    T: BitPackable + PartialEq,
+{
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.eq(other.as_slice())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         alloc::{vec, vec::Vec},
-        api::test::roundtrip,
+        api::test::{roundtrip, to_archived},
+        vec::ArchivedVec,
     };
 
     #[test]
@@ -102,4 +130,28 @@ mod tests {
         roundtrip(&Err::<(), _>(Vec::<i32>::new()));
         roundtrip(&Err::<(), _>(vec![1, 2, 3, 4]));
     }
+
+    #[test]
+    fn vec_seal_mutation() {
+        to_archived(&vec![3u32, 1, 4, 1, 5, 9, 2, 6], |mut archived| {
+            let mut slice = ArchivedVec::as_slice_seal(archived.as_mut());
+            slice.sort_unstable();
+            assert_eq!(*slice, [1, 1, 2, 3, 4, 5, 6, 9]);
+            slice.reverse();
+            assert_eq!(*slice, [9, 6, 5, 4, 3, 2, 1, 1]);
+            slice.swap(0, 7);
+            assert_eq!(*slice, [1, 6, 5, 4, 3, 2, 1, 9]);
+            slice.sort_unstable_by_key(|x| x.to_native() % 3);
+            assert!(slice.windows(2).all(|w| w[0] % 3 <= w[1] % 3));
+
+            ArchivedVec::truncate_seal(archived.as_mut(), 10);
+            assert_eq!(archived.len(), 8);
+            ArchivedVec::truncate_seal(archived.as_mut(), 3);
+            assert_eq!(archived.len(), 3);
+
+            let mut slice = ArchivedVec::as_slice_seal(archived.as_mut());
+            slice.fill(7.into());
+            assert_eq!(*archived, [7, 7, 7]);
+        });
+    }
 }
@@ -1,4 +1,4 @@
-use core::{marker::PhantomData, ops::ControlFlow};
+use core::{hash::Hash, marker::PhantomData, ops::ControlFlow};
 
 use ptr_meta::Pointee;
 use rancor::{Fallible, Source};
@@ -9,21 +9,44 @@ use crate::{
         boxed::Box,
         collections::{BTreeMap, BTreeSet},
         rc::Rc,
+        string::{String, ToString},
         vec::Vec,
     },
     collections::{
         btree_map::{ArchivedBTreeMap, BTreeMapResolver},
+        perfect_hash::{
+            ArchivedPerfectHashMap, ArchivedPerfectHashSet,
+            PerfectHashMapResolver, PerfectHashSetResolver,
+        },
+        sorted_vec::{
+            ArchivedSortedMap, ArchivedSortedVec, SortedMapResolver,
+            SortedVecResolver,
+        },
+        trie::{ArchivedTrieMap, TrieMapResolver},
         util::{Entry, EntryAdapter},
     },
+    hash::FxHasher64,
     impls::core::with::RefWrapper,
     niche::option_box::{ArchivedOptionBox, OptionBoxResolver},
     ser::{Allocator, Writer},
-    string::{ArchivedString, StringResolver},
+    string::{
+        compact::{
+            ArchivedInlineString, ArchivedPlainString, ArchivedPrefixedString,
+            LengthPrefix,
+        },
+        ArchivedString, StringResolver,
+    },
     traits::LayoutRaw,
-    vec::{ArchivedVec, VecResolver},
+    vec::{
+        bits::{ArchivedBitVec, BitPackable, BitVecResolver},
+        small::{ArchivedSmallVec, SmallVecResolver},
+        ArchivedVec, VecResolver,
+    },
     with::{
-        ArchiveWith, AsOwned, AsVec, DeserializeWith, Map, MapKV, Niche,
-        SerializeWith, Unshare,
+        ArchiveWith, AsInlineString, AsOwned, AsPerfectHash, AsPlainString,
+        AsPrefixedString, AsSortedVec, AsTrie, AsVec, BitPacked,
+        DeserializeWith, InlineSmall, Map, MapKV, Niche, SerializeWith,
+        Unshare,
     },
     Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized,
     Place, Serialize, SerializeUnsized,
@@ -319,6 +342,270 @@ where
     }
 }
 
+// AsPrefixedString
+
+impl<L: LengthPrefix> ArchiveWith<String> for AsPrefixedString<L> {
+    type Archived = ArchivedPrefixedString<L>;
+    type Resolver = StringResolver;
+
+    fn resolve_with(
+        field: &String,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPrefixedString::resolve_from_str(field, resolver, out);
+    }
+}
+
+impl<L, S> SerializeWith<String, S> for AsPrefixedString<L>
+where
+    L: LengthPrefix,
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPrefixedString::<L>::serialize_from_str(field, serializer)
+    }
+}
+
+impl<L, D> DeserializeWith<ArchivedPrefixedString<L>, String, D>
+    for AsPrefixedString<L>
+where
+    L: LengthPrefix,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPrefixedString<L>,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        Ok(field.as_str().to_string())
+    }
+}
+
+// AsInlineString
+
+impl<const N: usize> ArchiveWith<String> for AsInlineString<N> {
+    type Archived = ArchivedInlineString<N>;
+    type Resolver = StringResolver;
+
+    fn resolve_with(
+        field: &String,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedInlineString::resolve_from_str(field, resolver, out);
+    }
+}
+
+impl<const N: usize, S> SerializeWith<String, S> for AsInlineString<N>
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedInlineString::<N>::serialize_from_str(field, serializer)
+    }
+}
+
+impl<const N: usize, D> DeserializeWith<ArchivedInlineString<N>, String, D>
+    for AsInlineString<N>
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedInlineString<N>,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        Ok(field.as_str().to_string())
+    }
+}
+
+// AsPlainString
+
+impl ArchiveWith<String> for AsPlainString {
+    type Archived = ArchivedPlainString;
+    type Resolver = StringResolver;
+
+    fn resolve_with(
+        field: &String,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPlainString::resolve_from_str(field, resolver, out);
+    }
+}
+
+impl<S> SerializeWith<String, S> for AsPlainString
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPlainString::serialize_from_str(field, serializer)
+    }
+}
+
+impl<D> DeserializeWith<ArchivedPlainString, String, D> for AsPlainString
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPlainString,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        Ok(field.as_str().to_string())
+    }
+}
+
+// InlineSmall
+
+impl<T: Archive, const N: usize> ArchiveWith<Vec<T>> for InlineSmall<N> {
+    type Archived = ArchivedSmallVec<T::Archived, N>;
+    type Resolver = SmallVecResolver<T::Resolver, N>;
+
+    fn resolve_with(
+        field: &Vec<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSmallVec::resolve_from_slice(field.as_slice(), resolver, out);
+    }
+}
+
+impl<T, S, const N: usize> SerializeWith<Vec<T>, S> for InlineSmall<N>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Vec<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSmallVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D, const N: usize>
+    DeserializeWith<ArchivedSmallVec<T::Archived, N>, Vec<T>, D>
+    for InlineSmall<N>
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSmallVec<T::Archived, N>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        let mut result = Vec::with_capacity(field.len());
+        for item in field.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// BitPacked
+
+impl<T: BitPackable, const BITS: u32> ArchiveWith<Vec<T>> for BitPacked<BITS> {
+    type Archived = ArchivedBitVec<T, BITS>;
+    type Resolver = BitVecResolver;
+
+    fn resolve_with(
+        field: &Vec<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedBitVec::<T, BITS>::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<T, S, const BITS: u32> SerializeWith<Vec<T>, S> for BitPacked<BITS>
+where
+    T: BitPackable,
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &Vec<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBitVec::<T, BITS>::serialize_from_iter(
+            field.iter().copied(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D, const BITS: u32> DeserializeWith<ArchivedBitVec<T, BITS>, Vec<T>, D>
+    for BitPacked<BITS>
+where
+    T: BitPackable,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBitVec<T, BITS>,
+        _: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        Ok(field.iter().collect())
+    }
+}
+
+impl<T: BitPackable, const BITS: u32> ArchiveWith<Box<[T]>>
+    for BitPacked<BITS>
+{
+    type Archived = ArchivedBitVec<T, BITS>;
+    type Resolver = BitVecResolver;
+
+    fn resolve_with(
+        field: &Box<[T]>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedBitVec::<T, BITS>::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<T, S, const BITS: u32> SerializeWith<Box<[T]>, S> for BitPacked<BITS>
+where
+    T: BitPackable,
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &Box<[T]>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBitVec::<T, BITS>::serialize_from_iter(
+            field.iter().copied(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D, const BITS: u32>
+    DeserializeWith<ArchivedBitVec<T, BITS>, Box<[T]>, D> for BitPacked<BITS>
+where
+    T: BitPackable,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBitVec<T, BITS>,
+        _: &mut D,
+    ) -> Result<Box<[T]>, D::Error> {
+        Ok(field.iter().collect())
+    }
+}
+
 // AsVec
 
 impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsVec {
@@ -344,10 +631,327 @@ where
         field: &BTreeMap<K, V>,
         serializer: &mut S,
     ) -> Result<Self::Resolver, S::Error> {
-        ArchivedVec::serialize_from_iter(
-            field.iter().map(|(key, value)| {
-                EntryAdapter::<_, _, K, V>::new(key, value)
-            }),
+        ArchivedVec::serialize_from_iter(
+            field.iter().map(|(key, value)| {
+                EntryAdapter::<_, _, K, V>::new(key, value)
+            }),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, D>
+    DeserializeWith<
+        ArchivedVec<Entry<K::Archived, V::Archived>>,
+        BTreeMap<K, V>,
+        D,
+    > for AsVec
+where
+    K: Archive + Ord,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedVec<Entry<K::Archived, V::Archived>>,
+        deserializer: &mut D,
+    ) -> Result<BTreeMap<K, V>, D::Error> {
+        let mut result = BTreeMap::new();
+        for entry in field.iter() {
+            result.insert(
+                entry.key.deserialize(deserializer)?,
+                entry.value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Archive> ArchiveWith<BTreeSet<T>> for AsVec {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    fn resolve_with(
+        field: &BTreeSet<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedVec::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<BTreeSet<T>, S> for AsVec
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &BTreeSet<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, BTreeSet<T>, D> for AsVec
+where
+    T: Archive + Ord,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeSet<T>, D::Error> {
+        let mut result = BTreeSet::new();
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsPerfectHash
+
+impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsPerfectHash {
+    type Archived = ArchivedPerfectHashMap<K::Archived, V::Archived>;
+    type Resolver = PerfectHashMapResolver;
+
+    fn resolve_with(
+        field: &BTreeMap<K, V>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPerfectHashMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, S> SerializeWith<BTreeMap<K, V>, S> for AsPerfectHash
+where
+    K: Serialize<S> + Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &BTreeMap<K, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPerfectHashMap::<_, _, FxHasher64>::serialize_from_iter::<
+            _,
+            _,
+            _,
+            K,
+            V,
+            _,
+        >(field.iter(), serializer)
+    }
+}
+
+impl<K, V, D>
+    DeserializeWith<
+        ArchivedPerfectHashMap<K::Archived, V::Archived>,
+        BTreeMap<K, V>,
+        D,
+    > for AsPerfectHash
+where
+    K: Archive + Ord,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPerfectHashMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeMap<K, V>, D::Error> {
+        let mut result = BTreeMap::new();
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Archive> ArchiveWith<BTreeSet<T>> for AsPerfectHash {
+    type Archived = ArchivedPerfectHashSet<T::Archived>;
+    type Resolver = PerfectHashSetResolver;
+
+    fn resolve_with(
+        field: &BTreeSet<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPerfectHashSet::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<BTreeSet<T>, S> for AsPerfectHash
+where
+    T: Serialize<S> + Hash + Eq,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &BTreeSet<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPerfectHashSet::<_, FxHasher64>::serialize_from_iter::<_, T, _>(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedPerfectHashSet<T::Archived>, BTreeSet<T>, D>
+    for AsPerfectHash
+where
+    T: Archive + Ord,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPerfectHashSet<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeSet<T>, D::Error> {
+        let mut result = BTreeSet::new();
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsSortedVec
+
+impl<T: Archive> ArchiveWith<Vec<T>> for AsSortedVec {
+    type Archived = ArchivedSortedVec<T::Archived>;
+    type Resolver = SortedVecResolver;
+
+    fn resolve_with(
+        field: &Vec<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedVec::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Vec<T>, S> for AsSortedVec
+where
+    T: Serialize<S> + Ord,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Vec<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedVec::serialize_from_iter::<T, _, _>(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedSortedVec<T::Archived>, Vec<T>, D>
+    for AsSortedVec
+where
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSortedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        let mut result = Vec::with_capacity(field.len());
+        for value in field.iter() {
+            result.push(value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Archive> ArchiveWith<BTreeSet<T>> for AsSortedVec {
+    type Archived = ArchivedSortedVec<T::Archived>;
+    type Resolver = SortedVecResolver;
+
+    fn resolve_with(
+        field: &BTreeSet<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedVec::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<BTreeSet<T>, S> for AsSortedVec
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &BTreeSet<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedVec::serialize_from_sorted_iter::<T, _, _>(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedSortedVec<T::Archived>, BTreeSet<T>, D>
+    for AsSortedVec
+where
+    T: Archive + Ord,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSortedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<BTreeSet<T>, D::Error> {
+        let mut result = BTreeSet::new();
+        for value in field.iter() {
+            result.insert(value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsSortedVec {
+    type Archived = ArchivedSortedMap<K::Archived, V::Archived>;
+    type Resolver = SortedMapResolver;
+
+    fn resolve_with(
+        field: &BTreeMap<K, V>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, S> SerializeWith<BTreeMap<K, V>, S> for AsSortedVec
+where
+    K: Serialize<S>,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &BTreeMap<K, V>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedMap::serialize_from_sorted_iter::<_, _, _, K, V, _>(
+            field.iter(),
             serializer,
         )
     }
@@ -355,10 +959,10 @@ where
 
 impl<K, V, D>
     DeserializeWith<
-        ArchivedVec<Entry<K::Archived, V::Archived>>,
+        ArchivedSortedMap<K::Archived, V::Archived>,
         BTreeMap<K, V>,
         D,
-    > for AsVec
+    > for AsSortedVec
 where
     K: Archive + Ord,
     V: Archive,
@@ -367,62 +971,66 @@ where
     D: Fallible + ?Sized,
 {
     fn deserialize_with(
-        field: &ArchivedVec<Entry<K::Archived, V::Archived>>,
+        field: &ArchivedSortedMap<K::Archived, V::Archived>,
         deserializer: &mut D,
     ) -> Result<BTreeMap<K, V>, D::Error> {
         let mut result = BTreeMap::new();
-        for entry in field.iter() {
+        for (key, value) in field.iter() {
             result.insert(
-                entry.key.deserialize(deserializer)?,
-                entry.value.deserialize(deserializer)?,
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
             );
         }
         Ok(result)
     }
 }
 
-impl<T: Archive> ArchiveWith<BTreeSet<T>> for AsVec {
-    type Archived = ArchivedVec<T::Archived>;
-    type Resolver = VecResolver;
+// AsTrie
+
+impl<V: Archive> ArchiveWith<BTreeMap<String, V>> for AsTrie {
+    type Archived = ArchivedTrieMap<V::Archived>;
+    type Resolver = TrieMapResolver;
 
     fn resolve_with(
-        field: &BTreeSet<T>,
+        field: &BTreeMap<String, V>,
         resolver: Self::Resolver,
         out: Place<Self::Archived>,
     ) {
-        ArchivedVec::resolve_from_len(field.len(), resolver, out);
+        ArchivedTrieMap::resolve_from_len(field.len(), resolver, out);
     }
 }
 
-impl<T, S> SerializeWith<BTreeSet<T>, S> for AsVec
+impl<V, S> SerializeWith<BTreeMap<String, V>, S> for AsTrie
 where
-    T: Serialize<S>,
+    V: Serialize<S>,
     S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
 {
     fn serialize_with(
-        field: &BTreeSet<T>,
+        field: &BTreeMap<String, V>,
         serializer: &mut S,
     ) -> Result<Self::Resolver, S::Error> {
-        ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(
+        ArchivedTrieMap::serialize_from_sorted_iter::<_, _, _, V, _>(
             field.iter(),
             serializer,
         )
     }
 }
 
-impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, BTreeSet<T>, D> for AsVec
+impl<V, D> DeserializeWith<ArchivedTrieMap<V::Archived>, BTreeMap<String, V>, D>
+    for AsTrie
 where
-    T: Archive + Ord,
-    T::Archived: Deserialize<T, D>,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
     D: Fallible + ?Sized,
 {
     fn deserialize_with(
-        field: &ArchivedVec<T::Archived>,
+        field: &ArchivedTrieMap<V::Archived>,
         deserializer: &mut D,
-    ) -> Result<BTreeSet<T>, D::Error> {
-        let mut result = BTreeSet::new();
-        for key in field.iter() {
-            result.insert(key.deserialize(deserializer)?);
+    ) -> Result<BTreeMap<String, V>, D::Error> {
+        let mut result = BTreeMap::new();
+        for (key, value) in field.iter() {
+            result.insert(key.to_string(), value.deserialize(deserializer)?);
         }
         Ok(result)
     }
@@ -569,14 +1177,18 @@ mod tests {
             boxed::Box,
             collections::{BTreeMap, BTreeSet},
             string::{String, ToString},
+            vec,
+            vec::Vec,
         },
-        api::test::{roundtrip, roundtrip_with, to_archived},
+        api::test::{deserialize, roundtrip, roundtrip_with, to_archived},
         niche::niching::Null,
         with::{
-            AsOwned, AsVec, DefaultNiche, InlineAsBox, Map, MapKV, Niche,
+            AsInlineString, AsOwned, AsPerfectHash, AsPlainString,
+            AsPrefixedString, AsSortedVec, AsTrie, AsVec, BitPacked,
+            DefaultNiche, InlineAsBox, InlineSmall, Intern, Map, MapKV, Niche,
             NicheInto,
         },
-        Archive, Deserialize, Serialize,
+        Archive, Archived, Deserialize, Serialize,
     };
 
     #[derive(Debug, Archive, Deserialize, Serialize, PartialEq)]
@@ -735,6 +1347,305 @@ mod tests {
         });
     }
 
+    #[test]
+    fn with_compact_strings() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsPrefixedString<u8>)]
+            a: String,
+            #[rkyv(with = AsPrefixedString<u16>)]
+            b: String,
+            #[rkyv(with = AsInlineString<16>)]
+            c: String,
+            #[rkyv(with = AsInlineString<16>)]
+            d: String,
+            #[rkyv(with = AsPlainString)]
+            e: String,
+        }
+
+        roundtrip(&Test {
+            a: "".to_string(),
+            b: "".to_string(),
+            c: "".to_string(),
+            d: "".to_string(),
+            e: "".to_string(),
+        });
+        roundtrip(&Test {
+            a: "hello".to_string(),
+            b: "hello world".to_string(),
+            c: "sixteen bytes!!!".to_string(),
+            d: "seventeen bytes!!".to_string(),
+            e: "hello world".to_string(),
+        });
+
+        to_archived(
+            &Test {
+                a: "a".to_string(),
+                b: "b".repeat(300),
+                c: "c".to_string(),
+                d: "d".repeat(17),
+                e: "e".to_string(),
+            },
+            |archived| {
+                assert_eq!(archived.a, "a");
+                assert_eq!(archived.b.len(), 300);
+                assert!(archived.c.is_inline());
+                assert!(!archived.d.is_inline());
+                assert_eq!(archived.d.len(), 17);
+                assert_eq!(archived.e, "e");
+            },
+        );
+    }
+
+    #[test]
+    fn with_inline_small() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        struct Test {
+            #[rkyv(with = InlineSmall<2>)]
+            a: Vec<u32>,
+            #[rkyv(with = InlineSmall<2>)]
+            b: Vec<String>,
+            #[rkyv(with = InlineSmall<0>)]
+            c: Vec<u8>,
+        }
+
+        roundtrip(&Test {
+            a: Vec::new(),
+            b: Vec::new(),
+            c: Vec::new(),
+        });
+        roundtrip(&Test {
+            a: vec![1, 2],
+            b: vec!["hello world, this is out-of-line".to_string()],
+            c: vec![1],
+        });
+        roundtrip(&Test {
+            a: vec![1, 2, 3, 4],
+            b: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            c: vec![1, 2, 3],
+        });
+
+        to_archived(
+            &Test {
+                a: vec![1, 2],
+                b: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+                c: Vec::new(),
+            },
+            |archived| {
+                assert!(archived.a.is_inline());
+                assert_eq!(*archived.a, [1, 2]);
+                assert!(!archived.b.is_inline());
+                assert_eq!(archived.b.len(), 3);
+                assert_eq!(archived.b[2], "c");
+                assert!(archived.c.is_inline());
+                assert!(archived.c.is_empty());
+            },
+        );
+    }
+
+    #[test]
+    fn with_bit_packed() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        struct Test {
+            #[rkyv(with = BitPacked<1>)]
+            a: Vec<bool>,
+            #[rkyv(with = BitPacked<3>)]
+            b: Vec<u8>,
+            #[rkyv(with = BitPacked<64>)]
+            c: Vec<u64>,
+        }
+
+        roundtrip(&Test {
+            a: Vec::new(),
+            b: Vec::new(),
+            c: Vec::new(),
+        });
+        roundtrip(&Test {
+            a: vec![true, false, false, true, true, false, true, false, true],
+            b: vec![0, 1, 2, 3, 4, 5, 6, 7, 3],
+            c: vec![0, u64::MAX, 1 << 63],
+        });
+
+        to_archived(
+            &Test {
+                a: (0..100).map(|i| i % 3 == 0).collect(),
+                b: vec![7, 0, 5],
+                c: vec![u64::MAX],
+            },
+            |archived| {
+                assert_eq!(archived.a.len(), 100);
+                assert_eq!(archived.a.as_bytes().len(), 13);
+                assert_eq!(archived.a.get(99), Some(true));
+                assert_eq!(archived.a.get(98), Some(false));
+                assert_eq!(archived.a.iter().filter(|b| *b).count(), 34);
+                assert_eq!(archived.b.as_bytes(), &[0b0100_0111, 0b1]);
+                assert_eq!(archived.b.iter().collect::<Vec<_>>(), [7, 0, 5]);
+                assert_eq!(archived.c.get(0), Some(u64::MAX));
+            },
+        );
+    }
+
+    #[test]
+    fn with_bit_packed_boxed_slice() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = BitPacked<10>)]
+            value: Box<[u16]>,
+        }
+
+        roundtrip_with(
+            &Test {
+                value: Box::new([1023, 0, 512, 1]),
+            },
+            |value, archived| {
+                assert_eq!(archived.value, *value.value);
+                assert_eq!(archived.value.as_bytes().len(), 5);
+            },
+        );
+    }
+
+    #[test]
+    fn with_bit_packed_flags() {
+        use rancor::Error;
+
+        use crate::{to_bytes, vec::bits::BitPackable};
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Permissions(u8);
+
+        impl Permissions {
+            const READ: Self = Self(0b001);
+            const WRITE: Self = Self(0b010);
+            const EXECUTE: Self = Self(0b100);
+        }
+
+        impl BitPackable for Permissions {
+            const MAX_BITS: u32 = 3;
+
+            fn to_bits(&self) -> u64 {
+                self.0 as u64
+            }
+
+            fn from_bits(bits: u64) -> Self {
+                Self(bits as u8)
+            }
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        struct Test {
+            #[rkyv(with = BitPacked<3>)]
+            permissions: Vec<Permissions>,
+        }
+
+        let none = Permissions(0);
+        let all = Permissions(
+            Permissions::READ.0 | Permissions::WRITE.0 | Permissions::EXECUTE.0,
+        );
+
+        let value = Test {
+            permissions: vec![none; 3],
+        };
+        roundtrip(&value);
+        to_archived(&value, |archived| {
+            assert_eq!(archived.permissions.as_bytes(), &[0, 0]);
+        });
+
+        let value = Test {
+            permissions: vec![all; 3],
+        };
+        roundtrip(&value);
+        to_archived(&value, |archived| {
+            assert_eq!(archived.permissions.as_bytes(), &[0xff, 0b1]);
+        });
+
+        let value = Test {
+            permissions: vec![all, Permissions(0b1000)],
+        };
+        to_bytes::<Error>(&value)
+            .expect_err("expected flags outside of 3 bits to be rejected");
+    }
+
+    #[test]
+    fn with_bit_packed_too_large() {
+        use rancor::Error;
+
+        use crate::{to_bytes, with::With};
+
+        let value = vec![1u8, 8];
+        to_bytes::<Error>(With::<_, BitPacked<3>>::cast(&value))
+            .expect_err("expected value too large error");
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn with_aligned() {
+        use core::mem::align_of;
+
+        use rancor::Error;
+
+        use crate::{
+            access, api::high::to_bytes_in, deserialize, util::AlignedVec,
+            with::Aligned,
+        };
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, align = 64, derive(Debug))]
+        struct Line {
+            value: u32,
+        }
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            header: u8,
+            #[rkyv(with = Aligned<4096>)]
+            page: [u8; 16],
+            lines: Vec<Line>,
+        }
+
+        assert_eq!(align_of::<ArchivedLine>(), 64);
+        assert_eq!(align_of::<ArchivedTest>(), 4096);
+
+        let value = Test {
+            header: 1,
+            page: [2; 16],
+            lines: vec![Line { value: 3 }, Line { value: 4 }],
+        };
+        let bytes =
+            to_bytes_in::<_, Error>(&value, AlignedVec::<4096>::new()).unwrap();
+        let archived = access::<ArchivedTest, Error>(&bytes).unwrap();
+        assert_eq!(archived.page.as_ptr() as usize % 4096, 0);
+        for line in archived.lines.iter() {
+            assert_eq!(line as *const ArchivedLine as usize % 64, 0);
+        }
+        let deserialized = deserialize::<Test, Error>(archived).unwrap();
+        assert_eq!(deserialized, value);
+
+        // Archived values are only aligned relative to the start of the
+        // buffer, so accessing them from a misaligned buffer must fail.
+        let mut misaligned = AlignedVec::<4096>::new();
+        misaligned.extend_from_slice(&[0; 64]);
+        misaligned.extend_from_slice(&bytes);
+        access::<ArchivedTest, Error>(&misaligned[64..])
+            .expect_err("expected misaligned access to fail");
+    }
+
+    #[test]
+    fn with_prefixed_string_too_long() {
+        use rancor::Error;
+
+        use crate::{to_bytes, with::With};
+
+        let value = "a".repeat(256);
+        to_bytes::<Error>(With::<_, AsPrefixedString<u8>>::cast(&value))
+            .expect_err("expected string too long error");
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn with_niche_box() {
@@ -831,4 +1742,326 @@ mod tests {
             assert_eq!(nichable.boxed.as_ref().to_native(), 727);
         });
     }
+
+    #[test]
+    fn with_btree_map_as_perfect_hash() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsPerfectHash)]
+            map: BTreeMap<String, u32>,
+            #[rkyv(with = AsPerfectHash)]
+            set: BTreeSet<u32>,
+        }
+
+        let value = Test {
+            map: (0..50).map(|i| (i.to_string(), i)).collect(),
+            set: (0..50).map(|i| i * 2).collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(a.map.len(), b.map.len());
+            for (key, value) in a.map.iter() {
+                assert_eq!(b.map[key.as_str()], *value);
+            }
+            assert_eq!(a.set.len(), b.set.len());
+            for i in 0..100 {
+                assert_eq!(b.set.contains(&i.into()), i % 2 == 0);
+            }
+        });
+    }
+
+    #[test]
+    fn with_as_sorted_vec() {
+        use core::ops::Bound;
+
+        use crate::Archived;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsSortedVec)]
+            vec: Vec<u32>,
+            #[rkyv(with = AsSortedVec)]
+            set: BTreeSet<u32>,
+            #[rkyv(with = AsSortedVec)]
+            map: BTreeMap<String, u32>,
+        }
+
+        let value = Test {
+            vec: vec![9, 3, 6, 0, 3, 12],
+            set: (0..50).map(|i| i * 2).collect(),
+            map: (0..50).map(|i| (i.to_string(), i)).collect(),
+        };
+
+        let archived = |x: u32| Archived::<u32>::from_native(x);
+
+        to_archived(&value, |b| {
+            let vec = &b.vec;
+            assert_eq!(vec.as_slice(), [0, 3, 3, 6, 9, 12]);
+            assert_eq!(vec.get(&archived(3)), Some(&vec[1]));
+            assert_eq!(vec.get(&archived(4)), None);
+            assert_eq!(vec.lower_bound(Bound::Included(&archived(3))), 1);
+            assert_eq!(vec.lower_bound(Bound::Excluded(&archived(3))), 3);
+            assert_eq!(vec.upper_bound(Bound::Included(&archived(3))), 3);
+            assert_eq!(vec.upper_bound::<Archived<u32>>(Bound::Unbounded), 6);
+            assert_eq!(vec.range(archived(3)..archived(9)), [3, 3, 6]);
+            assert!(vec.range(archived(9)..archived(3)).is_empty());
+
+            let joined = vec
+                .merge_join(&b.set)
+                .map(|(a, _)| a.to_native())
+                .collect::<Vec<_>>();
+            assert_eq!(joined, [0, 6, 12]);
+
+            assert_eq!(b.set.len(), 50);
+            for i in 0..100 {
+                assert_eq!(b.set.contains(&archived(i)), i % 2 == 0);
+            }
+
+            assert_eq!(b.map.len(), 50);
+            for (key, value) in value.map.iter() {
+                assert_eq!(b.map[key.as_str()], *value);
+            }
+            assert!(!b.map.contains_key("missing"));
+            let keys = b
+                .map
+                .range::<str, _>((Bound::Included("3"), Bound::Excluded("4")))
+                .map(|(key, _)| key.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                keys,
+                [
+                    "3", "30", "31", "32", "33", "34", "35", "36", "37", "38",
+                    "39"
+                ]
+            );
+        });
+
+        // Deserializing a sorted vec always yields a sorted `Vec`.
+        let mut value = value;
+        value.vec.sort();
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.vec.as_slice(), a.vec.as_slice());
+        });
+    }
+
+    #[test]
+    fn with_btree_map_as_trie() {
+        use crate::alloc::format;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsTrie)]
+            inner: BTreeMap<String, u32>,
+        }
+
+        let keys = [
+            "", "a", "app", "apple", "applet", "apply", "banana", "band", "é",
+            "è", "eau", "/usr", "/usr/bin", "/usr/lib", "/var",
+        ];
+        let value = Test {
+            inner: keys
+                .iter()
+                .zip(0..)
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            let trie = &b.inner;
+            assert_eq!(trie.len(), a.inner.len());
+            for (key, value) in a.inner.iter() {
+                assert_eq!(trie[key.as_str()], *value);
+            }
+            assert!(!trie.contains_key("ap"));
+            assert!(!trie.contains_key("applesauce"));
+            assert!(!trie.contains_key("e"));
+
+            for ((a_key, a_value), (b_key, b_value)) in
+                a.inner.iter().zip(trie.iter())
+            {
+                assert_eq!(b_key, a_key.as_str());
+                assert_eq!(b_key.len(), a_key.len());
+                assert_eq!(b_key.to_string(), *a_key);
+                assert_eq!(b_value, a_value);
+            }
+
+            let prefix = |p: &str| {
+                trie.prefix(p)
+                    .map(|(k, _)| k.to_string())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(prefix("app"), ["app", "apple", "applet", "apply"]);
+            assert_eq!(prefix("appl"), ["apple", "applet", "apply"]);
+            assert_eq!(prefix("ban"), ["banana", "band"]);
+            assert_eq!(prefix("/usr/"), ["/usr/bin", "/usr/lib"]);
+            assert!(prefix("c").is_empty());
+            assert!(prefix("apples").is_empty());
+            assert_eq!(prefix("").len(), keys.len());
+
+            let range = trie
+                .range("apple".."band")
+                .map(|(k, _)| k.to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(range, ["apple", "applet", "apply", "banana"]);
+            assert_eq!(trie.range("b"..="band").count(), 2);
+            assert_eq!(trie.range("z"..).count(), 2);
+            assert_eq!(trie.range("band".."apple").count(), 0);
+
+            let longest = |k: &str| {
+                trie.longest_prefix_of(k)
+                    .map(|(k, v)| (k.to_string(), v.to_native()))
+            };
+            assert_eq!(longest("/usr/bin/env"), Some(("/usr/bin".into(), 12)));
+            assert_eq!(longest("/usr/local"), Some(("/usr".into(), 11)));
+            assert_eq!(longest("zzz"), Some(("".into(), 0)));
+            assert_eq!(
+                format!("{:?}", trie.keys().nth(1).unwrap()),
+                "\"/usr\"",
+            );
+        });
+    }
+
+    #[test]
+    fn trie_matches_btree_map() {
+        use core::ops::Bound;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsTrie)]
+            inner: BTreeMap<String, u32>,
+        }
+
+        let value = Test {
+            inner: (0..1000).map(|i| ((i * 7).to_string(), i)).collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            let trie = &b.inner;
+            for prefix in ["", "1", "12", "123", "9", "99", "5000", "x"] {
+                let expected = a
+                    .inner
+                    .iter()
+                    .filter(|(k, _)| k.starts_with(prefix))
+                    .map(|(k, v)| (k.clone(), *v));
+                let actual = trie
+                    .prefix(prefix)
+                    .map(|(k, v)| (k.to_string(), v.to_native()));
+                assert!(expected.eq(actual));
+            }
+
+            for (start, end) in [("1", "2"), ("35", "357"), ("", "0")] {
+                let expected = a
+                    .inner
+                    .range::<str, _>((
+                        Bound::Included(start),
+                        Bound::Excluded(end),
+                    ))
+                    .map(|(k, v)| (k.clone(), *v));
+                let actual = trie
+                    .range(start..end)
+                    .map(|(k, v)| (k.to_string(), v.to_native()));
+                assert!(expected.eq(actual));
+            }
+            assert_eq!(trie.range("7".."").count(), 0);
+        });
+    }
+
+    #[test]
+    fn with_intern() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = Intern)]
+            a: String,
+            #[rkyv(with = Intern)]
+            b: String,
+            #[rkyv(with = Map<Intern>)]
+            c: Vec<Vec<u32>>,
+        }
+
+        let value = Test {
+            a: "a long repeated string".to_string(),
+            b: "a long repeated string".to_string(),
+            c: vec![vec![1, 2, 3], vec![4], vec![1, 2, 3]],
+        };
+
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.a.as_str(), a.a);
+            assert_eq!(b.b.as_str(), a.b);
+            assert_eq!(b.c.len(), a.c.len());
+            for (a, b) in a.c.iter().zip(b.c.iter()) {
+                assert_eq!(b.as_slice(), a.as_slice());
+            }
+
+            let deserialized = deserialize::<Test>(b);
+            assert_eq!(&deserialized, a);
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn intern_dedup() {
+        use rancor::Failure;
+
+        use crate::{
+            api::serialize_using,
+            ser::{
+                sharing::{Dedup, Share},
+                Serializer,
+            },
+            util::{with_arena, AlignedVec},
+        };
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Entry {
+            #[rkyv(with = Intern)]
+            section: String,
+            #[rkyv(with = Intern)]
+            tags: Vec<String>,
+        }
+
+        let entries = (0..100)
+            .map(|i| Entry {
+                section: ["first section", "second section"][i % 2].to_string(),
+                tags: vec!["common tag".to_string(), "other".to_string()],
+            })
+            .collect::<Vec<_>>();
+
+        let deduped = with_arena(|arena| {
+            let mut serializer = Serializer::new(
+                AlignedVec::<16>::new(),
+                arena.acquire(),
+                Dedup::new(),
+            );
+            serialize_using::<_, Failure>(&entries, &mut serializer).unwrap();
+            serializer.into_writer()
+        });
+        let shared = with_arena(|arena| {
+            let mut serializer = Serializer::new(
+                AlignedVec::<16>::new(),
+                arena.acquire(),
+                Share::new(),
+            );
+            serialize_using::<_, Failure>(&entries, &mut serializer).unwrap();
+            serializer.into_writer()
+        });
+        assert!(deduped.len() < shared.len() / 2);
+
+        let archived =
+            crate::access::<Archived<Vec<Entry>>, Failure>(&deduped).unwrap();
+        assert_eq!(archived.len(), 100);
+        for (i, entry) in archived.iter().enumerate() {
+            let expected = &*archived[i % 2].section;
+            assert!(core::ptr::eq(&*entry.section, expected));
+            assert_eq!(entry.tags.len(), 2);
+            assert!(core::ptr::eq(&*entry.tags, &*archived[0].tags));
+        }
+        assert_eq!(archived[1].section.as_str(), "second section");
+        assert_eq!(archived[0].tags[0], "common tag");
+    }
 }
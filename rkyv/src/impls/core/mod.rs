@@ -1,7 +1,8 @@
 use core::{
     alloc::{Layout, LayoutError},
+    any::type_name,
     marker::{PhantomData, PhantomPinned},
-    mem::{ManuallyDrop, MaybeUninit},
+    mem::{align_of, size_of, ManuallyDrop, MaybeUninit},
     ptr::{self, addr_of_mut},
     str,
 };
@@ -11,8 +12,11 @@ use rancor::Fallible;
 
 use crate::{
     primitive::ArchivedUsize,
-    ser::{Allocator, Writer, WriterExt as _},
-    traits::{ArchivePointee, CopyOptimization, LayoutRaw, NoUndef},
+    ser::{writer::Scope, Allocator, Writer, WriterExt as _},
+    traits::{
+        ArchivePointee, CopyOptimization, LayoutRaw, NoUndef, SizeHint,
+        SizeHintUnsized,
+    },
     tuple::*,
     Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized,
     Place, Portable, Serialize, SerializeUnsized,
@@ -73,9 +77,21 @@ where
     S: Fallible + Writer + ?Sized,
 {
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
-        let resolver = self.serialize(serializer)?;
-        serializer.align_for::<T::Archived>()?;
-        unsafe { serializer.resolve_aligned(self, resolver) }
+        serializer.enter_scope(Scope::Type(type_name::<T>()));
+        let result = (|| {
+            let resolver = self.serialize(serializer)?;
+            serializer.align_for::<T::Archived>()?;
+            unsafe { serializer.resolve_aligned(self, resolver) }
+        })();
+        serializer.exit_scope();
+        result
+    }
+}
+
+impl<T: SizeHint + Archive> SizeHintUnsized for T {
+    fn size_hint_unsized(&self) -> usize {
+        self.size_hint() + align_of::<T::Archived>() - 1
+            + size_of::<T::Archived>()
     }
 }
 
@@ -150,6 +166,15 @@ macro_rules! impl_tuple {
             }
         }
 
+        impl<$($type),*> SizeHint for ($($type,)*)
+        where
+            $($type: SizeHint,)*
+        {
+            fn size_hint(&self) -> usize {
+                0 $(+ self.$index.size_hint())*
+            }
+        }
+
         impl<$($type,)* D> Deserialize<($($type,)*), D>
             for $name<$($type::Archived,)*>
         where
@@ -238,6 +263,12 @@ where
     }
 }
 
+impl<T: SizeHint, const N: usize> SizeHint for [T; N] {
+    fn size_hint(&self) -> usize {
+        self.iter().map(T::size_hint).sum()
+    }
+}
+
 impl<T, D, const N: usize> Deserialize<[T; N], D> for [T::Archived; N]
 where
     T: Archive,
@@ -286,44 +317,64 @@ where
     S: Fallible + Allocator + Writer + ?Sized,
 {
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
-        if T::COPY_OPTIMIZATION.is_enabled() {
-            let result = serializer.align_for::<T::Archived>()?;
-            let as_bytes = unsafe {
-                core::slice::from_raw_parts(
-                    self.as_ptr().cast::<u8>(),
-                    core::mem::size_of_val(self),
-                )
-            };
-            serializer.write(as_bytes)?;
-
-            Ok(result)
-        } else {
-            use crate::util::SerVec;
-
-            SerVec::with_capacity(
-                serializer,
-                self.len(),
-                |resolvers, serializer| {
-                    for value in self.iter() {
-                        unsafe {
-                            resolvers
-                                .push_unchecked(value.serialize(serializer)?);
-                        }
+        serializer.enter_scope(Scope::Type(type_name::<[T]>()));
+        let result = serialize_slice(self, serializer);
+        serializer.exit_scope();
+        result
+    }
+}
+
+fn serialize_slice<T, S>(
+    slice: &[T],
+    serializer: &mut S,
+) -> Result<usize, S::Error>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    if T::COPY_OPTIMIZATION.is_enabled() {
+        let result = serializer.align_for::<T::Archived>()?;
+        let as_bytes = unsafe {
+            core::slice::from_raw_parts(
+                slice.as_ptr().cast::<u8>(),
+                core::mem::size_of_val(slice),
+            )
+        };
+        serializer.write(as_bytes)?;
+
+        Ok(result)
+    } else {
+        use crate::util::SerVec;
+
+        SerVec::with_capacity(
+            serializer,
+            slice.len(),
+            |resolvers, serializer| {
+                for value in slice.iter() {
+                    unsafe {
+                        resolvers.push_unchecked(value.serialize(serializer)?);
                     }
+                }
 
-                    let result = serializer.align_for::<T::Archived>()?;
+                let result = serializer.align_for::<T::Archived>()?;
 
-                    for (value, resolver) in self.iter().zip(resolvers.drain())
-                    {
-                        unsafe {
-                            serializer.resolve_aligned(value, resolver)?;
-                        }
+                for (value, resolver) in slice.iter().zip(resolvers.drain()) {
+                    unsafe {
+                        serializer.resolve_aligned(value, resolver)?;
                     }
+                }
 
-                    Ok(result)
-                },
-            )?
-        }
+                Ok(result)
+            },
+        )?
+    }
+}
+
+impl<T: SizeHint + Archive> SizeHintUnsized for [T] {
+    fn size_hint_unsized(&self) -> usize {
+        self.iter().map(T::size_hint).sum::<usize>() + align_of::<T::Archived>()
+            - 1
+            + size_of::<T::Archived>() * self.len()
     }
 }
 
@@ -390,11 +441,20 @@ impl ArchivePointee for str {
 impl<S: Fallible + Writer + ?Sized> SerializeUnsized<S> for str {
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
         let result = serializer.pos();
-        serializer.write(self.as_bytes())?;
+        serializer.enter_scope(Scope::Type("str"));
+        let written = serializer.write(self.as_bytes());
+        serializer.exit_scope();
+        written?;
         Ok(result)
     }
 }
 
+impl SizeHintUnsized for str {
+    fn size_hint_unsized(&self) -> usize {
+        self.len()
+    }
+}
+
 impl<D: Fallible + ?Sized> DeserializeUnsized<str, D> for str {
     unsafe fn deserialize_unsized(
         &self,
@@ -444,6 +504,12 @@ impl<T: ?Sized, S: Fallible + ?Sized> Serialize<S> for PhantomData<T> {
     }
 }
 
+impl<T: ?Sized> SizeHint for PhantomData<T> {
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
 impl<T: ?Sized, D: Fallible + ?Sized> Deserialize<PhantomData<T>, D>
     for PhantomData<T>
 {
@@ -475,6 +541,12 @@ impl<S: Fallible + ?Sized> Serialize<S> for PhantomPinned {
     }
 }
 
+impl SizeHint for PhantomPinned {
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
 impl<D: Fallible + ?Sized> Deserialize<PhantomPinned, D> for PhantomPinned {
     fn deserialize(&self, _: &mut D) -> Result<PhantomPinned, D::Error> {
         Ok(PhantomPinned)
@@ -510,6 +582,12 @@ impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for ManuallyDrop<T> {
     }
 }
 
+impl<T: SizeHint> SizeHint for ManuallyDrop<T> {
+    fn size_hint(&self) -> usize {
+        T::size_hint(self)
+    }
+}
+
 impl<T, D> Deserialize<ManuallyDrop<T>, D> for ManuallyDrop<T::Archived>
 where
     T: Archive,
@@ -4,8 +4,9 @@ use munge::munge;
 use rancor::Fallible;
 
 use crate::{
-    option::ArchivedOption, traits::NoUndef, Archive, Deserialize, Place,
-    Serialize,
+    option::ArchivedOption,
+    traits::{NoUndef, SizeHint},
+    Archive, Deserialize, Place, Serialize,
 };
 
 #[allow(dead_code)]
@@ -71,6 +72,12 @@ impl<T: Serialize<S>, S: Fallible + ?Sized> Serialize<S> for Option<T> {
     }
 }
 
+impl<T: SizeHint> SizeHint for Option<T> {
+    fn size_hint(&self) -> usize {
+        self.as_ref().map_or(0, T::size_hint)
+    }
+}
+
 impl<T, D> Deserialize<Option<T>, D> for ArchivedOption<T::Archived>
 where
     T: Archive,
@@ -14,7 +14,7 @@ use crate::{
         ArchivedNonZeroU32, ArchivedNonZeroU64, ArchivedNonZeroUsize,
         ArchivedU128, ArchivedU16, ArchivedU32, ArchivedU64, ArchivedUsize,
     },
-    traits::{CopyOptimization, NoUndef},
+    traits::{CopyOptimization, NoUndef, SizeHint},
     Archive, Deserialize, Place, Portable, Serialize,
 };
 
@@ -119,6 +119,13 @@ macro_rules! impl_serialize_noop {
                 Ok(())
             }
         }
+
+        impl SizeHint for $type {
+            #[inline]
+            fn size_hint(&self) -> usize {
+                0
+            }
+        }
     };
 }
 
@@ -277,6 +284,13 @@ impl<S: Fallible + ?Sized> Serialize<S> for usize {
     }
 }
 
+impl SizeHint for usize {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
 impl<D: Fallible + ?Sized> Deserialize<usize, D> for ArchivedUsize {
     fn deserialize(&self, _: &mut D) -> Result<usize, D::Error> {
         Ok(self.to_native() as usize)
@@ -308,6 +322,13 @@ impl<S: Fallible + ?Sized> Serialize<S> for isize {
     }
 }
 
+impl SizeHint for isize {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
 impl<D: Fallible + ?Sized> Deserialize<isize, D> for ArchivedIsize {
     fn deserialize(&self, _: &mut D) -> Result<isize, D::Error> {
         Ok(self.to_native() as isize)
@@ -341,6 +362,13 @@ impl<S: Fallible + ?Sized> Serialize<S> for NonZeroUsize {
     }
 }
 
+impl SizeHint for NonZeroUsize {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
 impl<D> Deserialize<NonZeroUsize, D> for ArchivedNonZeroUsize
 where
     D: Fallible + ?Sized,
@@ -377,6 +405,13 @@ impl<S: Fallible + ?Sized> Serialize<S> for NonZeroIsize {
     }
 }
 
+impl SizeHint for NonZeroIsize {
+    #[inline]
+    fn size_hint(&self) -> usize {
+        0
+    }
+}
+
 impl<D> Deserialize<NonZeroIsize, D> for ArchivedNonZeroIsize
 where
     D: Fallible + ?Sized,
@@ -455,4 +490,49 @@ mod tests {
             assert_eq!(*a, NonZeroUsize::try_from(b.to_native()).unwrap())
         });
     }
+
+    #[test]
+    fn archived_primitive_slices() {
+        use crate::{
+            api::test::to_archived, primitive::ArchivedPrimitiveSlice,
+        };
+
+        to_archived(&[1u32, 2, 3, 4], |archived| {
+            let mut out = [0u32; 4];
+            archived.copy_to_native_slice(&mut out);
+            assert_eq!(out, [1, 2, 3, 4]);
+
+            match_native_format! {
+                native: {
+                    assert_eq!(archived.as_native_slice(), &[1, 2, 3, 4]);
+                },
+                fallback: {},
+            }
+        });
+
+        to_archived(&[1.5f64, -2.0], |archived| {
+            let mut out = [0f64; 2];
+            archived.copy_to_native_slice(&mut out);
+            assert_eq!(out, [1.5, -2.0]);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn archived_primitive_slices_cow() {
+        use crate::{
+            alloc::{borrow::Cow, vec, vec::Vec},
+            api::test::to_archived,
+            primitive::{ArchivedPrimitiveSlice, NATIVE_FORMAT},
+        };
+
+        to_archived(&vec![1i64, -2, 3], |archived| {
+            let native = archived.to_native_cow();
+            assert_eq!(*native, [1, -2, 3]);
+            assert_eq!(matches!(native, Cow::Borrowed(_)), NATIVE_FORMAT);
+        });
+        to_archived(&Vec::<u16>::new(), |archived| {
+            assert!(archived.to_native_cow().is_empty());
+        });
+    }
 }
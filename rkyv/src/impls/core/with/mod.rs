@@ -9,6 +9,8 @@ mod niching;
 
 use core::{
     cell::{Cell, UnsafeCell},
+    error::Error,
+    fmt,
     hash::{Hash, Hasher},
     hint::unreachable_unchecked,
     marker::PhantomData,
@@ -20,9 +22,10 @@ use core::{
 };
 
 use munge::munge;
-use rancor::Fallible;
+use rancor::{fail, Fallible, Source};
 
 use crate::{
+    aligned::{Alignment, ArchivedAligned, ConstAlign},
     boxed::{ArchivedBox, BoxResolver},
     niche::{
         niched_option::NichedOption,
@@ -38,10 +41,14 @@ use crate::{
     },
     option::ArchivedOption,
     primitive::{FixedNonZeroIsize, FixedNonZeroUsize},
+    rc::{ArchivedRc, InternFlavor, RcResolver},
+    ser::{Interning, InterningExt as _, Writer, WriterExt as _},
     traits::NoUndef,
+    vec::bits::{ArchivedBitVec, BitPackable, BitVecResolver},
     with::{
-        ArchiveWith, AsBox, DeserializeWith, Identity, Inline, InlineAsBox,
-        Map, MapNiche, Niche, NicheInto, SerializeWith, Skip, Unsafe,
+        Aligned, ArchiveWith, AsBox, BitPacked, DeserializeWith, Identity,
+        Inline, InlineAsBox, Intern, Map, MapNiche, Niche, NicheInto,
+        SerializeWith, Skip, Unsafe,
     },
     Archive, ArchiveUnsized, Deserialize, Place, Serialize, SerializeUnsized,
 };
@@ -596,6 +603,131 @@ impl<F: Serialize<S>, S: Fallible + ?Sized> SerializeWith<&F, S> for Inline {
     }
 }
 
+// Aligned
+
+impl<F: Archive, const N: usize> ArchiveWith<F> for Aligned<N>
+where
+    ConstAlign<N>: Alignment,
+{
+    type Archived = ArchivedAligned<F::Archived, N>;
+    type Resolver = F::Resolver;
+
+    fn resolve_with(
+        field: &F,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field.resolve(resolver, ArchivedAligned::place_value(out));
+    }
+}
+
+impl<F, S, const N: usize> SerializeWith<F, S> for Aligned<N>
+where
+    F: Serialize<S>,
+    S: Fallible + ?Sized,
+    ConstAlign<N>: Alignment,
+{
+    fn serialize_with(
+        field: &F,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field.serialize(serializer)
+    }
+}
+
+impl<F, D, const N: usize>
+    DeserializeWith<ArchivedAligned<F::Archived, N>, F, D> for Aligned<N>
+where
+    F: Archive,
+    F::Archived: Deserialize<F, D>,
+    D: Fallible + ?Sized,
+    ConstAlign<N>: Alignment,
+{
+    fn deserialize_with(
+        field: &ArchivedAligned<F::Archived, N>,
+        deserializer: &mut D,
+    ) -> Result<F, D::Error> {
+        field.get().deserialize(deserializer)
+    }
+}
+
+// BitPacked
+
+#[derive(Debug)]
+struct BitPackedLengthError {
+    expected: usize,
+    actual: usize,
+}
+
+impl fmt::Display for BitPackedLengthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} bit-packed values but found {}",
+            self.expected, self.actual,
+        )
+    }
+}
+
+impl Error for BitPackedLengthError {}
+
+impl<T, const BITS: u32, const N: usize> ArchiveWith<[T; N]> for BitPacked<BITS>
+where
+    T: BitPackable,
+{
+    type Archived = ArchivedBitVec<T, BITS>;
+    type Resolver = BitVecResolver;
+
+    fn resolve_with(
+        _: &[T; N],
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedBitVec::<T, BITS>::resolve_from_len(N, resolver, out);
+    }
+}
+
+impl<T, S, const BITS: u32, const N: usize> SerializeWith<[T; N], S>
+    for BitPacked<BITS>
+where
+    T: BitPackable,
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &[T; N],
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBitVec::<T, BITS>::serialize_from_iter(
+            field.iter().copied(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D, const BITS: u32, const N: usize>
+    DeserializeWith<ArchivedBitVec<T, BITS>, [T; N], D> for BitPacked<BITS>
+where
+    T: BitPackable,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize_with(
+        field: &ArchivedBitVec<T, BITS>,
+        _: &mut D,
+    ) -> Result<[T; N], D::Error> {
+        if field.len() != N {
+            fail!(BitPackedLengthError {
+                expected: N,
+                actual: field.len(),
+            });
+        }
+
+        let mut iter = field.iter();
+        Ok(core::array::from_fn(|_| iter.next().unwrap()))
+    }
+}
+
 // Unsafe
 
 impl<F: Archive> ArchiveWith<UnsafeCell<F>> for Unsafe {
@@ -701,6 +833,55 @@ impl<F: Default, D: Fallible + ?Sized> DeserializeWith<(), F, D> for Skip {
     }
 }
 
+// Intern
+
+impl<F: Archive> ArchiveWith<F> for Intern {
+    type Archived = ArchivedRc<F::Archived, InternFlavor>;
+    type Resolver = RcResolver;
+
+    fn resolve_with(
+        field: &F,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedRc::resolve_from_ref(field, resolver, out);
+    }
+}
+
+impl<F, S> SerializeWith<F, S> for Intern
+where
+    F: Serialize<S> + Hash + Eq + Clone + 'static,
+    S: Fallible + Writer + Interning + ?Sized,
+{
+    fn serialize_with(
+        field: &F,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        let pos = serializer.serialize_interned(field)?;
+
+        if serializer.pos() == pos {
+            serializer.pad(1)?;
+        }
+
+        Ok(RcResolver::from_pos(pos))
+    }
+}
+
+impl<F, D> DeserializeWith<ArchivedRc<F::Archived, InternFlavor>, F, D>
+    for Intern
+where
+    F: Archive,
+    F::Archived: Deserialize<F, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedRc<F::Archived, InternFlavor>,
+        deserializer: &mut D,
+    ) -> Result<F, D::Error> {
+        field.get().deserialize(deserializer)
+    }
+}
+
 // Identity
 
 impl<F: Archive> ArchiveWith<F> for Identity {
@@ -1156,4 +1337,83 @@ mod tests {
             assert_eq!(archived.other, 10);
         });
     }
+
+    #[test]
+    fn with_bit_packed_array() {
+        use crate::with::BitPacked;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = BitPacked<1>)]
+            flags: [bool; 12],
+            #[rkyv(with = BitPacked<4>)]
+            nibbles: [u8; 3],
+        }
+
+        let value = Test {
+            flags: [
+                true, false, true, true, false, false, false, true, true,
+                false, false, true,
+            ],
+            nibbles: [0x1, 0xf, 0x7],
+        };
+        roundtrip_with(&value, |value, archived| {
+            assert_eq!(archived.flags, value.flags);
+            assert_eq!(archived.flags.get(3), Some(true));
+            assert_eq!(archived.flags.get(12), None);
+            assert_eq!(archived.flags.as_bytes(), &[0b1000_1101, 0b1001]);
+            assert_eq!(archived.nibbles, value.nibbles);
+            assert_eq!(archived.nibbles.iter().next_back(), Some(0x7));
+        });
+    }
+
+    #[cfg(all(
+        not(feature = "pointer_width_16"),
+        not(feature = "pointer_width_64"),
+        not(feature = "big_endian"),
+        feature = "bytecheck",
+    ))]
+    #[test]
+    fn invalid_bit_packed_tail() {
+        use rancor::Failure;
+
+        use crate::{api::low::access, util::Align, vec::bits::ArchivedBitVec};
+
+        let data = Align([
+            0b0000_0101,
+            0x00,
+            0x00,
+            0x00, // bits
+            0xfc,
+            0xff,
+            0xff,
+            0xff, // RelPtr with offset -4
+            0x03,
+            0x00,
+            0x00,
+            0x00, // len
+        ]);
+        let archived =
+            access::<ArchivedBitVec<bool, 1>, Failure>(&*data).unwrap();
+        assert_eq!(archived, &[true, false, true]);
+
+        // This buffer is invalid because the unused bits of the last byte are
+        // not zero
+        let data = Align([
+            0b1000_0101,
+            0x00,
+            0x00,
+            0x00, // bits
+            0xfc,
+            0xff,
+            0xff,
+            0xff, // RelPtr with offset -4
+            0x03,
+            0x00,
+            0x00,
+            0x00, // len
+        ]);
+        access::<ArchivedBitVec<bool, 1>, Failure>(&*data).unwrap_err();
+    }
 }
@@ -3,8 +3,12 @@ use rancor::Fallible;
 
 use crate::{
     ser::{Allocator, Writer},
-    vec::{ArchivedVec, VecResolver},
-    Archive, Archived, Deserialize, Place, Serialize,
+    vec::{
+        small::{ArchivedSmallVec, SmallVecResolver},
+        ArchivedVec, VecResolver,
+    },
+    with::{ArchiveWith, DeserializeWith, InlineSmall, SerializeWith},
+    Archive, Archived, Deserialize, Place, Resolver, Serialize,
 };
 
 impl<T, const CAP: usize> Archive for ArrayVec<T, CAP>
@@ -75,10 +79,64 @@ where
     }
 }
 
+impl<T, const CAP: usize, const N: usize> ArchiveWith<ArrayVec<T, CAP>>
+    for InlineSmall<N>
+where
+    T: Archive,
+{
+    type Archived = ArchivedSmallVec<Archived<T>, N>;
+    type Resolver = SmallVecResolver<Resolver<T>, N>;
+
+    fn resolve_with(
+        field: &ArrayVec<T, CAP>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSmallVec::resolve_from_slice(field.as_slice(), resolver, out);
+    }
+}
+
+impl<T, S, const CAP: usize, const N: usize> SerializeWith<ArrayVec<T, CAP>, S>
+    for InlineSmall<N>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &ArrayVec<T, CAP>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSmallVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D, const CAP: usize, const N: usize>
+    DeserializeWith<ArchivedSmallVec<Archived<T>, N>, ArrayVec<T, CAP>, D>
+    for InlineSmall<N>
+where
+    T: Archive,
+    Archived<T>: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSmallVec<Archived<T>, N>,
+        deserializer: &mut D,
+    ) -> Result<ArrayVec<T, CAP>, D::Error> {
+        let mut result = ArrayVec::new();
+        for item in field.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ArrayVec;
-    use crate::api::test::roundtrip_with;
+    use crate::{
+        api::test::roundtrip_with, with::InlineSmall, Archive, Deserialize,
+        Serialize,
+    };
 
     #[test]
     fn roundtrip_array_vec() {
@@ -86,4 +144,33 @@ mod tests {
             assert_eq!(**a, **b)
         });
     }
+
+    #[test]
+    fn roundtrip_array_vec_inline_small() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = InlineSmall<1>)]
+            value: ArrayVec<i32, 4>,
+        }
+
+        roundtrip_with(
+            &Test {
+                value: ArrayVec::from_iter([10]),
+            },
+            |a, b| {
+                assert!(b.value.is_inline());
+                assert_eq!(*a.value, *b.value);
+            },
+        );
+        roundtrip_with(
+            &Test {
+                value: ArrayVec::from([10, 20, 40, 80]),
+            },
+            |a, b| {
+                assert!(!b.value.is_inline());
+                assert_eq!(*a.value, *b.value);
+            },
+        );
+    }
 }
@@ -8,6 +8,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::{ArchivedHashMap, HashMapResolver},
+    hash::FxHasher64,
     impls::core::with::RefWrapper,
     ser::{Allocator, Writer},
     with::{ArchiveWith, DeserializeWith, MapKV, SerializeWith},
@@ -44,13 +45,12 @@ where
     S: Fallible + Writer + Allocator + ?Sized,
     S::Error: Source,
     H: Default + BuildHasher,
-    H::Hasher: Default,
 {
     fn serialize_with(
         field: &HashMap<K, V, H>,
         serializer: &mut S,
     ) -> Result<Self::Resolver, <S as Fallible>::Error> {
-        ArchivedHashMap::<_, _, H::Hasher>::serialize_from_iter(
+        ArchivedHashMap::<_, _, FxHasher64>::serialize_from_iter(
             field.iter().map(|(k, v)| {
                 (
                     RefWrapper::<'_, A, K>(k, PhantomData::<A>),
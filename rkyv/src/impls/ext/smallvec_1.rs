@@ -3,8 +3,12 @@ use smallvec_1::{Array, SmallVec};
 
 use crate::{
     ser::{Allocator, Writer},
-    vec::{ArchivedVec, VecResolver},
-    Archive, Archived, Deserialize, Place, Serialize,
+    vec::{
+        small::{ArchivedSmallVec, SmallVecResolver},
+        ArchivedVec, VecResolver,
+    },
+    with::{ArchiveWith, DeserializeWith, InlineSmall, SerializeWith},
+    Archive, Archived, Deserialize, Place, Resolver, Serialize,
 };
 
 impl<A: Array> Archive for SmallVec<A>
@@ -78,15 +82,99 @@ where
     }
 }
 
+impl<A, const N: usize> ArchiveWith<SmallVec<A>> for InlineSmall<N>
+where
+    A: Array,
+    A::Item: Archive,
+{
+    type Archived = ArchivedSmallVec<Archived<A::Item>, N>;
+    type Resolver = SmallVecResolver<Resolver<A::Item>, N>;
+
+    fn resolve_with(
+        field: &SmallVec<A>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSmallVec::resolve_from_slice(field.as_slice(), resolver, out);
+    }
+}
+
+impl<A, S, const N: usize> SerializeWith<SmallVec<A>, S> for InlineSmall<N>
+where
+    A: Array,
+    A::Item: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &SmallVec<A>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSmallVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<A, D, const N: usize>
+    DeserializeWith<ArchivedSmallVec<Archived<A::Item>, N>, SmallVec<A>, D>
+    for InlineSmall<N>
+where
+    A: Array,
+    A::Item: Archive,
+    Archived<A::Item>: Deserialize<A::Item, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSmallVec<Archived<A::Item>, N>,
+        deserializer: &mut D,
+    ) -> Result<SmallVec<A>, D::Error> {
+        let mut result = SmallVec::new();
+        for item in field.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use smallvec_1::{smallvec, SmallVec};
 
-    use crate::api::test::roundtrip_with;
+    use crate::{
+        api::test::roundtrip_with, with::InlineSmall, Archive, Deserialize,
+        Serialize,
+    };
 
     #[test]
     fn roundtrip_small_vec() {
         let value: SmallVec<[i32; 4]> = smallvec![10, 20, 40, 80];
         roundtrip_with(&value, |a, b| assert_eq!(**a, **b));
     }
+
+    #[test]
+    fn roundtrip_small_vec_inline_small() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = InlineSmall<2>)]
+            value: SmallVec<[i32; 4]>,
+        }
+
+        roundtrip_with(
+            &Test {
+                value: smallvec![10, 20],
+            },
+            |a, b| {
+                assert!(b.value.is_inline());
+                assert_eq!(*a.value, *b.value);
+            },
+        );
+        roundtrip_with(
+            &Test {
+                value: smallvec![10, 20, 40, 80],
+            },
+            |a, b| {
+                assert!(!b.value.is_inline());
+                assert_eq!(*a.value, *b.value);
+            },
+        );
+    }
 }
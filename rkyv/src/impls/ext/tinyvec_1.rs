@@ -8,6 +8,12 @@ use crate::{
     vec::{ArchivedVec, VecResolver},
     Archive, Archived, Deserialize, Place, Serialize,
 };
+#[cfg(feature = "alloc")]
+use crate::{
+    vec::small::{ArchivedSmallVec, SmallVecResolver},
+    with::{ArchiveWith, DeserializeWith, InlineSmall, SerializeWith},
+    Resolver,
+};
 
 // ArrayVec
 
@@ -132,6 +138,61 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<A, const N: usize> ArchiveWith<TinyVec<A>> for InlineSmall<N>
+where
+    A: Array,
+    A::Item: Archive,
+{
+    type Archived = ArchivedSmallVec<Archived<A::Item>, N>;
+    type Resolver = SmallVecResolver<Resolver<A::Item>, N>;
+
+    fn resolve_with(
+        field: &TinyVec<A>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSmallVec::resolve_from_slice(field.as_slice(), resolver, out);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A, S, const N: usize> SerializeWith<TinyVec<A>, S> for InlineSmall<N>
+where
+    A: Array,
+    A::Item: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &TinyVec<A>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSmallVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<A, D, const N: usize>
+    DeserializeWith<ArchivedSmallVec<Archived<A::Item>, N>, TinyVec<A>, D>
+    for InlineSmall<N>
+where
+    A: Array,
+    A::Item: Archive,
+    Archived<A::Item>: Deserialize<A::Item, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSmallVec<Archived<A::Item>, N>,
+        deserializer: &mut D,
+    ) -> Result<TinyVec<A>, D::Error> {
+        let mut result = TinyVec::new();
+        for item in field.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
 impl<T, A> PartialEq<ArrayVec<A>> for ArchivedVec<T>
 where
     A: Array,
@@ -220,4 +281,38 @@ mod tests {
             assert_eq!(**a, **b)
         });
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn roundtrip_tiny_vec_inline_small() {
+        use tinyvec_1::{tiny_vec, TinyVec};
+
+        use crate::{with::InlineSmall, Archive, Deserialize, Serialize};
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = InlineSmall<2>)]
+            value: TinyVec<[i32; 2]>,
+        }
+
+        roundtrip_with(
+            &Test {
+                value: tiny_vec!([i32; 2] => 10, 20),
+            },
+            |a, b| {
+                assert!(b.value.is_inline());
+                assert_eq!(*a.value, *b.value);
+            },
+        );
+        roundtrip_with(
+            &Test {
+                value: tiny_vec!([i32; 2] => 10, 20, 40, 80),
+            },
+            |a, b| {
+                assert!(!b.value.is_inline());
+                assert_eq!(*a.value, *b.value);
+            },
+        );
+    }
 }
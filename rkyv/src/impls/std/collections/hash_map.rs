@@ -110,7 +110,10 @@ mod tests {
     use ahash::RandomState;
 
     use crate::{
-        api::test::{roundtrip, roundtrip_with, to_archived},
+        api::test::{
+            roundtrip, roundtrip_with, to_archived, to_archived_from_bytes,
+            to_bytes,
+        },
         collections::swiss_table::ArchivedHashMap,
         string::ArchivedString,
         Archive, Archived, Deserialize, Serialize,
@@ -261,6 +264,46 @@ mod tests {
         });
     }
 
+    #[test]
+    fn remove_seal() {
+        let map = (0..200)
+            .map(|i| (i.to_string(), i))
+            .collect::<HashMap<_, _>>();
+
+        to_bytes(&map, |bytes| {
+            to_archived_from_bytes::<HashMap<String, i32>>(
+                bytes,
+                |mut archived| {
+                    for i in (0..200).step_by(2) {
+                        let key = i.to_string();
+                        assert!(ArchivedHashMap::remove_seal(
+                            archived.as_mut(),
+                            key.as_str(),
+                        ));
+                        assert!(!ArchivedHashMap::remove_seal(
+                            archived.as_mut(),
+                            key.as_str(),
+                        ));
+                    }
+                },
+            );
+
+            // Removing entries leaves a valid hash map
+            to_archived_from_bytes::<HashMap<String, i32>>(bytes, |archived| {
+                assert_eq!(archived.len(), 100);
+                assert_eq!(archived.iter().count(), 100);
+                for i in 0..200 {
+                    let value = archived.get(i.to_string().as_str());
+                    if i % 2 == 0 {
+                        assert!(value.is_none());
+                    } else {
+                        assert_eq!(*value.unwrap(), i);
+                    }
+                }
+            });
+        });
+    }
+
     #[test]
     fn large_hash_map() {
         let mut map = std::collections::HashMap::new();
@@ -270,6 +313,160 @@ mod tests {
         roundtrip_with(&map, assert_equal);
     }
 
+    #[test]
+    fn hash_table_group_widths() {
+        use rancor::{Fallible, Source};
+
+        use crate::{
+            collections::swiss_table::{ArchivedHashTable, HashTableResolver},
+            hash::{hash_value, FxHasher64},
+            ser::{Allocator, Writer},
+            Archived, Place,
+        };
+
+        const LOAD_FACTOR: (usize, usize) = (7, 8);
+
+        struct Table {
+            values: Vec<u32>,
+            group_width: usize,
+        }
+
+        impl Archive for Table {
+            type Archived = ArchivedHashTable<Archived<u32>>;
+            type Resolver = HashTableResolver;
+
+            fn resolve(
+                &self,
+                resolver: Self::Resolver,
+                out: Place<Self::Archived>,
+            ) {
+                ArchivedHashTable::resolve_from_len(
+                    self.values.len(),
+                    LOAD_FACTOR,
+                    resolver,
+                    out,
+                );
+            }
+        }
+
+        impl<S> Serialize<S> for Table
+        where
+            S: Fallible + Writer + Allocator + ?Sized,
+            S::Error: Source,
+        {
+            fn serialize(
+                &self,
+                serializer: &mut S,
+            ) -> Result<Self::Resolver, S::Error> {
+                ArchivedHashTable::serialize_from_iter_with_group_width(
+                    self.values.iter().copied(),
+                    self.values.iter().map(hash_value::<u32, FxHasher64>),
+                    LOAD_FACTOR,
+                    self.group_width,
+                    serializer,
+                )
+            }
+        }
+
+        // Tables of every group width can be probed on every target.
+        for group_width in [16, 32, 64] {
+            for len in [1, 10, 100, 1000] {
+                let table = Table {
+                    values: (0..len).map(|i| i * 3).collect(),
+                    group_width,
+                };
+                to_archived(&table, |archived| {
+                    assert_eq!(archived.group_width(), group_width);
+                    assert_eq!(archived.len(), len as usize);
+                    for i in 0..3 * len {
+                        let hash = hash_value::<u32, FxHasher64>(&i);
+                        let found = archived.get_with(hash, |v| *v == i);
+                        assert_eq!(found.is_some(), i % 3 == 0);
+                    }
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn serialize_from_iter_chunked() {
+        use rancor::{Fallible, Source};
+
+        use crate::{
+            api::test::{to_archived_from_bytes, to_bytes},
+            collections::swiss_table::HashMapResolver,
+            ser::{Allocator, Writer},
+            Place,
+        };
+
+        struct Chunked {
+            map: HashMap<String, u32>,
+            chunk_len: usize,
+        }
+
+        impl Archive for Chunked {
+            type Archived = ArchivedHashMap<ArchivedString, Archived<u32>>;
+            type Resolver = HashMapResolver;
+
+            fn resolve(
+                &self,
+                resolver: Self::Resolver,
+                out: Place<Self::Archived>,
+            ) {
+                ArchivedHashMap::resolve_from_len(
+                    self.map.len(),
+                    (7, 8),
+                    resolver,
+                    out,
+                );
+            }
+        }
+
+        impl<S> Serialize<S> for Chunked
+        where
+            S: Fallible + Writer + Allocator + ?Sized,
+            S::Error: Source,
+        {
+            fn serialize(
+                &self,
+                serializer: &mut S,
+            ) -> Result<Self::Resolver, S::Error> {
+                <Self::Archived>::serialize_from_iter_chunked::<
+                    _,
+                    _,
+                    _,
+                    String,
+                    u32,
+                    _,
+                >(
+                    self.map.iter(), (7, 8), self.chunk_len, serializer
+                )
+            }
+        }
+
+        // Keys are long enough to be stored out-of-line, so they must also be
+        // serialized in the same order.
+        let map = (0..1000)
+            .map(|i| (format!("a key which is not inline {i}"), i))
+            .collect::<HashMap<_, _>>();
+        let mut expected = Vec::new();
+        to_bytes(&map, |bytes| expected.extend_from_slice(bytes));
+
+        // Chunked serialization writes the same bytes for any chunk length.
+        for chunk_len in [0, 1, 7, 100, usize::MAX] {
+            let value = Chunked {
+                map: map.clone(),
+                chunk_len,
+            };
+            to_bytes(&value, |bytes| {
+                assert_eq!(bytes, expected.as_slice());
+                to_archived_from_bytes::<Chunked>(bytes, |archived| {
+                    assert_equal(&map, &archived);
+                });
+            });
+        }
+    }
+
     #[cfg(feature = "bytecheck")]
     #[test]
     fn nested_hash_map() {
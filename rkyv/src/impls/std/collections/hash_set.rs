@@ -7,7 +7,10 @@ use std::collections::HashSet;
 use rancor::{Fallible, Source};
 
 use crate::{
-    collections::swiss_table::set::{ArchivedHashSet, HashSetResolver},
+    collections::{
+        set_ops::Contains,
+        swiss_table::set::{ArchivedHashSet, HashSetResolver},
+    },
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -88,11 +91,25 @@ impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
     }
 }
 
+impl<K, Q, S> Contains<Q> for HashSet<K, S>
+where
+    K: Hash + Eq + Borrow<Q>,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    fn contains(&self, value: &Q) -> bool {
+        HashSet::contains(self, value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
-    use crate::api::test::{roundtrip, roundtrip_with};
+    use crate::{
+        api::test::{roundtrip, roundtrip_with, to_archived},
+        string::ArchivedString,
+    };
 
     #[test]
     fn roundtrip_hash_set() {
@@ -122,4 +139,57 @@ mod tests {
         value.insert(());
         roundtrip(&value);
     }
+
+    fn sorted<'a>(
+        iter: impl Iterator<Item = &'a ArchivedString>,
+    ) -> Vec<&'a str> {
+        let mut keys = iter.map(ArchivedString::as_str).collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn hash_set_ops() {
+        let a = HashSet::from(["a", "b", "c"].map(str::to_string));
+        let b = HashSet::from(["b", "c", "d"].map(str::to_string));
+
+        to_archived(&(a.clone(), b.clone()), |archived| {
+            let (x, y) = (&archived.0, &archived.1);
+
+            assert_eq!(sorted(x.intersection(y)), ["b", "c"]);
+            assert_eq!(sorted(x.difference(y)), ["a"]);
+            assert_eq!(sorted(y.difference(x)), ["d"]);
+            assert_eq!(sorted(x.union(y)), ["a", "b", "c", "d"]);
+            assert!(!x.is_subset(y));
+            assert!(!x.is_disjoint(y));
+            assert!(x.is_subset(x));
+
+            let in_b = |k: &ArchivedString| b.contains(k.as_str());
+            assert_eq!(sorted(x.intersection(&in_b)), ["b", "c"]);
+            assert_eq!(sorted(x.difference(&in_b)), ["a"]);
+
+            let in_a = |k: &ArchivedString| a.contains(k.as_str());
+            assert!(x.is_subset(&in_a));
+            assert!(!y.is_subset(&in_a));
+        });
+    }
+
+    #[test]
+    fn hash_set_ops_native() {
+        let value = HashSet::from([1u8, 2, 3, 4]);
+        let native = HashSet::from([3u8, 4, 5]);
+
+        to_archived(&value, |archived| {
+            let mut common = archived.intersection(&native).collect::<Vec<_>>();
+            common.sort_unstable();
+            assert_eq!(common, [&3, &4]);
+
+            let mut only = archived.difference(&native).collect::<Vec<_>>();
+            only.sort_unstable();
+            assert_eq!(only, [&1, &2]);
+
+            assert!(archived.is_disjoint(&HashSet::from([0u8, 5])));
+            assert!(archived.is_subset(&HashSet::from([0u8, 1, 2, 3, 4])));
+        });
+    }
 }
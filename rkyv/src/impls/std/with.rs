@@ -15,19 +15,28 @@ use rancor::{Fallible, OptionExt, ResultExt, Source};
 
 use crate::{
     collections::{
-        swiss_table::{ArchivedHashMap, HashMapResolver},
+        perfect_hash::{
+            ArchivedPerfectHashMap, ArchivedPerfectHashSet,
+            PerfectHashMapResolver, PerfectHashSetResolver,
+        },
+        sorted_vec::{ArchivedSortedMap, SortedMapResolver},
+        swiss_table::{
+            ArchivedHashMap, ArchivedHashSet, HashMapResolver, HashSetResolver,
+        },
+        trie::{ArchivedTrieMap, TrieMapResolver},
         util::{Entry, EntryAdapter},
     },
     ffi::{ArchivedCString, CStringResolver},
-    hash::FxHasher64,
+    hash::{FxHasher64, TableHasher},
     impls::core::with::RefWrapper,
     ser::{Allocator, Writer},
     string::{ArchivedString, StringResolver},
     time::ArchivedDuration,
     vec::{ArchivedVec, VecResolver},
     with::{
-        ArchiveWith, AsOwned, AsString, AsUnixTime, AsVec, DeserializeWith,
-        Lock, MapKV, SerializeWith,
+        ArchiveWith, AsOwned, AsPerfectHash, AsSortedVec, AsString, AsTrie,
+        AsUnixTime, AsVec, DeserializeWith, Lock, MapKV, SerializeWith,
+        WithHasher,
     },
     Archive, Deserialize, Place, Serialize, SerializeUnsized,
 };
@@ -463,6 +472,373 @@ where
     }
 }
 
+// AsPerfectHash
+
+impl<K: Archive, V: Archive, H> ArchiveWith<HashMap<K, V, H>>
+    for AsPerfectHash
+{
+    type Archived = ArchivedPerfectHashMap<K::Archived, V::Archived>;
+    type Resolver = PerfectHashMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPerfectHashMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, H, S> SerializeWith<HashMap<K, V, H>, S> for AsPerfectHash
+where
+    K: Serialize<S> + Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPerfectHashMap::<_, _, FxHasher64>::serialize_from_iter::<
+            _,
+            _,
+            _,
+            K,
+            V,
+            _,
+        >(field.iter(), serializer)
+    }
+}
+
+impl<K, V, H, D>
+    DeserializeWith<
+        ArchivedPerfectHashMap<K::Archived, V::Archived>,
+        HashMap<K, V, H>,
+        D,
+    > for AsPerfectHash
+where
+    K: Archive + Hash + Eq,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    H: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPerfectHashMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, H>, D::Error> {
+        let mut result =
+            HashMap::with_capacity_and_hasher(field.len(), H::default());
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Archive, H> ArchiveWith<HashSet<T, H>> for AsPerfectHash {
+    type Archived = ArchivedPerfectHashSet<T::Archived>;
+    type Resolver = PerfectHashSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<T, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedPerfectHashSet::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<T, H, S> SerializeWith<HashSet<T, H>, S> for AsPerfectHash
+where
+    T: Serialize<S> + Hash + Eq,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashSet<T, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPerfectHashSet::<_, FxHasher64>::serialize_from_iter::<_, T, _>(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<T, H, D>
+    DeserializeWith<ArchivedPerfectHashSet<T::Archived>, HashSet<T, H>, D>
+    for AsPerfectHash
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Deserialize<T, D>,
+    H: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedPerfectHashSet<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<T, H>, D::Error> {
+        let mut result =
+            HashSet::with_capacity_and_hasher(field.len(), H::default());
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// AsSortedVec
+
+impl<K: Archive, V: Archive, H> ArchiveWith<HashMap<K, V, H>> for AsSortedVec {
+    type Archived = ArchivedSortedMap<K::Archived, V::Archived>;
+    type Resolver = SortedMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedSortedMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<K, V, H, S> SerializeWith<HashMap<K, V, H>, S> for AsSortedVec
+where
+    K: Serialize<S> + Ord,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedSortedMap::serialize_from_iter::<_, _, _, K, V, _>(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, H, D>
+    DeserializeWith<
+        ArchivedSortedMap<K::Archived, V::Archived>,
+        HashMap<K, V, H>,
+        D,
+    > for AsSortedVec
+where
+    K: Archive + Hash + Eq,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    H: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedSortedMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, H>, D::Error> {
+        let mut result =
+            HashMap::with_capacity_and_hasher(field.len(), H::default());
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+// AsTrie
+
+impl<V: Archive, H> ArchiveWith<HashMap<String, V, H>> for AsTrie {
+    type Archived = ArchivedTrieMap<V::Archived>;
+    type Resolver = TrieMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<String, V, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedTrieMap::resolve_from_len(field.len(), resolver, out);
+    }
+}
+
+impl<V, H, S> SerializeWith<HashMap<String, V, H>, S> for AsTrie
+where
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<String, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedTrieMap::serialize_from_iter::<_, _, _, V, _>(
+            field.iter(),
+            serializer,
+        )
+    }
+}
+
+impl<V, H, D>
+    DeserializeWith<ArchivedTrieMap<V::Archived>, HashMap<String, V, H>, D>
+    for AsTrie
+where
+    V: Archive,
+    H: BuildHasher + Default,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedTrieMap<V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<String, V, H>, D::Error> {
+        let mut result =
+            HashMap::with_capacity_and_hasher(field.len(), H::default());
+        for (key, value) in field.iter() {
+            result.insert(key.to_string(), value.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// WithHasher
+
+impl<K, V, S, H> ArchiveWith<HashMap<K, V, S>> for WithHasher<H>
+where
+    K: Archive,
+    V: Archive,
+    H: TableHasher,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived, H>;
+    type Resolver = HashMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<K, V, S, H, Se> SerializeWith<HashMap<K, V, S>, Se> for WithHasher<H>
+where
+    K: Serialize<Se> + Hash + Eq,
+    V: Serialize<Se>,
+    H: TableHasher,
+    Se: Fallible + Allocator + Writer + ?Sized,
+    Se::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, S>,
+        serializer: &mut Se,
+    ) -> Result<Self::Resolver, Se::Error> {
+        ArchivedHashMap::<_, _, H>::serialize_from_iter::<_, _, _, K, V, _>(
+            field.iter(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, S, H, D>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, V::Archived, H>,
+        HashMap<K, V, S>,
+        D,
+    > for WithHasher<H>
+where
+    K: Archive + Hash + Eq,
+    V: Archive,
+    K::Archived: Deserialize<K, D>,
+    V::Archived: Deserialize<V, D>,
+    S: BuildHasher + Default,
+    H: TableHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, S>, D::Error> {
+        let mut result =
+            HashMap::with_capacity_and_hasher(field.len(), S::default());
+        for (key, value) in field.iter() {
+            result.insert(
+                key.deserialize(deserializer)?,
+                value.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<T, S, H> ArchiveWith<HashSet<T, S>> for WithHasher<H>
+where
+    T: Archive,
+    H: TableHasher,
+{
+    type Archived = ArchivedHashSet<T::Archived, H>;
+    type Resolver = HashSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<T, S>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashSet::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<T, S, H, Se> SerializeWith<HashSet<T, S>, Se> for WithHasher<H>
+where
+    T: Serialize<Se> + Hash + Eq,
+    H: TableHasher,
+    Se: Fallible + Allocator + Writer + ?Sized,
+    Se::Error: Source,
+{
+    fn serialize_with(
+        field: &HashSet<T, S>,
+        serializer: &mut Se,
+    ) -> Result<Self::Resolver, Se::Error> {
+        ArchivedHashSet::<_, H>::serialize_from_iter::<_, T, _>(
+            field.iter(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<T, S, H, D>
+    DeserializeWith<ArchivedHashSet<T::Archived, H>, HashSet<T, S>, D>
+    for WithHasher<H>
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Deserialize<T, D>,
+    S: BuildHasher + Default,
+    H: TableHasher,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashSet<T::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<T, S>, D::Error> {
+        let mut result =
+            HashSet::with_capacity_and_hasher(field.len(), S::default());
+        for key in field.iter() {
+            result.insert(key.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
 // UnixTimestamp
 
 impl ArchiveWith<SystemTime> for AsUnixTime {
@@ -553,7 +929,7 @@ where
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::BTreeMap,
+        collections::{BTreeMap, HashSet},
         ffi::OsString,
         path::PathBuf,
         sync::{Mutex, RwLock},
@@ -562,7 +938,10 @@ mod tests {
     use crate::{
         alloc::collections::HashMap,
         api::test::{roundtrip_with, to_archived},
-        with::{AsString, InlineAsBox, Lock, MapKV},
+        with::{
+            AsPerfectHash, AsSortedVec, AsString, AsTrie, InlineAsBox, Lock,
+            MapKV, WithHasher,
+        },
         Archive, Deserialize, Serialize,
     };
 
@@ -697,4 +1076,437 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn with_hash_map_as_perfect_hash() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsPerfectHash)]
+            inner: HashMap<String, u32>,
+        }
+
+        for size in [0, 1, 2, 5, 6, 31, 100, 1000] {
+            let value = Test {
+                inner: (0..size).map(|i| (i.to_string(), i)).collect(),
+            };
+
+            roundtrip_with(&value, |a, b| {
+                assert_eq!(a.inner.len(), b.inner.len());
+                assert_eq!(b.inner.iter().count(), size as usize);
+                for (key, value) in a.inner.iter() {
+                    assert_eq!(b.inner.get(key.as_str()), Some(&value.into()));
+                }
+                assert!(!b.inner.contains_key("missing"));
+                assert!(!b.inner.contains_key(size.to_string().as_str()));
+            });
+        }
+    }
+
+    #[test]
+    fn with_hash_set_as_perfect_hash() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsPerfectHash)]
+            inner: HashSet<u32>,
+        }
+
+        let value = Test {
+            inner: (0..100).map(|i| i * 3).collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(a.inner.len(), b.inner.len());
+            for i in 0..300 {
+                assert_eq!(b.inner.contains(&i.into()), i % 3 == 0);
+            }
+        });
+    }
+
+    #[test]
+    fn large_hash_set_as_perfect_hash() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsPerfectHash)]
+            inner: HashSet<u32>,
+        }
+
+        let value = Test {
+            inner: (0..50_000).collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(a.inner.len(), b.inner.len());
+            for i in (0..100_000).step_by(7) {
+                assert_eq!(b.inner.contains(&i.into()), i < 50_000);
+            }
+        });
+    }
+
+    #[test]
+    fn with_hash_map_as_sorted_vec() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsSortedVec)]
+            inner: HashMap<u32, String>,
+        }
+
+        let value = Test {
+            inner: (0..100).map(|i| (i * 7 % 100, i.to_string())).collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(a.inner.len(), b.inner.len());
+            let keys = b.inner.keys().collect::<Vec<_>>();
+            assert!(keys.windows(2).all(|w| w[0] < w[1]));
+            for (key, value) in a.inner.iter() {
+                assert_eq!(b.inner[&(*key).into()], *value);
+            }
+        });
+    }
+
+    #[cfg(all(
+        not(feature = "pointer_width_16"),
+        not(feature = "pointer_width_64"),
+        not(feature = "big_endian"),
+        feature = "bytecheck",
+    ))]
+    #[test]
+    fn invalid_sorted_map() {
+        use rancor::Failure;
+
+        use crate::{access, to_bytes};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsSortedVec)]
+            inner: HashMap<u32, u32>,
+        }
+
+        let value = Test {
+            inner: (0..10).map(|i| (i, i)).collect(),
+        };
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<ArchivedTest, Failure>(&bytes).unwrap();
+
+        // The entries are written immediately before the root.
+        let entries = bytes.len() - 8 - 10 * 8;
+
+        // Duplicate keys are rejected.
+        let mut invalid = bytes.clone();
+        invalid[entries + 8..entries + 12].copy_from_slice(&0u32.to_le_bytes());
+        access::<ArchivedTest, Failure>(&invalid).unwrap_err();
+
+        // Out-of-order keys are rejected.
+        let mut invalid = bytes.clone();
+        invalid[entries..entries + 4].copy_from_slice(&5u32.to_le_bytes());
+        access::<ArchivedTest, Failure>(&invalid).unwrap_err();
+    }
+
+    #[test]
+    fn with_hash_map_as_trie() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsTrie)]
+            inner: HashMap<String, u32>,
+        }
+
+        for size in [0, 1, 2, 10, 100] {
+            let value = Test {
+                inner: (0..size).map(|i| (format!("key{i}"), i)).collect(),
+            };
+
+            roundtrip_with(&value, |a, b| {
+                assert_eq!(a.inner.len(), b.inner.len());
+                for (key, value) in a.inner.iter() {
+                    assert_eq!(b.inner.get(key), Some(&value.into()));
+                }
+                assert!(!b.inner.contains_key("key"));
+                assert_eq!(b.inner.prefix("key").count(), size as usize);
+            });
+        }
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn invalid_trie() {
+        use rancor::Failure;
+
+        use crate::{access, to_bytes};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsTrie)]
+            inner: HashMap<String, u32>,
+        }
+
+        let value = Test {
+            inner: [("xy".to_string(), 1), ("xz".to_string(), 2)].into(),
+        };
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<ArchivedTest, Failure>(&bytes).unwrap();
+
+        let labels = bytes.windows(3).position(|w| w == b"xyz").unwrap();
+
+        // Labels must be valid UTF-8.
+        let mut invalid = bytes.clone();
+        invalid[labels + 1] = 0xff;
+        access::<ArchivedTest, Failure>(&invalid).unwrap_err();
+
+        // Siblings must be sorted by their labels.
+        let mut invalid = bytes.clone();
+        invalid[labels + 1..labels + 3].copy_from_slice(b"zy");
+        access::<ArchivedTest, Failure>(&invalid).unwrap_err();
+    }
+
+    #[test]
+    fn perfect_hash_colliding_keys() {
+        use core::hash::{Hash, Hasher};
+
+        use rancor::Failure;
+
+        use crate::to_bytes;
+
+        #[derive(Archive, Serialize, PartialEq, Eq)]
+        #[rkyv(crate)]
+        struct Collide(u32);
+
+        impl Hash for Collide {
+            fn hash<H: Hasher>(&self, _: &mut H) {}
+        }
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Test {
+            #[rkyv(with = AsPerfectHash)]
+            inner: HashSet<Collide>,
+        }
+
+        let value = Test {
+            inner: [Collide(1), Collide(2)].into_iter().collect(),
+        };
+        to_bytes::<Failure>(&value).unwrap_err();
+    }
+
+    #[cfg(all(
+        not(feature = "pointer_width_16"),
+        not(feature = "pointer_width_64"),
+        not(feature = "big_endian"),
+        feature = "bytecheck",
+    ))]
+    #[test]
+    fn invalid_perfect_hash_map() {
+        use rancor::Failure;
+
+        use crate::{access, to_bytes};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsPerfectHash)]
+            inner: HashMap<u32, u32>,
+        }
+
+        let value = Test {
+            inner: (0..10).map(|i| (i, i)).collect(),
+        };
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<ArchivedTest, Failure>(&bytes).unwrap();
+
+        // The root is two relative vecs followed by the seed.
+        let root = bytes.len() - 24;
+        let offset =
+            i32::from_le_bytes(bytes[root..root + 4].try_into().unwrap());
+        let displacements = (root as isize + offset as isize) as usize;
+
+        // Displacements must be less than the number of entries.
+        let mut invalid = bytes.clone();
+        invalid[displacements..displacements + 4]
+            .copy_from_slice(&10u32.to_le_bytes());
+        access::<ArchivedTest, Failure>(&invalid).unwrap_err();
+
+        // Changing the seed moves keys away from their slots.
+        let mut invalid = bytes.clone();
+        invalid[root + 16] ^= 1;
+        access::<ArchivedTest, Failure>(&invalid).unwrap_err();
+    }
+
+    #[test]
+    fn with_hasher_sip_hasher_13() {
+        use crate::hash::SipHasher13;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = WithHasher<SipHasher13>)]
+            map: HashMap<String, u32>,
+            #[rkyv(with = WithHasher<SipHasher13>)]
+            set: HashSet<u32>,
+        }
+
+        let value = Test {
+            map: (0..100).map(|i| (i.to_string(), i)).collect(),
+            set: (0..100).map(|i| i * 2).collect(),
+        };
+
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(a.map.len(), b.map.len());
+            for (key, value) in a.map.iter() {
+                assert_eq!(b.map.get(key.as_str()), Some(&value.into()));
+            }
+            assert!(!b.map.contains_key("missing"));
+            for i in 0..200 {
+                assert_eq!(b.set.contains(&i.into()), i % 2 == 0);
+            }
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn with_hasher_probe_limit() {
+        use core::hash::Hasher;
+
+        use rancor::Failure;
+
+        use crate::{access, hash::TableHasher, to_bytes, Place};
+
+        struct Constant;
+
+        impl Hasher for Constant {
+            fn write(&mut self, _: &[u8]) {}
+
+            fn finish(&self) -> u64 {
+                0
+            }
+        }
+
+        impl TableHasher for Constant {
+            type Seed = ();
+
+            const PROBE_LIMIT: Option<usize> = Some(2);
+
+            fn new_seed() -> u64 {
+                0
+            }
+
+            fn resolve_seed(_: u64, _: Place<Self::Seed>) {}
+
+            fn seed(_: &Self::Seed) -> u64 {
+                0
+            }
+
+            fn with_seed(_: u64) -> Self {
+                Self
+            }
+        }
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = WithHasher<Constant>)]
+            set: HashSet<u32>,
+        }
+
+        // Every key collides, so a few keys fit within the probe limit...
+        let value = Test {
+            set: (0..10).collect(),
+        };
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<ArchivedTest, Failure>(&bytes).unwrap();
+
+        // ... but many keys form a cluster which takes too many probes.
+        let value = Test {
+            set: (0..100).collect(),
+        };
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<ArchivedTest, Failure>(&bytes).unwrap_err();
+    }
+
+    #[cfg(all(
+        not(feature = "pointer_width_16"),
+        not(feature = "pointer_width_64"),
+        not(feature = "big_endian"),
+        feature = "bytecheck",
+    ))]
+    #[test]
+    fn invalid_hash_map_full_buckets() {
+        use core::mem::size_of;
+
+        use rancor::Failure;
+
+        use crate::{
+            access, collections::swiss_table::ArchivedHashSet, to_bytes,
+        };
+
+        let value = (0..10).collect::<HashSet<u32>>();
+        let mut bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<ArchivedHashSet<u32>, Failure>(&bytes).unwrap();
+
+        // The root is a relative pointer to the control bytes, the length, the
+        // capacity, and the group width.
+        let root = bytes.len() - size_of::<ArchivedHashSet<u32>>();
+        let offset =
+            i32::from_le_bytes(bytes[root..root + 4].try_into().unwrap());
+        let controls = (root as isize + offset as isize) as usize;
+        let cap =
+            u32::from_le_bytes(bytes[root + 8..root + 12].try_into().unwrap());
+
+        // Marking an empty bucket as full leaves too few empty buckets.
+        let empty = (controls..controls + cap as usize)
+            .find(|&i| bytes[i] == 0xff)
+            .unwrap();
+        bytes[empty] = 0;
+        access::<ArchivedHashSet<u32>, Failure>(&bytes).unwrap_err();
+        bytes[empty] = 0xff;
+        access::<ArchivedHashSet<u32>, Failure>(&bytes).unwrap();
+
+        // Group widths must be a power of two from 16 to 64.
+        bytes[root + 12] = 48;
+        access::<ArchivedHashSet<u32>, Failure>(&bytes).unwrap_err();
+    }
+
+    #[cfg(all(
+        not(feature = "pointer_width_16"),
+        not(feature = "pointer_width_64"),
+        not(feature = "big_endian"),
+        feature = "bytecheck",
+    ))]
+    #[test]
+    fn invalid_hash_map_only_deleted_buckets() {
+        use core::mem::size_of;
+
+        use rancor::Failure;
+
+        use crate::{
+            access, collections::swiss_table::ArchivedHashMap, to_bytes,
+        };
+
+        type Archived = ArchivedHashMap<u32, u32>;
+
+        let value = (0..3).map(|i| (i, i)).collect::<HashMap<u32, u32>>();
+        let mut bytes = to_bytes::<Failure>(&value).unwrap();
+        access::<Archived, Failure>(&bytes).unwrap();
+
+        // The root starts with a relative pointer to the control bytes, which
+        // are the last bytes written before the root.
+        let root = bytes.len() - size_of::<Archived>();
+        let offset =
+            i32::from_le_bytes(bytes[root..root + 4].try_into().unwrap());
+        let controls = (root as isize + offset as isize) as usize;
+
+        // Replacing every EMPTY bucket with a DELETED one would leave lookups
+        // for missing keys probing forever.
+        for byte in bytes[controls..root].iter_mut() {
+            if *byte == 0xff {
+                *byte = 0x80;
+            }
+        }
+        access::<Archived, Failure>(&bytes).unwrap_err();
+    }
 }
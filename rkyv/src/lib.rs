@@ -135,6 +135,8 @@
 //! - `std`: Enables standard library support. Enabled by default.
 //! - `bytecheck`: Enables data validation through `bytecheck`. Enabled by
 //!   default.
+//! - `mmap`: Enables writing archives directly into memory-mapped files.
+//! - `rayon`: Enables parallel serialization of large slices with `rayon`.
 //!
 //! ### Crates
 //!
@@ -215,9 +217,11 @@ pub use ::rend;
 mod alias;
 #[macro_use]
 mod _macros;
+pub mod aligned;
 pub mod api;
 pub mod boxed;
 pub mod collections;
+pub mod context;
 pub mod de;
 pub mod ffi;
 mod fmt;
@@ -240,6 +240,33 @@ impl<T> RangeBounds<T> for ArchivedRangeToInclusive<T> {
     }
 }
 
+// Archived ranges are usually accessed by reference, so they also implement
+// `RangeBounds` by reference. This lets them be passed directly to methods like
+// `ArchivedBTreeMap::range`.
+macro_rules! impl_range_bounds_for_ref {
+    ($($range:ident),* $(,)?) => {
+        $(
+            impl<T> RangeBounds<T> for &$range<T> {
+                fn start_bound(&self) -> Bound<&T> {
+                    (**self).start_bound()
+                }
+
+                fn end_bound(&self) -> Bound<&T> {
+                    (**self).end_bound()
+                }
+            }
+        )*
+    };
+}
+
+impl_range_bounds_for_ref!(
+    ArchivedRange,
+    ArchivedRangeInclusive,
+    ArchivedRangeFrom,
+    ArchivedRangeTo,
+    ArchivedRangeToInclusive,
+);
+
 /// An archived [`Bound`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Portable)]
 #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
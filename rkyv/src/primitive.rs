@@ -1,6 +1,8 @@
 //! Definitions of archived primitives and type aliases based on enabled
 //! features.
 
+#[cfg(feature = "alloc")]
+use crate::alloc::borrow::Cow;
 // Unaligned big-endian
 #[cfg(all(feature = "unaligned", feature = "big_endian"))]
 use crate::rend::unaligned::{
@@ -31,6 +33,7 @@ use crate::rend::{
     u32_le, u64_le, NonZeroI128_le, NonZeroI16_le, NonZeroI32_le,
     NonZeroI64_le, NonZeroU128_le, NonZeroU16_le, NonZeroU32_le, NonZeroU64_le,
 };
+use crate::Portable;
 
 #[rustfmt::skip]
 macro_rules! define_archived_type_alias {
@@ -208,3 +211,147 @@ pub type ArchivedNonZeroUsize = match_pointer_width!(
     ArchivedNonZeroU32,
     ArchivedNonZeroU64
 );
+
+/// Whether archived multibyte primitives have the same representation as their
+/// native equivalents on the target.
+///
+/// This is `true` when the `unaligned` feature is disabled and the endianness
+/// selected by the `big_endian` feature matches the target endianness.
+pub const NATIVE_FORMAT: bool =
+    match_native_format!(native: { true }, fallback: { false });
+
+/// An archived multibyte primitive with a native equivalent.
+///
+/// # Safety
+///
+/// When [`NATIVE_FORMAT`] is `true`, `Self` must have the same size and bit
+/// validity as `Self::Native`, and an alignment at least as large as that of
+/// `Self::Native`.
+pub unsafe trait ArchivedPrimitive: Copy + Portable {
+    /// The native equivalent of the archived primitive.
+    type Native: Copy;
+
+    /// Converts the archived primitive to its native equivalent.
+    fn to_native(self) -> Self::Native;
+}
+
+macro_rules! impl_archived_primitive {
+    ($($archived:ident: $native:ty),* $(,)?) => {
+        $(
+            // SAFETY: When `NATIVE_FORMAT` is `true`, the archived primitive
+            // is an aligned primitive with the same endianness as the target.
+            // These have the same size and bit validity as the native type,
+            // and are aligned to their size.
+            unsafe impl ArchivedPrimitive for $archived {
+                type Native = $native;
+
+                #[inline]
+                fn to_native(self) -> Self::Native {
+                    $archived::to_native(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_archived_primitive! {
+    ArchivedI16: i16,
+    ArchivedI32: i32,
+    ArchivedI64: i64,
+    ArchivedI128: i128,
+    ArchivedU16: u16,
+    ArchivedU32: u32,
+    ArchivedU64: u64,
+    ArchivedU128: u128,
+    ArchivedF32: f32,
+    ArchivedF64: f64,
+}
+
+/// Conversions from slices of archived primitives to native slices.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{primitive::ArchivedPrimitiveSlice as _, rancor::Error};
+///
+/// let bytes = rkyv::to_bytes::<Error>(&vec![1.0f32, 2.0, 3.0]).unwrap();
+/// let archived =
+///     rkyv::access::<rkyv::Archived<Vec<f32>>, Error>(&bytes).unwrap();
+///
+/// // Borrows the archived data when the archive format matches the target,
+/// // and copies it otherwise.
+/// let native = archived.to_native_cow();
+/// assert_eq!(&*native, &[1.0, 2.0, 3.0]);
+/// ```
+pub trait ArchivedPrimitiveSlice {
+    /// The native equivalent of the archived primitives.
+    type Native: Copy;
+
+    match_native_format! {
+        native: {
+            /// Returns the archived primitives as a slice of native
+            /// primitives.
+            ///
+            /// This is only available when the archive format matches the
+            /// target (see [`NATIVE_FORMAT`]).
+            fn as_native_slice(&self) -> &[Self::Native];
+        },
+        fallback: {},
+    }
+
+    /// Copies the archived primitives into a slice of native primitives.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` does not have the same length as `self`.
+    fn copy_to_native_slice(&self, out: &mut [Self::Native]);
+
+    /// Returns the archived primitives as native primitives, borrowing them if
+    /// the archive format matches the target and copying them otherwise.
+    #[cfg(feature = "alloc")]
+    fn to_native_cow(&self) -> Cow<'_, [Self::Native]>;
+}
+
+impl<T: ArchivedPrimitive> ArchivedPrimitiveSlice for [T] {
+    type Native = T::Native;
+
+    match_native_format! {
+        native: {
+            #[inline]
+            fn as_native_slice(&self) -> &[Self::Native] {
+                // SAFETY: The archive format matches the target, so `T` has
+                // the same size and bit validity as `T::Native` and is at
+                // least as aligned.
+                unsafe {
+                    core::slice::from_raw_parts(
+                        self.as_ptr().cast(),
+                        self.len(),
+                    )
+                }
+            }
+        },
+        fallback: {},
+    }
+
+    #[inline]
+    fn copy_to_native_slice(&self, out: &mut [Self::Native]) {
+        assert_eq!(
+            self.len(),
+            out.len(),
+            "destination and source slices have different lengths",
+        );
+        for (out, value) in out.iter_mut().zip(self.iter()) {
+            *out = value.to_native();
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_native_cow(&self) -> Cow<'_, [Self::Native]> {
+        match_native_format! {
+            native: { Cow::Borrowed(self.as_native_slice()) },
+            fallback: {
+                Cow::Owned(self.iter().map(|v| v.to_native()).collect())
+            },
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Mutable references to values which may not be moved or de-initialized.
 
 use core::{
+    cmp::Ordering,
     ops::{Deref, DerefMut},
     slice::SliceIndex,
 };
@@ -117,3 +118,60 @@ impl<'a, T> Seal<'a, [T]> {
         Seal::new(&mut ptr[index])
     }
 }
+
+/// Methods which rearrange the elements of a sealed slice without moving the
+/// slice itself. These require that the elements may be moved freely.
+impl<T: NoUndef + Unpin> Seal<'_, [T]> {
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        self.inner
+    }
+
+    /// Swaps two elements in the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b);
+    }
+
+    /// Reverses the order of elements in the slice, in place.
+    pub fn reverse(&mut self) {
+        self.as_mut_slice().reverse();
+    }
+
+    /// Fills the slice with elements by cloning `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.as_mut_slice().fill(value);
+    }
+
+    /// Sorts the slice without preserving the initial order of equal elements.
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().sort_unstable();
+    }
+
+    /// Sorts the slice with a comparison function without preserving the
+    /// initial order of equal elements.
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_mut_slice().sort_unstable_by(compare);
+    }
+
+    /// Sorts the slice with a key extraction function without preserving the
+    /// initial order of equal elements.
+    pub fn sort_unstable_by_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.as_mut_slice().sort_unstable_by_key(f);
+    }
+}
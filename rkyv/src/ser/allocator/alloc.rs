@@ -5,9 +5,12 @@ use core::{
     ptr::{slice_from_raw_parts_mut, NonNull},
 };
 
+use rancor::{ResultExt as _, Source};
+
 use crate::{
     alloc::alloc::{alloc, dealloc, handle_alloc_error},
-    ser::Allocator,
+    ser::{Allocator, TryAlloc},
+    util::AllocError,
 };
 
 struct Block {
@@ -17,12 +20,19 @@ struct Block {
 
 impl Block {
     fn alloc(size: usize) -> NonNull<Self> {
-        debug_assert!(size >= size_of::<Self>());
         let layout = Layout::from_size_align(size, align_of::<Self>()).unwrap();
-        let ptr = unsafe { alloc(layout).cast::<Self>() };
-        let Some(ptr) = NonNull::new(ptr) else {
-            handle_alloc_error(layout)
-        };
+        Self::alloc_layout(layout).unwrap_or_else(|| handle_alloc_error(layout))
+    }
+
+    fn try_alloc(size: usize) -> Result<NonNull<Self>, AllocError> {
+        let layout = Layout::from_size_align(size, align_of::<Self>())
+            .map_err(|_| AllocError::CapacityOverflow)?;
+        Self::alloc_layout(layout).ok_or(AllocError::Exhausted { layout })
+    }
+
+    fn alloc_layout(layout: Layout) -> Option<NonNull<Self>> {
+        debug_assert!(layout.size() >= size_of::<Self>());
+        let ptr = NonNull::new(unsafe { alloc(layout).cast::<Self>() })?;
 
         unsafe {
             ptr.as_ptr().write(Self {
@@ -31,7 +41,7 @@ impl Block {
             });
         }
 
-        ptr
+        Some(ptr)
     }
 
     unsafe fn dealloc(ptr: NonNull<Self>, size: usize) {
@@ -210,23 +220,23 @@ pub struct ArenaHandle<'a> {
 // SAFETY: ArenaHandle is safe to send to other threads
 unsafe impl Send for ArenaHandle<'_> {}
 
-unsafe impl<E> Allocator<E> for ArenaHandle<'_> {
-    unsafe fn push_alloc(
+impl ArenaHandle<'_> {
+    fn try_push_alloc(
         &mut self,
         layout: Layout,
-    ) -> Result<NonNull<[u8]>, E> {
+    ) -> Result<NonNull<[u8]>, AllocError> {
         let pos = self.tail_ptr.as_ptr() as usize + self.used;
         let pad = 0usize.wrapping_sub(pos) % layout.align();
         if pad + layout.size() <= self.tail_size - self.used {
             self.used += pad;
         } else {
             // Allocation request is too large, allocate a new block
-            let size = usize::max(
-                2 * self.tail_size,
+            let required =
                 (size_of::<Block>() + layout.size() + layout.align())
-                    .next_power_of_two(),
-            );
-            let next = Block::alloc(size);
+                    .checked_next_power_of_two()
+                    .ok_or(AllocError::CapacityOverflow)?;
+            let size = usize::max(2 * self.tail_size, required);
+            let next = Block::try_alloc(size)?;
             unsafe {
                 Block::push_next(self.tail_ptr, next);
             }
@@ -247,6 +257,19 @@ unsafe impl<E> Allocator<E> for ArenaHandle<'_> {
         self.used += layout.size();
         Ok(result)
     }
+}
+
+unsafe impl<E> Allocator<E> for ArenaHandle<'_> {
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, E> {
+        match self.try_push_alloc(layout) {
+            Ok(result) => Ok(result),
+            Err(AllocError::Exhausted { layout }) => handle_alloc_error(layout),
+            Err(AllocError::CapacityOverflow) => panic!("capacity overflow"),
+        }
+    }
 
     unsafe fn pop_alloc(
         &mut self,
@@ -266,16 +289,35 @@ unsafe impl<E> Allocator<E> for ArenaHandle<'_> {
     }
 }
 
+unsafe impl<E: Source> Allocator<E> for TryAlloc<ArenaHandle<'_>> {
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, E> {
+        self.inner_mut().try_push_alloc(layout).into_error()
+    }
+
+    unsafe fn pop_alloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `ArenaHandle::pop_alloc()` are
+        // the same as the safety requirements for `pop_alloc()`.
+        unsafe { self.inner_mut().pop_alloc(ptr, layout) }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::alloc::Layout;
 
-    use rancor::{Panic, ResultExt};
+    use rancor::{Error, Panic, ResultExt};
 
     use crate::{
         alloc::{string::ToString, vec},
         api::high::to_bytes_in_with_alloc,
-        ser::{allocator::Arena, Allocator},
+        ser::{allocator::Arena, Allocator, TryAlloc},
         util::AlignedVec,
     };
 
@@ -320,4 +362,24 @@ mod tests {
                 .always_ok();
         }
     }
+
+    #[test]
+    fn oversized_alloc_fails() {
+        let mut arena = Arena::new();
+        let mut handle = TryAlloc::new(arena.acquire());
+
+        let oversized =
+            Layout::from_size_align(isize::MAX as usize - 15, 16).unwrap();
+        let small = Layout::from_size_align(16, 16).unwrap();
+
+        unsafe {
+            assert!(
+                Allocator::<Error>::push_alloc(&mut handle, oversized).is_err()
+            );
+            let ptr =
+                Allocator::<Error>::push_alloc(&mut handle, small).unwrap();
+            Allocator::<Error>::pop_alloc(&mut handle, ptr.cast(), small)
+                .unwrap();
+        }
+    }
 }
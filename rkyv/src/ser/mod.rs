@@ -4,13 +4,13 @@ pub mod allocator;
 pub mod sharing;
 pub mod writer;
 
-use ::core::{alloc::Layout, ptr::NonNull};
+use ::core::{alloc::Layout, hash::Hash, ptr::NonNull};
 
 #[doc(inline)]
 pub use self::{
     allocator::Allocator,
-    sharing::{Sharing, SharingExt},
-    writer::{Positional, Writer, WriterExt},
+    sharing::{Interning, InterningExt, Sharing, SharingExt},
+    writer::{Positional, WriteAt, Writer, WriterExt},
 };
 
 /// A serializer built from composeable pieces.
@@ -57,6 +57,24 @@ impl<W: Writer<E>, A, S, E> Writer<E> for Serializer<W, A, S> {
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
         self.writer.write(bytes)
     }
+
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        self.writer.write_padding(len)
+    }
+
+    fn enter_scope(&mut self, scope: writer::Scope) {
+        self.writer.enter_scope(scope)
+    }
+
+    fn exit_scope(&mut self) {
+        self.writer.exit_scope()
+    }
+}
+
+impl<W: WriteAt<E>, A, S, E> WriteAt<E> for Serializer<W, A, S> {
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        self.writer.write_at(pos, bytes)
+    }
 }
 
 unsafe impl<W, A: Allocator<E>, S, E> Allocator<E> for Serializer<W, A, S> {
@@ -89,3 +107,94 @@ impl<W, A, S: Sharing<E>, E> Sharing<E> for Serializer<W, A, S> {
         self.sharing.finish_sharing(address, pos)
     }
 }
+
+impl<W, A, S: Interning> Interning for Serializer<W, A, S> {
+    fn interned_pos<T: Hash + Eq + 'static>(&self, value: &T) -> Option<usize> {
+        self.sharing.interned_pos(value)
+    }
+
+    fn finish_interning<T: Hash + Eq + Clone + 'static>(
+        &mut self,
+        value: &T,
+        pos: usize,
+    ) {
+        self.sharing.finish_interning(value, pos)
+    }
+}
+
+/// An adapter which returns allocation failures from a writer or allocator as
+/// errors.
+///
+/// [`AlignedVec`](crate::util::AlignedVec), `Vec<u8>`, and
+/// [`ArenaHandle`](allocator::ArenaHandle) abort the process when they fail to
+/// allocate memory, just like the standard library collections. Wrapping them
+/// in a `TryAlloc` makes them grow with `try_reserve` instead, so that running
+/// out of memory or requesting an oversized allocation fails serialization with
+/// an error.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_in_with_alloc,
+///     rancor::Error,
+///     ser::TryAlloc,
+///     util::{with_arena, AlignedVec},
+/// };
+///
+/// let value = vec![1, 2, 3, 4];
+/// let bytes = with_arena(|arena| {
+///     to_bytes_in_with_alloc::<_, _, Error>(
+///         &value,
+///         TryAlloc::new(AlignedVec::<16>::new()),
+///         TryAlloc::new(arena.acquire()),
+///     )
+/// })
+/// .unwrap()
+/// .into_inner();
+///
+/// let expected = rkyv::to_bytes::<Error>(&value).unwrap();
+/// assert_eq!(bytes.as_slice(), expected.as_slice());
+/// ```
+#[derive(Debug, Default)]
+pub struct TryAlloc<T> {
+    inner: T,
+}
+
+impl<T> TryAlloc<T> {
+    /// Wraps the given writer or allocator.
+    pub fn new(inner: T) -> Self {
+        Self { inner }
+    }
+
+    /// Returns a reference to the wrapped writer or allocator.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped writer or allocator.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes the adapter and returns the wrapped writer or allocator.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Positional> Positional for TryAlloc<T> {
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+}
+
+impl<T, E> WriteAt<E> for TryAlloc<T>
+where
+    T: WriteAt<E>,
+    Self: Writer<E>,
+{
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        self.inner_mut().write_at(pos, bytes)
+    }
+}
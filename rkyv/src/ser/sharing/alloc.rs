@@ -1,11 +1,23 @@
-use core::{error::Error, fmt, hash::BuildHasherDefault};
+use core::{
+    any::{Any, TypeId},
+    error::Error,
+    fmt,
+    hash::{BuildHasherDefault, Hash, Hasher as _},
+};
 
-use hashbrown::hash_map::{Entry, HashMap};
+use hashbrown::{
+    hash_map::{Entry, HashMap},
+    HashTable,
+};
 use rancor::{fail, Source};
 
 use crate::{
+    alloc::boxed::Box,
     hash::FxHasher64,
-    ser::{sharing::SharingState, Sharing},
+    ser::{
+        sharing::{Interning, SharingState},
+        Sharing,
+    },
 };
 
 /// A shared pointer strategy that shares serializations of the same shared
@@ -94,3 +106,152 @@ impl<E: Source> Sharing<E> for Share {
         }
     }
 }
+
+impl Interning for Share {
+    fn interned_pos<T: Hash + Eq + 'static>(&self, _: &T) -> Option<usize> {
+        None
+    }
+
+    fn finish_interning<T: Hash + Eq + Clone + 'static>(
+        &mut self,
+        _: &T,
+        _: usize,
+    ) {
+    }
+}
+
+#[derive(Debug)]
+struct InternedValue {
+    hash: u64,
+    value: Box<dyn Any>,
+    pos: usize,
+}
+
+/// A shared pointer strategy that shares serializations of the same shared
+/// pointer and deduplicates equal interned values.
+///
+/// Shared pointers are shared the same way as [`Share`]. Values serialized
+/// with the [`Intern`](crate::with::Intern) wrapper are compared by value, and
+/// each distinct value is serialized only once. `Dedup` keeps a clone of every
+/// distinct interned value until it is cleared.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access,
+///     api::serialize_using,
+///     rancor::Error,
+///     ser::{sharing::Dedup, Serializer},
+///     util::{with_arena, AlignedVec},
+///     with::Intern,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Entry {
+///     #[rkyv(with = Intern)]
+///     section: String,
+///     value: u32,
+/// }
+///
+/// let entries = (0..100)
+///     .map(|value| Entry {
+///         section: "a fairly long section name".to_string(),
+///         value,
+///     })
+///     .collect::<Vec<_>>();
+///
+/// let bytes = with_arena(|arena| {
+///     let mut serializer = Serializer::new(
+///         AlignedVec::<16>::new(),
+///         arena.acquire(),
+///         Dedup::new(),
+///     );
+///     serialize_using::<_, Error>(&entries, &mut serializer).unwrap();
+///     serializer.into_writer()
+/// });
+///
+/// let archived = access::<rkyv::Archived<Vec<Entry>>, Error>(&bytes).unwrap();
+/// assert_eq!(archived[0].section.as_ptr(), archived[99].section.as_ptr());
+/// assert_eq!(archived[42].section.as_str(), "a fairly long section name");
+/// ```
+#[derive(Debug, Default)]
+pub struct Dedup {
+    share: Share,
+    interned: HashTable<InternedValue>,
+}
+
+impl Dedup {
+    /// Creates a new deduplicating strategy.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new deduplicating strategy with initial capacity for shared
+    /// pointers and interned values.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            share: Share::with_capacity(capacity),
+            interned: HashTable::with_capacity(capacity),
+        }
+    }
+
+    /// Clears the deduplicating strategy for reuse.
+    pub fn clear(&mut self) {
+        self.share.clear();
+        self.interned.clear();
+    }
+
+    fn hash_value<T: Hash + 'static>(value: &T) -> u64 {
+        let mut hasher = FxHasher64::default();
+        TypeId::of::<T>().hash(&mut hasher);
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl<E: Source> Sharing<E> for Dedup {
+    fn start_sharing(&mut self, address: usize) -> SharingState {
+        Sharing::<E>::start_sharing(&mut self.share, address)
+    }
+
+    fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
+        self.share.finish_sharing(address, pos)
+    }
+}
+
+impl Interning for Dedup {
+    fn interned_pos<T: Hash + Eq + 'static>(&self, value: &T) -> Option<usize> {
+        let hash = Self::hash_value(value);
+        self.interned
+            .find(hash, |entry| {
+                entry.hash == hash
+                    && entry.value.downcast_ref::<T>() == Some(value)
+            })
+            .map(|entry| entry.pos)
+    }
+
+    fn finish_interning<T: Hash + Eq + Clone + 'static>(
+        &mut self,
+        value: &T,
+        pos: usize,
+    ) {
+        if self.interned_pos(value).is_some() {
+            return;
+        }
+
+        let hash = Self::hash_value(value);
+        self.interned.insert_unique(
+            hash,
+            InternedValue {
+                hash,
+                value: Box::new(value.clone()),
+                pos,
+            },
+            |entry| entry.hash,
+        );
+    }
+}
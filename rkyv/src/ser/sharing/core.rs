@@ -1,4 +1,9 @@
-use crate::ser::{sharing::SharingState, Sharing};
+use core::hash::Hash;
+
+use crate::ser::{
+    sharing::{Interning, SharingState},
+    Sharing,
+};
 
 /// A shared pointer strategy that duplicates serializations of the same shared
 /// pointer.
@@ -14,3 +19,16 @@ impl<E> Sharing<E> for Unshare {
         Ok(())
     }
 }
+
+impl Interning for Unshare {
+    fn interned_pos<T: Hash + Eq + 'static>(&self, _: &T) -> Option<usize> {
+        None
+    }
+
+    fn finish_interning<T: Hash + Eq + Clone + 'static>(
+        &mut self,
+        _: &T,
+        _: usize,
+    ) {
+    }
+}
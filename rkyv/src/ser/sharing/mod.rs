@@ -4,7 +4,7 @@
 mod alloc;
 mod core;
 
-use ::core::{error::Error, fmt};
+use ::core::{error::Error, fmt, hash::Hash};
 use rancor::{fail, Fallible, Source, Strategy};
 
 #[cfg(feature = "alloc")]
@@ -110,3 +110,82 @@ pub trait SharingExt<E>: Sharing<E> {
 }
 
 impl<S, E> SharingExt<E> for S where S: Sharing<E> + ?Sized {}
+
+/// A value deduplication strategy.
+///
+/// This trait is required to serialize fields with the
+/// [`Intern`](crate::with::Intern) wrapper. Unlike [`Sharing`], which
+/// deduplicates shared pointers by address, interning compares the values
+/// themselves so equal values from separate allocations are only serialized
+/// once.
+pub trait Interning {
+    /// Returns the position of a previously interned value equal to the given
+    /// value, if any.
+    fn interned_pos<T: Hash + Eq + 'static>(&self, value: &T) -> Option<usize>;
+
+    /// Records that the given value was serialized at the given position.
+    fn finish_interning<T: Hash + Eq + Clone + 'static>(
+        &mut self,
+        value: &T,
+        pos: usize,
+    );
+}
+
+impl<T> Interning for &mut T
+where
+    T: Interning + ?Sized,
+{
+    fn interned_pos<U: Hash + Eq + 'static>(&self, value: &U) -> Option<usize> {
+        T::interned_pos(*self, value)
+    }
+
+    fn finish_interning<U: Hash + Eq + Clone + 'static>(
+        &mut self,
+        value: &U,
+        pos: usize,
+    ) {
+        T::finish_interning(*self, value, pos)
+    }
+}
+
+impl<T, E> Interning for Strategy<T, E>
+where
+    T: Interning + ?Sized,
+{
+    fn interned_pos<U: Hash + Eq + 'static>(&self, value: &U) -> Option<usize> {
+        T::interned_pos(self, value)
+    }
+
+    fn finish_interning<U: Hash + Eq + Clone + 'static>(
+        &mut self,
+        value: &U,
+        pos: usize,
+    ) {
+        T::finish_interning(self, value, pos)
+    }
+}
+
+/// Helper methods for [`Interning`].
+pub trait InterningExt: Interning {
+    /// Serializes the given value and returns its position. If an equal value
+    /// has already been interned then it returns the position of the
+    /// previously serialized value.
+    fn serialize_interned<T>(
+        &mut self,
+        value: &T,
+    ) -> Result<usize, <Self as Fallible>::Error>
+    where
+        T: SerializeUnsized<Self> + Hash + Eq + Clone + 'static,
+        Self: Fallible,
+    {
+        if let Some(pos) = self.interned_pos(value) {
+            return Ok(pos);
+        }
+
+        let pos = value.serialize_unsized(self)?;
+        self.finish_interning(value, pos);
+        Ok(pos)
+    }
+}
+
+impl<S> InterningExt for S where S: Interning + ?Sized {}
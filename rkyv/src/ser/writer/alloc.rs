@@ -1,6 +1,11 @@
+use rancor::{ResultExt as _, Source};
+
 use crate::{
     alloc::vec::Vec,
-    ser::{Positional, Writer},
+    ser::{
+        writer::{check_write_at, WriteAt},
+        Positional, TryAlloc, Writer,
+    },
     util::AlignedVec,
 };
 
@@ -18,6 +23,14 @@ impl<E> Writer<E> for Vec<u8> {
     }
 }
 
+impl<E: Source> WriteAt<E> for Vec<u8> {
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        check_write_at(pos, bytes.len(), self.len())?;
+        self[pos..pos + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
 impl<const A: usize> Positional for AlignedVec<A> {
     #[inline]
     fn pos(&self) -> usize {
@@ -31,3 +44,43 @@ impl<E, const A: usize> Writer<E> for AlignedVec<A> {
         Ok(())
     }
 }
+
+impl<E: Source, const A: usize> WriteAt<E> for AlignedVec<A> {
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        check_write_at(pos, bytes.len(), self.len())?;
+        self[pos..pos + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl<E: Source> Writer<E> for TryAlloc<Vec<u8>> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        let vec = self.inner_mut();
+        vec.try_reserve(bytes.len()).into_error()?;
+        vec.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        let vec = self.inner_mut();
+        vec.try_reserve(len).into_error()?;
+        vec.resize(vec.len() + len, 0);
+        Ok(())
+    }
+}
+
+impl<E: Source, const A: usize> Writer<E> for TryAlloc<AlignedVec<A>> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        let vec = self.inner_mut();
+        vec.try_reserve(bytes.len())?;
+        vec.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        let vec = self.inner_mut();
+        vec.try_reserve(len)?;
+        vec.resize(vec.len() + len, 0);
+        Ok(())
+    }
+}
@@ -10,7 +10,10 @@ use core::{
 
 use rancor::{fail, Source};
 
-use crate::ser::{Positional, Writer};
+use crate::ser::{
+    writer::{check_write_at, WriteAt},
+    Positional, Writer,
+};
 
 #[derive(Debug)]
 struct BufferOverflow {
@@ -166,6 +169,14 @@ impl<E: Source> Writer<E> for Buffer<'_> {
     }
 }
 
+impl<E: Source> WriteAt<E> for Buffer<'_> {
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        check_write_at(pos, bytes.len(), self.len)?;
+        self[pos..pos + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::mem::MaybeUninit;
@@ -203,3 +214,49 @@ mod tests {
             .all(|&b| b == 0));
     }
 }
+
+/// A writer which discards its output and only counts the bytes written.
+///
+/// Serializing into a `Counter` computes the exact size of the serialized
+/// bytes without allocating an output buffer.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_in,
+///     rancor::Error,
+///     ser::{writer::Counter, Positional},
+/// };
+///
+/// let value = vec!["hello".to_string(), "world".to_string()];
+/// let counter = to_bytes_in::<_, Error>(&value, Counter::new()).unwrap();
+/// let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+/// assert_eq!(counter.pos(), bytes.len());
+/// ```
+#[derive(Debug, Default)]
+pub struct Counter {
+    pos: usize,
+}
+
+impl Counter {
+    /// Creates a new counter starting at position zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Positional for Counter {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<E> Writer<E> for Counter {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.pos += bytes.len();
+        Ok(())
+    }
+}
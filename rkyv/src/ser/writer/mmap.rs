@@ -0,0 +1,250 @@
+use std::{fs::File, io};
+
+use memmap2::{Mmap, MmapMut};
+use rancor::{ResultExt as _, Source};
+
+use crate::ser::{
+    writer::{check_write_at, WriteAt},
+    Positional, Writer,
+};
+
+/// A writer which serializes directly into a memory-mapped file.
+///
+/// The file is grown with [`File::set_len`] and remapped whenever a write
+/// would exceed the mapped capacity. Because the written bytes live in the
+/// page cache instead of anonymous memory, archives can be larger than the
+/// available RAM. Calling [`finish`](MmapWriter::finish) truncates the file to
+/// the serialized length and returns a read-only mapping of it, which can be
+/// accessed in place without reading the file back.
+///
+/// Memory maps are page-aligned, so the written bytes are suitably aligned for
+/// any archived type.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_in, rancor::Error, ser::writer::MmapWriter,
+///     Archive, Archived, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Snapshot {
+///     names: Vec<String>,
+/// }
+///
+/// let value = Snapshot {
+///     names: (0..1000).map(|i| format!("name number {i}")).collect(),
+/// };
+///
+/// # let path = std::env::temp_dir().join("rkyv_mmap_writer_example");
+/// let file = std::fs::OpenOptions::new()
+///     .read(true)
+///     .write(true)
+///     .create(true)
+///     .truncate(true)
+///     .open(&path)
+///     .unwrap();
+///
+/// let writer = unsafe { MmapWriter::new(file).unwrap() };
+/// let map = to_bytes_in::<_, Error>(&value, writer)
+///     .unwrap()
+///     .finish()
+///     .unwrap();
+///
+/// let archived = rkyv::access::<ArchivedSnapshot, Error>(&map).unwrap();
+/// assert_eq!(archived.names[999], "name number 999");
+/// # drop(map);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MmapWriter {
+    file: File,
+    map: MmapMut,
+    len: usize,
+}
+
+impl MmapWriter {
+    /// The capacity to start with when none is given.
+    pub const DEFAULT_CAPACITY: usize = 64 * 1024;
+
+    /// Creates a new `MmapWriter` which writes to the given file.
+    ///
+    /// The file must be opened for reading and writing. Any existing contents
+    /// will be overwritten.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated by anything else while it is
+    /// mapped, including by other processes. See [`MmapMut::map_mut`] for
+    /// details.
+    pub unsafe fn new(file: File) -> io::Result<Self> {
+        unsafe { Self::with_capacity(file, Self::DEFAULT_CAPACITY) }
+    }
+
+    /// Creates a new `MmapWriter` which writes to the given file, and sizes the
+    /// file to hold at least `capacity` bytes before growing.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated by anything else while it is
+    /// mapped, including by other processes. See [`MmapMut::map_mut`] for
+    /// details.
+    pub unsafe fn with_capacity(
+        file: File,
+        capacity: usize,
+    ) -> io::Result<Self> {
+        let capacity = capacity.max(1);
+        file.set_len(capacity as u64)?;
+        // SAFETY: The caller has guaranteed that the file will not be modified
+        // while it is mapped.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        Ok(Self { file, map, len: 0 })
+    }
+
+    /// Returns the number of bytes written.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether no bytes have been written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes that can be written before the file must
+    /// be grown.
+    pub fn capacity(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns the bytes written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.map[..self.len]
+    }
+
+    fn grow(&mut self, required: usize) -> io::Result<()> {
+        let capacity = required.max(self.capacity() * 2);
+        self.file.set_len(capacity as u64)?;
+        // SAFETY: The caller of `with_capacity` has guaranteed that the file
+        // will not be modified while it is mapped.
+        self.map = unsafe { MmapMut::map_mut(&self.file)? };
+        Ok(())
+    }
+
+    /// Flushes the written bytes to the file, truncates the file to the
+    /// written length, and returns a read-only map of the written bytes.
+    pub fn finish(self) -> io::Result<Mmap> {
+        self.map.flush_range(0, self.len)?;
+        drop(self.map);
+        self.file.set_len(self.len as u64)?;
+        // SAFETY: The caller of `with_capacity` has guaranteed that the file
+        // will not be modified while it is mapped.
+        unsafe { Mmap::map(&self.file) }
+    }
+}
+
+impl Positional for MmapWriter {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.len
+    }
+}
+
+impl<E: Source> Writer<E> for MmapWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        let end = self.len + bytes.len();
+        if end > self.capacity() {
+            self.grow(end).into_error()?;
+        }
+        self.map[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<E: Source> WriteAt<E> for MmapWriter {
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        check_write_at(pos, bytes.len(), self.len)?;
+        self.map[pos..pos + bytes.len()].copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::{self, OpenOptions},
+        path::PathBuf,
+    };
+
+    use rancor::Panic;
+
+    use crate::{
+        alloc::{string::String, vec::Vec},
+        api::{access_unchecked, high::to_bytes_in},
+        ser::writer::MmapWriter,
+        to_bytes, Archived,
+    };
+
+    struct TempFile(PathBuf);
+
+    impl TempFile {
+        fn new(name: &str) -> Self {
+            Self(std::env::temp_dir().join(format!(
+                "rkyv_{}_{}",
+                name,
+                std::process::id(),
+            )))
+        }
+
+        fn open(&self) -> fs::File {
+            OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&self.0)
+                .unwrap()
+        }
+    }
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn mmap_writer_grows() {
+        let value = (0..10_000)
+            .map(|i| format!("a string which is too long to inline #{i}"))
+            .collect::<Vec<_>>();
+        let expected = to_bytes::<Panic>(&value).unwrap();
+
+        let temp = TempFile::new("mmap_writer_grows");
+        let writer = unsafe { MmapWriter::with_capacity(temp.open(), 16) };
+        let writer = to_bytes_in::<_, Panic>(&value, writer.unwrap()).unwrap();
+        assert_eq!(writer.len(), expected.len());
+        assert!(writer.capacity() >= expected.len());
+        assert_eq!(writer.as_slice(), expected.as_slice());
+
+        let map = writer.finish().unwrap();
+        assert_eq!(&*map, expected.as_slice());
+        assert_eq!(fs::metadata(&temp.0).unwrap().len(), expected.len() as u64);
+
+        let archived =
+            unsafe { access_unchecked::<Archived<Vec<String>>>(&map) };
+        assert_eq!(archived.len(), value.len());
+        assert_eq!(archived[1234], value[1234]);
+    }
+
+    #[test]
+    fn mmap_writer_empty() {
+        let temp = TempFile::new("mmap_writer_empty");
+        let writer = unsafe { MmapWriter::new(temp.open()).unwrap() };
+        assert!(writer.is_empty());
+        let map = writer.finish().unwrap();
+        assert!(map.is_empty());
+    }
+}
@@ -3,13 +3,21 @@
 #[cfg(feature = "alloc")]
 mod alloc;
 mod core;
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "alloc")]
+mod profiler;
 #[cfg(feature = "std")]
 mod std;
 
-use ::core::mem;
-use rancor::{Fallible, Strategy};
+use ::core::{any::type_name, error::Error, fmt, mem};
+use rancor::{fail, Fallible, Source, Strategy};
 
 pub use self::core::*;
+#[cfg(feature = "mmap")]
+pub use self::mmap::*;
+#[cfg(feature = "alloc")]
+pub use self::profiler::*;
 #[cfg(feature = "std")]
 pub use self::std::*;
 use crate::{Archive, ArchiveUnsized, Place, RelPtr};
@@ -59,6 +67,36 @@ where
 pub trait Writer<E = <Self as Fallible>::Error>: Positional {
     /// Attempts to write the given bytes to the serializer.
     fn write(&mut self, bytes: &[u8]) -> Result<(), E>;
+
+    /// Attempts to write the given number of zero bytes as padding.
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        const MAX_ZEROS: usize = 32;
+        const ZEROS: [u8; MAX_ZEROS] = [0; MAX_ZEROS];
+
+        let mut remaining = len;
+        while remaining > MAX_ZEROS {
+            self.write(&ZEROS)?;
+            remaining -= MAX_ZEROS;
+        }
+        self.write(&ZEROS[0..remaining])
+    }
+
+    /// Notifies the writer that the following writes belong to the given
+    /// scope.
+    ///
+    /// Scopes are used by profiling writers like [`SizeProfiler`] to attribute
+    /// bytes to the types and fields that wrote them. The default
+    /// implementation does nothing.
+    #[inline]
+    fn enter_scope(&mut self, scope: Scope) {
+        let _ = scope;
+    }
+
+    /// Notifies the writer that the most recently entered scope has ended.
+    ///
+    /// The default implementation does nothing.
+    #[inline]
+    fn exit_scope(&mut self) {}
 }
 
 impl<T, E> Writer<E> for &mut T
@@ -68,6 +106,18 @@ where
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
         T::write(*self, bytes)
     }
+
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        T::write_padding(*self, len)
+    }
+
+    fn enter_scope(&mut self, scope: Scope) {
+        T::enter_scope(*self, scope)
+    }
+
+    fn exit_scope(&mut self) {
+        T::exit_scope(*self)
+    }
 }
 
 impl<T, E> Writer<E> for Strategy<T, E>
@@ -77,17 +127,109 @@ where
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
         T::write(self, bytes)
     }
+
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        T::write_padding(self, len)
+    }
+
+    fn enter_scope(&mut self, scope: Scope) {
+        T::enter_scope(self, scope)
+    }
+
+    fn exit_scope(&mut self) {
+        T::exit_scope(self)
+    }
+}
+
+/// A writer that can overwrite bytes that it has already written.
+///
+/// This is used to patch placeholders which are reserved before the values
+/// they describe are serialized, like the header of a root-first archive.
+pub trait WriteAt<E = <Self as Fallible>::Error>: Writer<E> {
+    /// Attempts to overwrite the previously-written bytes starting at `pos`
+    /// with the given bytes.
+    ///
+    /// Fails if any of the overwritten bytes have not been written yet.
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E>;
+}
+
+impl<T, E> WriteAt<E> for &mut T
+where
+    T: WriteAt<E> + ?Sized,
+{
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        T::write_at(*self, pos, bytes)
+    }
+}
+
+impl<T, E> WriteAt<E> for Strategy<T, E>
+where
+    T: WriteAt<E> + ?Sized,
+{
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        T::write_at(self, pos, bytes)
+    }
+}
+
+#[derive(Debug)]
+struct WriteAtOutOfBounds {
+    pos: usize,
+    write_len: usize,
+    len: usize,
+}
+
+impl fmt::Display for WriteAtOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "attempted to overwrite {} bytes at position {} but only {} bytes \
+             have been written",
+            self.write_len, self.pos, self.len,
+        )
+    }
+}
+
+impl Error for WriteAtOutOfBounds {}
+
+/// Checks that `write_len` bytes at `pos` have already been written to a
+/// writer which has written `len` bytes.
+fn check_write_at<E: Source>(
+    pos: usize,
+    write_len: usize,
+    len: usize,
+) -> Result<(), E> {
+    if pos.checked_add(write_len).is_some_and(|end| end <= len) {
+        Ok(())
+    } else {
+        fail!(WriteAtOutOfBounds {
+            pos,
+            write_len,
+            len,
+        });
+    }
+}
+
+/// A region of serialization that bytes can be attributed to.
+///
+/// See [`Writer::enter_scope`] for more details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// Serializing a value of the named type.
+    Type(&'static str),
+    /// Serializing a field of a struct or enum.
+    Field {
+        /// The name of the field, qualified with the name of its type.
+        name: &'static str,
+        /// The name of the type of the field.
+        ty: &'static str,
+    },
 }
 
 /// Helper methods for [`Writer`].
 pub trait WriterExt<E>: Writer<E> {
     /// Advances the given number of bytes as padding.
     fn pad(&mut self, padding: usize) -> Result<(), E> {
-        const MAX_ZEROS: usize = 32;
-        const ZEROS: [u8; MAX_ZEROS] = [0; MAX_ZEROS];
-        debug_assert!(padding < MAX_ZEROS);
-
-        self.write(&ZEROS[0..padding])
+        self.write_padding(padding)
     }
 
     /// Aligns the position of the serializer to the given alignment.
@@ -132,7 +274,10 @@ pub trait WriterExt<E>: Writer<E> {
         // of its bytes are initialized.
         let out = unsafe { Place::new_unchecked(pos, resolved.as_mut_ptr()) };
         value.resolve(resolver, out);
-        self.write(out.as_slice())?;
+        self.enter_scope(Scope::Type(type_name::<T>()));
+        let result = self.write(out.as_slice());
+        self.exit_scope();
+        result?;
         Ok(pos)
     }
 
@@ -201,4 +346,22 @@ mod tests {
 
         assert_eq!(writer.capacity(), 4);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn align_large() {
+        use rancor::Error;
+
+        use crate::{
+            ser::{Writer, WriterExt},
+            util::AlignedVec,
+        };
+
+        let mut writer = AlignedVec::<16>::new();
+        Writer::<Error>::write(&mut writer, &[1]).unwrap();
+        let pos = WriterExt::<Error>::align(&mut writer, 4096).unwrap();
+        assert_eq!(pos, 4096);
+        assert_eq!(writer.len(), 4096);
+        assert!(writer[1..].iter().all(|&b| b == 0));
+    }
 }
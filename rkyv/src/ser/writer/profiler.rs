@@ -0,0 +1,329 @@
+use core::{cmp::Reverse, fmt};
+
+use crate::{
+    alloc::{collections::BTreeMap, vec::Vec},
+    ser::{writer::Scope, Positional, Writer},
+};
+
+/// The number of bytes attributed to a type or field.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ByteCounts {
+    /// The number of bytes of data written.
+    pub data: usize,
+    /// The number of bytes of padding written.
+    pub padding: usize,
+}
+
+impl ByteCounts {
+    /// Returns the total number of bytes written.
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.data + self.padding
+    }
+
+    #[inline]
+    fn add(&mut self, len: usize, padding: bool) {
+        if padding {
+            self.padding += len;
+        } else {
+            self.data += len;
+        }
+    }
+}
+
+/// A breakdown of the bytes written during serialization.
+///
+/// Each byte is counted once in `types`, under the innermost type being
+/// serialized when it was written. Fields are only reported by types derived
+/// with `#[rkyv(profile)]`. Each byte is counted under every such field that
+/// was being serialized when it was written, so the bytes of nested fields are
+/// included in the fields that contain them.
+///
+/// The inline bytes of a field are written as part of the value which contains
+/// it, so field totals only count the out-of-line data and padding written
+/// while serializing the field.
+#[derive(Clone, Debug, Default)]
+pub struct SizeProfile {
+    /// The total number of bytes written.
+    pub total: ByteCounts,
+    /// The number of bytes written by each type.
+    pub types: BTreeMap<&'static str, ByteCounts>,
+    /// The number of bytes written while serializing each field.
+    pub fields: BTreeMap<&'static str, ByteCounts>,
+}
+
+impl SizeProfile {
+    fn record(&mut self, scopes: &[Scope], len: usize, padding: bool) {
+        if len == 0 {
+            return;
+        }
+
+        self.total.add(len, padding);
+
+        let ty = match scopes.last() {
+            Some(Scope::Type(ty)) | Some(Scope::Field { ty, .. }) => ty,
+            None => "<unscoped>",
+        };
+        self.types.entry(ty).or_default().add(len, padding);
+
+        for (i, scope) in scopes.iter().enumerate() {
+            if let Scope::Field { name, .. } = scope {
+                // Recursive types can enter the same field more than once.
+                let is_outermost = !scopes[..i].iter().any(
+                    |s| matches!(s, Scope::Field { name: n, .. } if n == name),
+                );
+                if is_outermost {
+                    self.fields.entry(name).or_default().add(len, padding);
+                }
+            }
+        }
+    }
+}
+
+fn write_table(
+    f: &mut fmt::Formatter<'_>,
+    title: &str,
+    counts: &BTreeMap<&'static str, ByteCounts>,
+) -> fmt::Result {
+    let mut rows = counts.iter().collect::<Vec<_>>();
+    rows.sort_by_key(|(_, counts)| Reverse(counts.total()));
+
+    writeln!(
+        f,
+        "{:>10} {:>10} {:>10}  {}",
+        "total", "data", "padding", title
+    )?;
+    for (name, counts) in rows {
+        writeln!(
+            f,
+            "{:>10} {:>10} {:>10}  {}",
+            counts.total(),
+            counts.data,
+            counts.padding,
+            name,
+        )?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for SizeProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} bytes ({} data, {} padding)",
+            self.total.total(),
+            self.total.data,
+            self.total.padding,
+        )?;
+        writeln!(f)?;
+        write_table(f, "type", &self.types)?;
+        if !self.fields.is_empty() {
+            writeln!(f)?;
+            write_table(f, "field", &self.fields)?;
+        }
+        Ok(())
+    }
+}
+
+/// A passthrough writer that attributes written bytes to the types and fields
+/// that wrote them.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     rancor::Error, ser::writer::SizeProfiler, util::AlignedVec, Archive,
+///     Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// #[rkyv(profile)]
+/// struct Document {
+///     title: String,
+///     tags: Vec<String>,
+/// }
+///
+/// let value = Document {
+///     title: "Where do my bytes go".to_string(),
+///     tags: vec!["serialization".to_string(), "profiling".to_string()],
+/// };
+///
+/// let profiler = rkyv::api::high::to_bytes_in::<_, Error>(
+///     &value,
+///     SizeProfiler::new(AlignedVec::<16>::new()),
+/// )
+/// .unwrap();
+/// let (bytes, profile) = profiler.into_parts();
+///
+/// assert_eq!(profile.total.total(), bytes.len());
+/// assert!(profile.fields["Document.tags"].total() > 0);
+/// println!("{profile}");
+/// ```
+#[derive(Debug)]
+pub struct SizeProfiler<W> {
+    inner: W,
+    scopes: Vec<Scope>,
+    profile: SizeProfile,
+}
+
+impl<W> SizeProfiler<W> {
+    /// Returns a new size profiler wrapping the given writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            scopes: Vec::new(),
+            profile: SizeProfile::default(),
+        }
+    }
+
+    /// Returns the profile accumulated so far.
+    pub fn profile(&self) -> &SizeProfile {
+        &self.profile
+    }
+
+    /// Returns the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Returns the wrapped writer and the profile accumulated during
+    /// serialization.
+    pub fn into_parts(self) -> (W, SizeProfile) {
+        (self.inner, self.profile)
+    }
+}
+
+impl<W> From<W> for SizeProfiler<W> {
+    fn from(inner: W) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<W: Positional> Positional for SizeProfiler<W> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+}
+
+impl<W: Writer<E>, E> Writer<E> for SizeProfiler<W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.inner.write(bytes)?;
+        self.profile.record(&self.scopes, bytes.len(), false);
+        Ok(())
+    }
+
+    fn write_padding(&mut self, len: usize) -> Result<(), E> {
+        self.inner.write_padding(len)?;
+        self.profile.record(&self.scopes, len, true);
+        Ok(())
+    }
+
+    fn enter_scope(&mut self, scope: Scope) {
+        self.scopes.push(scope);
+        self.inner.enter_scope(scope);
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+        self.inner.exit_scope();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{
+        alloc::{
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
+        api::high::to_bytes_in,
+        ser::writer::SizeProfiler,
+        util::AlignedVec,
+        Archive, Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, profile)]
+    struct Inner {
+        name: String,
+        values: Vec<u64>,
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, profile)]
+    enum Outer {
+        Empty,
+        Full { id: u8, inner: Inner },
+        Many(Vec<Inner>),
+    }
+
+    #[test]
+    fn profile_types_and_fields() {
+        let value = vec![
+            Outer::Empty,
+            Outer::Full {
+                id: 1,
+                inner: Inner {
+                    name: "a name that is too long to inline".to_string(),
+                    values: vec![1, 2, 3],
+                },
+            },
+            Outer::Many(vec![Inner {
+                name: "ab".to_string(),
+                values: vec![4],
+            }]),
+        ];
+
+        let profiler = to_bytes_in::<_, Panic>(
+            &value,
+            SizeProfiler::new(AlignedVec::<16>::new()),
+        )
+        .unwrap();
+        let (bytes, profile) = profiler.into_parts();
+
+        assert_eq!(profile.total.total(), bytes.len());
+        assert_eq!(
+            profile.types.values().map(|c| c.data).sum::<usize>(),
+            profile.total.data,
+        );
+        assert_eq!(
+            profile.types.values().map(|c| c.padding).sum::<usize>(),
+            profile.total.padding,
+        );
+
+        let str_bytes = "a name that is too long to inline".len();
+        assert_eq!(profile.types["str"].data, str_bytes);
+
+        // Inline fields do not write bytes of their own.
+        assert!(!profile.fields.contains_key("Outer::Full.id"));
+        assert_eq!(profile.fields["Inner.name"].data, str_bytes);
+        assert_eq!(profile.fields["Inner.values"].data, 4 * 8);
+        assert!(profile.fields["Outer::Full.inner"].data >= str_bytes + 3 * 8);
+        assert!(profile.fields["Outer::Many.0"].data > 8);
+
+        let report = profile.to_string();
+        assert!(report.contains("Inner.name"));
+        assert!(report.contains("str"));
+    }
+
+    #[test]
+    fn scopes_close_on_error() {
+        use rancor::Failure;
+
+        use crate::ser::writer::Buffer;
+
+        let value = Inner {
+            name: "a name that is too long to fit in the buffer".to_string(),
+            values: vec![1, 2, 3],
+        };
+
+        let mut buffer = [0u8; 16];
+        let mut profiler = SizeProfiler::new(Buffer::from(&mut buffer));
+        to_bytes_in::<_, Failure>(&value, &mut profiler).unwrap_err();
+        assert!(profiler.scopes.is_empty());
+    }
+}
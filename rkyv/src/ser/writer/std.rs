@@ -1,8 +1,14 @@
-use std::io;
+use std::io::{self, IoSlice, SeekFrom};
 
 use rancor::{ResultExt as _, Source};
 
-use crate::ser::{Positional, Writer};
+use crate::{
+    alloc::vec::Vec,
+    ser::{
+        writer::{check_write_at, WriteAt},
+        Positional, Writer,
+    },
+};
 
 /// Wraps a type that implements [`io::Write`](std::io::Write) and equips it
 /// with [`Writer`].
@@ -61,13 +67,169 @@ impl<W: io::Write, E: Source> Writer<E> for IoWriter<W> {
     }
 }
 
+impl<W: io::Write + io::Seek, E: Source> WriteAt<E> for IoWriter<W> {
+    fn write_at(&mut self, pos: usize, bytes: &[u8]) -> Result<(), E> {
+        check_write_at(pos, bytes.len(), self.pos)?;
+        // Seek relative to the current position, since the position of the
+        // writer may not match the position of the underlying stream.
+        let back = (self.pos - pos) as i64;
+        self.inner.seek(SeekFrom::Current(-back)).into_error()?;
+        self.inner.write_all(bytes).into_error()?;
+        let forward = back - bytes.len() as i64;
+        self.inner.seek(SeekFrom::Current(forward)).into_error()?;
+        Ok(())
+    }
+}
+
+/// Wraps a type that implements [`io::Write`](std::io::Write) and writes large
+/// byte slices without copying them.
+///
+/// Small writes are collected in a buffer. Writes of at least `threshold` bytes
+/// are passed to [`write_vectored`](io::Write::write_vectored) along with the
+/// buffered bytes, so large payloads like `Vec<u8>`, `Box<[u8]>`, and other
+/// slices of [copy-optimized](crate::traits::CopyOptimization) types go
+/// straight from the serialized value to the underlying writer.
+///
+/// The buffered bytes are only written when the buffer fills up, when a large
+/// slice is written, or when they are written out with
+/// [`write_pending`](VectoredIoWriter::write_pending) or
+/// [`into_inner`](VectoredIoWriter::into_inner).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_in, rancor::Error, ser::writer::VectoredIoWriter,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Message {
+///     id: u32,
+///     payload: Vec<u8>,
+/// }
+///
+/// let value = Message {
+///     id: 42,
+///     payload: vec![0xab; 64 * 1024],
+/// };
+///
+/// let writer =
+///     to_bytes_in::<_, Error>(&value, VectoredIoWriter::new(Vec::new()))
+///         .unwrap();
+/// let bytes = writer.into_inner().unwrap();
+/// assert_eq!(bytes, rkyv::to_bytes::<Error>(&value).unwrap().as_slice());
+/// ```
+#[derive(Debug)]
+pub struct VectoredIoWriter<W> {
+    inner: W,
+    pos: usize,
+    buffer: Vec<u8>,
+    threshold: usize,
+}
+
+impl<W> VectoredIoWriter<W> {
+    /// The default size at which writes are passed through without copying.
+    pub const DEFAULT_THRESHOLD: usize = 4096;
+
+    /// Creates a new vectored writer from a writer.
+    pub fn new(inner: W) -> Self {
+        Self::with_threshold(inner, Self::DEFAULT_THRESHOLD)
+    }
+
+    /// Creates a new vectored writer from a writer, passing writes of at least
+    /// `threshold` bytes through without copying them.
+    pub fn with_threshold(inner: W, threshold: usize) -> Self {
+        Self {
+            inner,
+            pos: 0,
+            buffer: Vec::with_capacity(threshold),
+            threshold,
+        }
+    }
+
+    /// Returns the number of bytes which have been buffered but not yet
+    /// written to the internal writer.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl<W: io::Write> VectoredIoWriter<W> {
+    /// Writes any buffered bytes to the internal writer.
+    ///
+    /// This does not flush the internal writer.
+    pub fn write_pending(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.buffer)?;
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Writes any buffered bytes, then consumes the vectored writer and returns
+    /// the internal writer used to create it.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.write_pending()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W> Positional for VectoredIoWriter<W> {
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<W: io::Write, E: Source> Writer<E> for VectoredIoWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        if bytes.len() >= self.threshold {
+            let mut slices = [IoSlice::new(&self.buffer), IoSlice::new(bytes)];
+            write_all_vectored(&mut self.inner, &mut slices).into_error()?;
+            self.buffer.clear();
+        } else {
+            self.buffer.extend_from_slice(bytes);
+            if self.buffer.len() >= self.threshold {
+                self.write_pending().into_error()?;
+            }
+        }
+        self.pos += bytes.len();
+        Ok(())
+    }
+}
+
+fn write_all_vectored<W: io::Write>(
+    writer: &mut W,
+    mut slices: &mut [IoSlice<'_>],
+) -> io::Result<()> {
+    IoSlice::advance_slices(&mut slices, 0);
+    while !slices.is_empty() {
+        match writer.write_vectored(slices) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ))
+            }
+            Ok(n) => IoSlice::advance_slices(&mut slices, n),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
-    use rancor::Failure;
+    use std::io::{self, IoSlice};
+
+    use rancor::{Failure, Panic};
 
     use crate::{
-        api::serialize_using, ser::writer::IoWriter, util::Align, Archive,
-        Serialize,
+        alloc::{boxed::Box, vec, vec::Vec},
+        api::{high::to_bytes_in, serialize_using},
+        ser::writer::{IoWriter, VectoredIoWriter},
+        to_bytes,
+        util::Align,
+        Archive, Serialize,
     };
 
     #[test]
@@ -84,4 +246,85 @@ mod tests {
         serialize_using::<_, Failure>(&foo, &mut ser)
             .expect_err("serialized to an undersized buffer must fail");
     }
+
+    /// Records the address and length of every slice written to it.
+    #[derive(Default)]
+    struct RecordingWriter {
+        bytes: Vec<u8>,
+        slices: Vec<(usize, usize)>,
+    }
+
+    impl io::Write for RecordingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.write_vectored(&[IoSlice::new(buf)])
+        }
+
+        fn write_vectored(
+            &mut self,
+            bufs: &[IoSlice<'_>],
+        ) -> io::Result<usize> {
+            // Write at most part of the first two slices to exercise partial
+            // writes.
+            let mut written = 0;
+            for buf in bufs.iter().take(2) {
+                let len = buf.len().min(1000);
+                self.slices.push((buf.as_ptr() as usize, len));
+                self.bytes.extend_from_slice(&buf[..len]);
+                written += len;
+                if len < buf.len() {
+                    break;
+                }
+            }
+            Ok(written)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn vectored_writer_borrows_large_slices() {
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Blobs {
+            id: u32,
+            small: Vec<u8>,
+            large: Vec<u8>,
+            boxed: Box<[u8]>,
+        }
+
+        let value = Blobs {
+            id: 42,
+            small: vec![1; 100],
+            large: (0..=255).cycle().take(10_000).collect(),
+            boxed: vec![2; 5000].into_boxed_slice(),
+        };
+
+        let writer = to_bytes_in::<_, Panic>(
+            &value,
+            VectoredIoWriter::with_threshold(RecordingWriter::default(), 4096),
+        )
+        .unwrap();
+        let recording = writer.into_inner().unwrap();
+
+        assert_eq!(
+            recording.bytes,
+            to_bytes::<Panic>(&value).unwrap().as_slice()
+        );
+
+        // Large payloads are written straight from the serialized value.
+        for payload in [value.large.as_slice(), &value.boxed] {
+            let start = payload.as_ptr() as usize;
+            let written = recording
+                .slices
+                .iter()
+                .filter(|(ptr, _)| (start..start + payload.len()).contains(ptr))
+                .map(|(_, len)| len)
+                .sum::<usize>();
+            assert_eq!(written, payload.len());
+        }
+        let small = value.small.as_ptr() as usize;
+        assert!(!recording.slices.iter().any(|(ptr, _)| *ptr == small));
+    }
 }
@@ -94,7 +94,9 @@ impl Group {
 
     #[inline]
     pub fn match_empty(self) -> Bitmask {
-        let bits = self.0 & Self::repeat(0x80);
+        // Only EMPTY (0xFF) has both of its top two bits set. DELETED (0x80)
+        // and full (0x00 to 0x7F) bytes do not.
+        let bits = self.0 & (self.0 << 1) & Self::repeat(0x80);
         Bitmask(bits)
     }
 
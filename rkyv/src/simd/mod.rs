@@ -66,4 +66,117 @@ mod generic;
 ))]
 pub use self::generic::*;
 
-pub const MAX_GROUP_WIDTH: usize = 16;
+// Wide groups are only used to probe tables with a group width of at least
+// their own width. AVX-512 intrinsics were stabilized in Rust 1.89, and stable
+// compilers before then never enable the `avx512bw` target feature.
+
+#[cfg(all(
+    target_feature = "avx512bw",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(miri),
+))]
+#[allow(clippy::incompatible_msrv)]
+mod avx512;
+
+#[cfg(all(
+    target_feature = "avx512bw",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(miri),
+))]
+pub use self::avx512::Group as WideGroup;
+
+#[cfg(all(
+    target_feature = "avx2",
+    not(target_feature = "avx512bw"),
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(miri),
+))]
+mod avx2;
+
+#[cfg(all(
+    target_feature = "avx2",
+    not(target_feature = "avx512bw"),
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(miri),
+))]
+pub use self::avx2::Group as WideGroup;
+
+#[cfg(not(all(
+    any(target_feature = "avx2", target_feature = "avx512bw"),
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(miri),
+)))]
+pub type WideGroup = Group;
+
+/// The narrowest group width of an archived hash table.
+pub const MIN_GROUP_WIDTH: usize = 16;
+
+/// The widest group width of an archived hash table.
+pub const MAX_GROUP_WIDTH: usize = 64;
+
+/// The widest group width that this target can probe with a single read.
+pub const NATIVE_GROUP_WIDTH: usize = if WideGroup::WIDTH > MIN_GROUP_WIDTH {
+    WideGroup::WIDTH
+} else {
+    MIN_GROUP_WIDTH
+};
+
+/// A group of control bytes which can be matched in parallel.
+pub trait ProbeGroup: Copy {
+    type Bitmask: Iterator<Item = usize>;
+
+    const WIDTH: usize;
+
+    /// # Safety
+    ///
+    /// `ptr` must be valid for reads and point to `WIDTH` bytes.
+    unsafe fn read(ptr: *const u8) -> Self;
+
+    fn match_byte(self, byte: u8) -> Self::Bitmask;
+
+    fn any_empty(self) -> bool;
+}
+
+macro_rules! impl_probe_group {
+    ($group:ty, $bitmask:ty) => {
+        impl ProbeGroup for $group {
+            type Bitmask = $bitmask;
+
+            const WIDTH: usize = <$group>::WIDTH;
+
+            #[inline]
+            unsafe fn read(ptr: *const u8) -> Self {
+                // SAFETY: The caller has guaranteed that `ptr` is valid for
+                // reads and points to `WIDTH` bytes.
+                unsafe { <$group>::read(ptr) }
+            }
+
+            #[inline]
+            fn match_byte(self, byte: u8) -> Self::Bitmask {
+                <$group>::match_byte(self, byte)
+            }
+
+            #[inline]
+            fn any_empty(self) -> bool {
+                <$group>::match_empty(self).any_bit_set()
+            }
+        }
+    };
+}
+
+impl_probe_group!(Group, Bitmask);
+
+#[cfg(all(
+    target_feature = "avx512bw",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(miri),
+))]
+impl_probe_group!(avx512::Group, avx512::Bitmask);
+
+#[cfg(all(
+    target_feature = "avx2",
+    not(target_feature = "avx512bw"),
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(miri),
+))]
+impl_probe_group!(avx2::Group, avx2::Bitmask);
@@ -76,11 +76,7 @@ impl Group {
 
     #[inline]
     pub fn match_empty(self) -> Bitmask {
-        unsafe {
-            Self::unpack(aarch64::vcltzq_s8(aarch64::vreinterpretq_s8_u8(
-                self.0,
-            )))
-        }
+        self.match_byte(0xff)
     }
 
     #[inline]
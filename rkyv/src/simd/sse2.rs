@@ -66,7 +66,7 @@ impl Group {
 
     #[inline]
     pub fn match_empty(self) -> Bitmask {
-        unsafe { Bitmask(x86::_mm_movemask_epi8(self.0) as u16) }
+        self.match_byte(0xff)
     }
 
     #[inline]
@@ -0,0 +1,662 @@
+//! Alternative archived string representations.
+//!
+//! [`ArchivedString`](crate::string::ArchivedString) always uses a
+//! pointer-width representation which can inline strings of up to
+//! [`INLINE_CAPACITY`] bytes. The string types in this module trade some of
+//! that flexibility for a smaller footprint, and can be selected for a field
+//! with the corresponding wrapper:
+//!
+//! - [`ArchivedPrefixedString`] with [`AsPrefixedString`] stores a relative
+//!   pointer to a length-prefixed string.
+//! - [`ArchivedInlineString`] with [`AsInlineString`] inlines strings of up to
+//!   `N` bytes.
+//! - [`ArchivedPlainString`] with [`AsPlainString`] always stores a relative
+//!   pointer and a length.
+//!
+//! [`AsPrefixedString`]: crate::with::AsPrefixedString
+//! [`AsInlineString`]: crate::with::AsInlineString
+//! [`AsPlainString`]: crate::with::AsPlainString
+
+use core::{
+    borrow::Borrow,
+    cmp, fmt, hash,
+    marker::PhantomData,
+    mem::size_of,
+    ops::Deref,
+    ptr::{copy_nonoverlapping, write_bytes},
+    slice, str,
+};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    primitive::{ArchivedU16, ArchivedU32, ArchivedUsize, FixedUsize},
+    seal::Seal,
+    ser::{Writer, WriterExt as _},
+    string::{
+        repr::{ArchivedStringRepr, INLINE_CAPACITY, OUT_OF_LINE_CAPACITY},
+        StringResolver, StringTooLongError,
+    },
+    Archive, Place, Portable, RelPtr, SerializeUnsized,
+};
+
+macro_rules! impl_str_traits {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> AsRef<str> for $ty {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl<$($generics)*> Borrow<str> for $ty {
+            #[inline]
+            fn borrow(&self) -> &str {
+                self.as_str()
+            }
+        }
+
+        impl<$($generics)*> fmt::Debug for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(self.as_str(), f)
+            }
+        }
+
+        impl<$($generics)*> Deref for $ty {
+            type Target = str;
+
+            #[inline]
+            fn deref(&self) -> &Self::Target {
+                self.as_str()
+            }
+        }
+
+        impl<$($generics)*> fmt::Display for $ty {
+            #[inline]
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(self.as_str(), f)
+            }
+        }
+
+        impl<$($generics)*> Eq for $ty {}
+
+        impl<$($generics)*> hash::Hash for $ty {
+            fn hash<H: hash::Hasher>(&self, state: &mut H) {
+                self.as_str().hash(state)
+            }
+        }
+
+        impl<$($generics)*> Ord for $ty {
+            #[inline]
+            fn cmp(&self, other: &Self) -> cmp::Ordering {
+                self.as_str().cmp(other.as_str())
+            }
+        }
+
+        impl<$($generics)*> PartialEq for $ty {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.as_str() == other.as_str()
+            }
+        }
+
+        impl<$($generics)*> PartialOrd for $ty {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<$($generics)*> PartialEq<str> for $ty {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                PartialEq::eq(self.as_str(), other)
+            }
+        }
+
+        impl<$($generics)*> PartialEq<&str> for $ty {
+            #[inline]
+            fn eq(&self, other: &&str) -> bool {
+                PartialEq::eq(self.as_str(), *other)
+            }
+        }
+
+        impl<$($generics)*> PartialEq<$ty> for str {
+            #[inline]
+            fn eq(&self, other: &$ty) -> bool {
+                PartialEq::eq(other.as_str(), self)
+            }
+        }
+
+        impl<$($generics)*> PartialOrd<str> for $ty {
+            #[inline]
+            fn partial_cmp(&self, other: &str) -> Option<cmp::Ordering> {
+                self.as_str().partial_cmp(other)
+            }
+        }
+    };
+}
+
+/// A native integer type which can be used as the length prefix of an
+/// [`ArchivedPrefixedString`].
+///
+/// This is implemented for `u8`, `u16`, and `u32`.
+pub trait LengthPrefix: Archive<Resolver = ()> {
+    /// The maximum length which can be stored in the prefix.
+    const MAX: usize;
+
+    /// Returns the prefix for the given length.
+    ///
+    /// The length must be less than or equal to [`MAX`](LengthPrefix::MAX).
+    fn from_len(len: usize) -> Self;
+
+    /// Returns the length stored in the given archived prefix.
+    fn to_len(archived: &Self::Archived) -> usize;
+}
+
+impl LengthPrefix for u8 {
+    const MAX: usize = u8::MAX as usize;
+
+    #[inline]
+    fn from_len(len: usize) -> Self {
+        len as u8
+    }
+
+    #[inline]
+    fn to_len(archived: &u8) -> usize {
+        *archived as usize
+    }
+}
+
+macro_rules! impl_length_prefix {
+    ($prim:ty, $archived:ty) => {
+        impl LengthPrefix for $prim {
+            const MAX: usize = if (<$prim>::MAX as u64) < usize::MAX as u64 {
+                <$prim>::MAX as usize
+            } else {
+                usize::MAX
+            };
+
+            #[inline]
+            fn from_len(len: usize) -> Self {
+                len as $prim
+            }
+
+            #[inline]
+            fn to_len(archived: &$archived) -> usize {
+                archived.to_native() as usize
+            }
+        }
+    };
+}
+
+impl_length_prefix!(u16, ArchivedU16);
+impl_length_prefix!(u32, ArchivedU32);
+
+/// An archived string which is stored out-of-line with a length prefix.
+///
+/// The string itself consists of only a relative pointer. The pointed-to data
+/// is an archived `L` holding the length of the string, immediately followed by
+/// the bytes of the string. Using a `u8` or `u16` for `L` makes this the
+/// smallest archived representation for collections of short strings.
+#[derive(Portable)]
+#[rkyv(crate)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[repr(transparent)]
+pub struct ArchivedPrefixedString<L: LengthPrefix> {
+    ptr: RelPtr<L::Archived>,
+    _phantom: PhantomData<L>,
+}
+
+impl<L: LengthPrefix> ArchivedPrefixedString<L> {
+    /// Returns the length of the string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        L::to_len(unsafe { &*self.ptr.as_ptr() })
+    }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the bytes of the string.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        unsafe { self.ptr.as_ptr().cast::<u8>().add(size_of::<L::Archived>()) }
+    }
+
+    /// Extracts a string slice containing the entire string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe {
+            str::from_utf8_unchecked(slice::from_raw_parts(
+                self.as_ptr(),
+                self.len(),
+            ))
+        }
+    }
+
+    /// Extracts a sealed mutable string slice containing the entire string.
+    #[inline]
+    pub fn as_str_seal(this: Seal<'_, Self>) -> Seal<'_, str> {
+        let len = this.len();
+        munge!(let Self { ptr, .. } = this);
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(
+                RelPtr::as_mut_ptr(ptr)
+                    .cast::<u8>()
+                    .add(size_of::<L::Archived>()),
+                len,
+            )
+        };
+        Seal::new(unsafe { str::from_utf8_unchecked_mut(bytes) })
+    }
+
+    /// Resolves an archived prefixed string from a given `str`.
+    #[inline]
+    pub fn resolve_from_str(
+        _: &str,
+        resolver: StringResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedPrefixedString { ptr, _phantom: _ } = out);
+        RelPtr::emplace(resolver.pos as usize, ptr);
+    }
+
+    /// Serializes an archived prefixed string from a given `str`.
+    ///
+    /// Returns an error if the length of the string does not fit in an `L`.
+    pub fn serialize_from_str<S>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<StringResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+        S::Error: Source,
+    {
+        if value.len() > L::MAX {
+            fail!(StringTooLongError);
+        }
+
+        let pos = serializer.align_for::<L::Archived>()?;
+        unsafe {
+            serializer.resolve_aligned(&L::from_len(value.len()), ())?;
+        }
+        serializer.write(value.as_bytes())?;
+
+        Ok(StringResolver {
+            pos: pos as FixedUsize,
+        })
+    }
+}
+
+impl_str_traits!([L: LengthPrefix] ArchivedPrefixedString<L>);
+
+/// An archived string which can inline strings of up to `N` bytes.
+///
+/// This is a variant of [`ArchivedString`](crate::string::ArchivedString) with
+/// a configurable inline capacity. Strings longer than `N` bytes are stored
+/// out-of-line, using the same encoding as
+/// [`ArchivedStringRepr`]. `N` must be at least [`INLINE_CAPACITY`].
+#[derive(Portable)]
+#[rkyv(crate)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[repr(C)]
+pub struct ArchivedInlineString<const N: usize> {
+    _align: [ArchivedUsize; 0],
+    bytes: [u8; N],
+}
+
+impl<const N: usize> ArchivedInlineString<N> {
+    const CHECK_CAPACITY: () = assert!(
+        N >= INLINE_CAPACITY,
+        "the capacity of an inline string must be at least INLINE_CAPACITY",
+    );
+
+    /// The maximum number of bytes that can be inlined.
+    pub const INLINE_CAPACITY: usize = N;
+
+    #[inline]
+    fn out_of_line_repr(&self) -> &ArchivedStringRepr {
+        let () = Self::CHECK_CAPACITY;
+        // SAFETY: `Self` is at least as large as an `ArchivedStringRepr` and
+        // has the same alignment.
+        unsafe { &*(self as *const Self).cast::<ArchivedStringRepr>() }
+    }
+
+    /// Returns whether the string is inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.out_of_line_repr().is_inline()
+    }
+
+    /// Returns the length of the string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        if self.is_inline() {
+            self.bytes.iter().position(|b| *b == 0xff).unwrap_or(N)
+        } else {
+            self.out_of_line_repr().len()
+        }
+    }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the bytes of the string.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        if self.is_inline() {
+            self.bytes.as_ptr()
+        } else {
+            self.out_of_line_repr().as_ptr()
+        }
+    }
+
+    /// Extracts a string slice containing the entire string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe {
+            str::from_utf8_unchecked(slice::from_raw_parts(
+                self.as_ptr(),
+                self.len(),
+            ))
+        }
+    }
+
+    /// Extracts a sealed mutable string slice containing the entire string.
+    #[inline]
+    pub fn as_str_seal(this: Seal<'_, Self>) -> Seal<'_, str> {
+        let this = unsafe { this.unseal_unchecked() };
+        let len = this.len();
+        let ptr = if this.is_inline() {
+            this.bytes.as_mut_ptr()
+        } else {
+            let repr = unsafe {
+                &mut *(this as *mut Self).cast::<ArchivedStringRepr>()
+            };
+            ArchivedStringRepr::as_mut_ptr(Seal::new(repr))
+        };
+        let bytes = unsafe { slice::from_raw_parts_mut(ptr, len) };
+        Seal::new(unsafe { str::from_utf8_unchecked_mut(bytes) })
+    }
+
+    /// Resolves an archived inline string from a given `str`.
+    pub fn resolve_from_str(
+        value: &str,
+        resolver: StringResolver,
+        out: Place<Self>,
+    ) {
+        let () = Self::CHECK_CAPACITY;
+
+        if value.len() <= N {
+            munge!(let ArchivedInlineString { bytes, .. } = out);
+            // SAFETY: `bytes` points to `N` bytes, which is at least the
+            // length of `value`.
+            unsafe {
+                let bytes = bytes.ptr().cast::<u8>();
+                write_bytes(bytes, 0xff, N);
+                copy_nonoverlapping(value.as_ptr(), bytes, value.len());
+            }
+        } else {
+            // SAFETY: `Self` is at least as large as an `ArchivedStringRepr`
+            // and has the same alignment. The length of `value` is greater
+            // than `N`, which is at least `INLINE_CAPACITY`. It is less than
+            // `OUT_OF_LINE_CAPACITY` because it was checked during
+            // serialization.
+            unsafe {
+                let repr = out.cast_unchecked::<ArchivedStringRepr>();
+                ArchivedStringRepr::emplace_out_of_line(
+                    value,
+                    resolver.pos as usize,
+                    repr,
+                );
+            }
+        }
+    }
+
+    /// Serializes an archived inline string from a given `str`.
+    pub fn serialize_from_str<S>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<StringResolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        S::Error: Source,
+        str: SerializeUnsized<S>,
+    {
+        if value.len() <= N {
+            Ok(StringResolver { pos: 0 })
+        } else if value.len() > OUT_OF_LINE_CAPACITY {
+            fail!(StringTooLongError);
+        } else {
+            Ok(StringResolver {
+                pos: value.serialize_unsized(serializer)? as FixedUsize,
+            })
+        }
+    }
+}
+
+impl_str_traits!([const N: usize] ArchivedInlineString<N>);
+
+/// An archived string which always stores a relative pointer and a length.
+///
+/// Unlike [`ArchivedString`](crate::string::ArchivedString), this never
+/// inlines the string. It has the same layout as an
+/// [`ArchivedBox<str>`](crate::boxed::ArchivedBox), and can always represent
+/// strings up to the maximum archived `usize`.
+#[derive(Portable)]
+#[rkyv(crate)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[repr(transparent)]
+pub struct ArchivedPlainString {
+    ptr: RelPtr<str>,
+}
+
+impl ArchivedPlainString {
+    /// Returns the length of the string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ptr.metadata().to_native() as usize
+    }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the bytes of the string.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        unsafe { self.ptr.as_ptr().cast() }
+    }
+
+    /// Extracts a string slice containing the entire string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { &*self.ptr.as_ptr() }
+    }
+
+    /// Extracts a sealed mutable string slice containing the entire string.
+    #[inline]
+    pub fn as_str_seal(this: Seal<'_, Self>) -> Seal<'_, str> {
+        munge!(let Self { ptr } = this);
+        Seal::new(unsafe { &mut *RelPtr::as_mut_ptr(ptr) })
+    }
+
+    /// Resolves an archived plain string from a given `str`.
+    #[inline]
+    pub fn resolve_from_str(
+        value: &str,
+        resolver: StringResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedPlainString { ptr } = out);
+        RelPtr::emplace_unsized(
+            resolver.pos as usize,
+            ArchivedUsize::from_native(value.len() as FixedUsize),
+            ptr,
+        );
+    }
+
+    /// Serializes an archived plain string from a given `str`.
+    pub fn serialize_from_str<S>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<StringResolver, S::Error>
+    where
+        S: Fallible + ?Sized,
+        S::Error: Source,
+        str: SerializeUnsized<S>,
+    {
+        if value.len() > FixedUsize::MAX as usize {
+            fail!(StringTooLongError);
+        }
+
+        Ok(StringResolver {
+            pos: value.serialize_unsized(serializer)? as FixedUsize,
+        })
+    }
+}
+
+impl_str_traits!([] ArchivedPlainString);
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{alloc::Layout, error::Error, fmt, mem::size_of, str};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        CheckBytes, Verify,
+    };
+    use rancor::{fail, ResultExt as _};
+
+    use super::{
+        ArchivedInlineString, ArchivedPlainString, ArchivedPrefixedString,
+        LengthPrefix,
+    };
+    use crate::validation::{ArchiveContext, ArchiveContextExt};
+
+    /// An error resulting from an invalid inline string.
+    ///
+    /// Strings that are out-of-line must be longer than the inline capacity.
+    #[derive(Debug)]
+    struct CheckInlineStringError;
+
+    impl fmt::Display for CheckInlineStringError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "inline string was out-of-line but the length was too short",
+            )
+        }
+    }
+
+    impl Error for CheckInlineStringError {}
+
+    unsafe impl<L, C> Verify<C> for ArchivedPrefixedString<L>
+    where
+        L: LengthPrefix,
+        L::Archived: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let prefix = self.ptr.as_ptr_wrapping();
+            let prefix_layout = Layout::new::<L::Archived>();
+            context.check_subtree_ptr(prefix.cast(), &prefix_layout)?;
+
+            // SAFETY: We checked that the prefix is located inside of the
+            // subtree range and is properly aligned.
+            let len = unsafe {
+                L::Archived::check_bytes(prefix, context)?;
+                L::to_len(&*prefix)
+            };
+
+            let layout = Layout::from_size_align(
+                size_of::<L::Archived>() + len,
+                prefix_layout.align(),
+            )
+            .into_error()?;
+
+            context.in_subtree_raw(prefix.cast(), layout, |context| {
+                let bytes =
+                    prefix.cast::<u8>().wrapping_add(size_of::<L::Archived>());
+                let ptr = ptr_meta::from_raw_parts::<str>(bytes.cast(), len);
+                // SAFETY: `in_subtree_raw` has guaranteed that the prefix and
+                // the following `len` bytes are located within the subtree.
+                unsafe { str::check_bytes(ptr, context) }
+            })
+        }
+    }
+
+    unsafe impl<const N: usize, C> Verify<C> for ArchivedInlineString<N>
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            if self.is_inline() {
+                let ptr =
+                    ptr_meta::from_raw_parts::<str>(self.as_ptr().cast(), {
+                        self.len()
+                    });
+                // SAFETY: Inline strings are located entirely inside of
+                // `self`.
+                unsafe { str::check_bytes(ptr, context) }
+            } else {
+                let repr = self.out_of_line_repr();
+                let len = repr.len();
+                if len <= N {
+                    fail!(CheckInlineStringError);
+                }
+
+                let base =
+                    (repr as *const super::ArchivedStringRepr).cast::<u8>();
+                let offset = unsafe { repr.out_of_line_offset() };
+                let address = base.wrapping_offset(offset).cast::<()>();
+                let ptr = ptr_meta::from_raw_parts::<str>(address, len);
+
+                context.in_subtree(ptr, |context| {
+                    // SAFETY: `in_subtree` has guaranteed that `ptr` is
+                    // properly aligned and points to enough bytes to represent
+                    // the pointed-to `str`.
+                    unsafe { str::check_bytes(ptr, context) }
+                })
+            }
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedPlainString
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let ptr = self.ptr.as_ptr_wrapping();
+            context.in_subtree(ptr, |context| unsafe {
+                str::check_bytes(ptr, context)
+            })
+        }
+    }
+}
@@ -1,5 +1,6 @@
 //! Archived versions of string types.
 
+pub mod compact;
 pub mod repr;
 
 use core::{
@@ -54,6 +55,25 @@ impl ArchivedString {
         ArchivedStringRepr::as_str_seal(repr)
     }
 
+    /// Returns the number of bytes the archived string can hold without being
+    /// moved.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.repr.capacity()
+    }
+
+    /// Overwrites the archived string in place with `value`.
+    ///
+    /// Returns an error if `value` is longer than the
+    /// [`capacity`](ArchivedString::capacity) of the archived string.
+    pub fn try_overwrite_seal<E: Source>(
+        this: Seal<'_, Self>,
+        value: &str,
+    ) -> Result<(), E> {
+        munge!(let Self { repr } = this);
+        ArchivedStringRepr::try_overwrite(repr, value)
+    }
+
     /// Resolves an archived string from a given `str`.
     #[inline]
     pub fn resolve_from_str(
@@ -89,20 +109,6 @@ impl ArchivedString {
         if value.len() <= INLINE_CAPACITY {
             Ok(StringResolver { pos: 0 })
         } else if value.len() > repr::OUT_OF_LINE_CAPACITY {
-            #[derive(Debug)]
-            struct StringTooLongError;
-
-            impl fmt::Display for StringTooLongError {
-                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                    write!(
-                        f,
-                        "String was too long for the archived representation",
-                    )
-                }
-            }
-
-            impl Error for StringTooLongError {}
-
             fail!(StringTooLongError);
         } else {
             Ok(StringResolver {
@@ -259,6 +265,17 @@ pub struct StringResolver {
     pos: FixedUsize,
 }
 
+#[derive(Debug)]
+struct StringTooLongError;
+
+impl fmt::Display for StringTooLongError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "String was too long for the archived representation")
+    }
+}
+
+impl Error for StringTooLongError {}
+
 #[cfg(feature = "bytecheck")]
 mod verify {
     use bytecheck::{
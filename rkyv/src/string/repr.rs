@@ -1,6 +1,8 @@
 //! An archived string representation that supports inlining short strings.
 
 use core::{
+    error::Error,
+    fmt,
     marker::PhantomPinned,
     mem,
     ptr::{self, copy_nonoverlapping, write_bytes},
@@ -8,7 +10,7 @@ use core::{
 };
 
 use munge::munge;
-use rancor::{Panic, ResultExt as _, Source};
+use rancor::{fail, Panic, ResultExt as _, Source};
 
 use crate::{
     primitive::{ArchivedIsize, ArchivedUsize, FixedIsize, FixedUsize},
@@ -37,6 +39,37 @@ struct InlineRepr {
     bytes: [u8; INLINE_CAPACITY],
 }
 
+#[derive(Debug)]
+struct StringCapacityError {
+    len: usize,
+    capacity: usize,
+}
+
+impl fmt::Display for StringCapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string of length {} does not fit in an archived string with \
+             capacity {}",
+            self.len, self.capacity,
+        )
+    }
+}
+
+impl Error for StringCapacityError {}
+
+/// Returns the encoded length of an out-of-line representation.
+fn out_of_line_len(len: usize) -> ArchivedUsize {
+    let l = len as FixedUsize;
+    // Little-endian: insert 10 as the 7th and 8th bits
+    #[cfg(not(feature = "big_endian"))]
+    let l = (l & 0b0011_1111) | 0b1000_0000 | ((l & !0b0011_1111) << 2);
+    // Big-endian: set the top two bits to 10
+    #[cfg(feature = "big_endian")]
+    let l = l & (FixedUsize::MAX >> 2) | (1 << FixedUsize::BITS - 1);
+    ArchivedUsize::from_native(l)
+}
+
 /// An archived string representation that can inline short strings.
 #[derive(Portable)]
 #[rkyv(crate)]
@@ -123,6 +156,60 @@ impl ArchivedStringRepr {
         self.len() == 0
     }
 
+    /// Returns the number of bytes the string can hold without moving.
+    ///
+    /// Inline strings can hold up to [`INLINE_CAPACITY`] bytes. Out-of-line
+    /// strings can hold up to their current length.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        if self.is_inline() {
+            INLINE_CAPACITY
+        } else {
+            self.len()
+        }
+    }
+
+    /// Overwrites the string in place with `value`.
+    ///
+    /// Returns an error if `value` is longer than the capacity of the string.
+    /// If `value` is short enough to be inlined, the representation becomes
+    /// inline. Otherwise, `value` is copied over the existing out-of-line
+    /// bytes.
+    pub fn try_overwrite<E: Source>(
+        this: Seal<'_, Self>,
+        value: &str,
+    ) -> Result<(), E> {
+        let capacity = this.capacity();
+        if value.len() > capacity {
+            fail!(StringCapacityError {
+                len: value.len(),
+                capacity,
+            });
+        }
+
+        // SAFETY: Only initialized bytes are written to `this`.
+        let this = unsafe { this.unseal_unchecked() };
+        if value.len() <= INLINE_CAPACITY {
+            // SAFETY: We checked that the length of `value` is less than or
+            // equal to `INLINE_CAPACITY`, and `this` is a valid location.
+            unsafe {
+                Self::emplace_inline(value, this);
+            }
+        } else {
+            // The capacity of the string is greater than `INLINE_CAPACITY`, so
+            // it must be out-of-line.
+            let ptr = Self::as_mut_ptr(Seal::new(this));
+            // SAFETY: The out-of-line bytes are valid for writes of `capacity`
+            // bytes, and the length of `value` is at most `capacity`.
+            unsafe {
+                copy_nonoverlapping(value.as_ptr(), ptr, value.len());
+            }
+            this.out_of_line.len = out_of_line_len(value.len());
+        }
+
+        Ok(())
+    }
+
     /// Returns a pointer to the string as a `str`.
     #[inline]
     pub fn as_str_ptr(&self) -> *const str {
@@ -208,14 +295,7 @@ impl ArchivedStringRepr {
             } = out;
         }
 
-        let l = value.len() as FixedUsize;
-        // Little-endian: insert 10 as the 7th and 8th bits
-        #[cfg(not(feature = "big_endian"))]
-        let l = (l & 0b0011_1111) | 0b1000_0000 | ((l & !0b0011_1111) << 2);
-        // Big-endian: set the top two bits to 10
-        #[cfg(feature = "big_endian")]
-        let l = l & (FixedUsize::MAX >> 2) | (1 << FixedUsize::BITS - 1);
-        len.write(ArchivedUsize::from_native(l));
+        len.write(out_of_line_len(value.len()));
 
         let off = crate::rel_ptr::signed_offset(out.pos(), target)?;
         offset.write(ArchivedIsize::from_native(off as FixedIsize));
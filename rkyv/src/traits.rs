@@ -462,6 +462,49 @@ pub trait SerializeUnsized<S: Fallible + ?Sized>: ArchiveUnsized {
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error>;
 }
 
+/// A type which can cheaply estimate the size of its serialized dependencies.
+///
+/// `size_hint` returns an upper bound on the number of bytes written by
+/// [`Serialize::serialize`], which does not include the archived value
+/// itself. The bound accounts for worst-case alignment padding, so it is
+/// usually larger than the exact size computed by
+/// [`serialized_size`](crate::api::high::serialized_size).
+///
+/// This can be derived for structs and enums with `#[rkyv(size_hint)]`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{api::serialized_size_hint, rancor::Error, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[rkyv(size_hint)]
+/// struct Example {
+///     name: String,
+///     values: Vec<u32>,
+/// }
+///
+/// let value = Example {
+///     name: "a name which is too long to inline".to_string(),
+///     values: vec![1, 2, 3, 4],
+/// };
+/// let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+/// assert!(serialized_size_hint(&value) >= bytes.len());
+/// ```
+pub trait SizeHint {
+    /// Returns an upper bound on the number of bytes written to serialize the
+    /// dependencies of this value.
+    fn size_hint(&self) -> usize;
+}
+
+/// A counterpart of [`SizeHint`] that's suitable for unsized types.
+pub trait SizeHintUnsized {
+    /// Returns an upper bound on the number of bytes written by
+    /// [`SerializeUnsized::serialize_unsized`], including the archived value
+    /// and its dependencies.
+    fn size_hint_unsized(&self) -> usize;
+}
+
 /// A counterpart of [`Deserialize`] that's suitable for unsized types.
 pub trait DeserializeUnsized<T: Pointee + ?Sized, D: Fallible + ?Sized>:
     ArchivePointee
@@ -7,7 +7,7 @@ use core::{
     slice,
 };
 
-use rancor::Fallible;
+use rancor::{fail, Fallible, Source};
 
 use crate::{
     alloc::{
@@ -16,6 +16,7 @@ use crate::{
         vec::Vec,
     },
     ser::{Allocator, Writer},
+    util::AllocError,
     vec::{ArchivedVec, VecResolver},
     with::{ArchiveWith, AsVec, DeserializeWith, SerializeWith},
     Place,
@@ -180,10 +181,36 @@ impl<const ALIGNMENT: usize> AlignedVec<ALIGNMENT> {
     ///   [`MAX_CAPACITY`](AlignedVec::MAX_CAPACITY)
     /// - `new_cap` must be greater than or equal to [`len()`](AlignedVec::len)
     pub unsafe fn change_capacity(&mut self, new_cap: usize) {
+        // SAFETY: The caller has guaranteed that `new_cap` is less than or
+        // equal to `MAX_CAPACITY` and greater than or equal to `len`.
+        if let Err(layout) = unsafe { self.try_change_capacity(new_cap) } {
+            handle_alloc_error(layout);
+        }
+    }
+
+    /// Changes the capacity of the vector to exactly `new_cap`, returning the
+    /// layout that could not be allocated if allocation fails.
+    ///
+    /// # Safety
+    ///
+    /// - `new_cap` must be less than or equal to
+    ///   [`MAX_CAPACITY`](AlignedVec::MAX_CAPACITY)
+    /// - `new_cap` must be greater than or equal to [`len()`](AlignedVec::len)
+    unsafe fn try_change_capacity(
+        &mut self,
+        new_cap: usize,
+    ) -> Result<(), Layout> {
         debug_assert!(new_cap <= Self::MAX_CAPACITY);
         debug_assert!(new_cap >= self.len);
 
         if new_cap > 0 {
+            // SAFETY:
+            // - `ALIGNMENT` is always guaranteed to be a nonzero power of two.
+            // - The caller has guaranteed that `new_cap` doesn't overflow
+            //   `isize` when rounded up to the nearest power of two.
+            let layout = unsafe {
+                Layout::from_size_align_unchecked(new_cap, Self::ALIGNMENT)
+            };
             let new_ptr = if self.cap > 0 {
                 // SAFETY:
                 // - `self.ptr` is currently allocated because `self.cap` is
@@ -191,43 +218,13 @@ impl<const ALIGNMENT: usize> AlignedVec<ALIGNMENT> {
                 // - `self.layout()` always matches the layout used to allocate
                 //   the current block of memory.
                 // - We checked that `new_cap` is greater than zero.
-                let new_ptr = unsafe {
-                    realloc(self.ptr.as_ptr(), self.layout(), new_cap)
-                };
-                if new_ptr.is_null() {
-                    // SAFETY:
-                    // - `ALIGNMENT` is always guaranteed to be a nonzero power
-                    //   of two.
-                    // - We checked that `new_cap` doesn't overflow `isize` when
-                    //   rounded up to the nearest power of two.
-                    let layout = unsafe {
-                        Layout::from_size_align_unchecked(
-                            new_cap,
-                            Self::ALIGNMENT,
-                        )
-                    };
-                    handle_alloc_error(layout);
-                }
-                new_ptr
+                unsafe { realloc(self.ptr.as_ptr(), self.layout(), new_cap) }
             } else {
-                // SAFETY:
-                // - `ALIGNMENT` is always guaranteed to be a nonzero power of
-                //   two.
-                // - We checked that `new_cap` doesn't overflow `isize` when
-                //   rounded up to the nearest power of two.
-                let layout = unsafe {
-                    Layout::from_size_align_unchecked(new_cap, Self::ALIGNMENT)
-                };
                 // SAFETY: We checked that `new_cap` has non-zero size.
-                let new_ptr = unsafe { alloc(layout) };
-                if new_ptr.is_null() {
-                    handle_alloc_error(layout);
-                }
-                new_ptr
+                unsafe { alloc(layout) }
             };
-            // SAFETY: We checked that `new_ptr` is non-null in each of the
-            // branches.
-            self.ptr = unsafe { NonNull::new_unchecked(new_ptr) };
+            // On failure, the existing allocation is left untouched.
+            self.ptr = NonNull::new(new_ptr).ok_or(layout)?;
             self.cap = new_cap;
         } else if self.cap > 0 {
             // SAFETY: Because the capacity is nonzero, `self.ptr` points to a
@@ -239,6 +236,8 @@ impl<const ALIGNMENT: usize> AlignedVec<ALIGNMENT> {
             self.ptr = NonNull::dangling();
             self.cap = 0;
         }
+
+        Ok(())
     }
 
     /// Shrinks the capacity of the vector as much as possible.
@@ -458,18 +457,8 @@ impl<const ALIGNMENT: usize> AlignedVec<ALIGNMENT> {
     pub unsafe fn grow_capacity_to(&mut self, new_cap: usize) {
         debug_assert!(new_cap > self.cap);
 
-        let new_cap = if new_cap > (isize::MAX as usize + 1) >> 1 {
-            // Rounding up to next power of 2 would result in `isize::MAX + 1`
-            // or higher, which exceeds max capacity. So cap at max
-            // instead.
-            assert!(
-                new_cap <= Self::MAX_CAPACITY,
-                "cannot reserve a larger AlignedVec"
-            );
-            Self::MAX_CAPACITY
-        } else {
-            // Cannot overflow due to check above
-            new_cap.next_power_of_two()
+        let Some(new_cap) = Self::grown_capacity(new_cap) else {
+            panic!("cannot reserve a larger AlignedVec");
         };
         // SAFETY: We just checked that `new_cap` is greater than or equal to
         // `len` and less than or equal to `MAX_CAPACITY`.
@@ -478,6 +467,81 @@ impl<const ALIGNMENT: usize> AlignedVec<ALIGNMENT> {
         }
     }
 
+    /// Returns the capacity that growing to `new_cap` results in, or `None` if
+    /// it would exceed `Self::MAX_CAPACITY`.
+    fn grown_capacity(new_cap: usize) -> Option<usize> {
+        if new_cap > (isize::MAX as usize + 1) >> 1 {
+            // Rounding up to next power of 2 would result in `isize::MAX + 1`
+            // or higher, which exceeds max capacity. So cap at max
+            // instead.
+            (new_cap <= Self::MAX_CAPACITY).then_some(Self::MAX_CAPACITY)
+        } else {
+            // Cannot overflow due to check above
+            Some(new_cap.next_power_of_two())
+        }
+    }
+
+    /// Tries to reserve capacity for at least `additional` more bytes to be
+    /// inserted into the given `AlignedVec`.
+    ///
+    /// This grows the vector the same way as [`reserve`](AlignedVec::reserve),
+    /// but returns an error instead of panicking if the new capacity exceeds
+    /// `Self::MAX_CAPACITY` or aborting if the allocation fails. The vector is
+    /// left unchanged if an error is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rkyv::{rancor::Error, util::AlignedVec};
+    ///
+    /// let mut vec = AlignedVec::<16>::new();
+    /// vec.push(1);
+    /// vec.try_reserve::<Error>(10).unwrap();
+    /// assert!(vec.capacity() >= 11);
+    ///
+    /// assert!(vec.try_reserve::<Error>(usize::MAX).is_err());
+    /// assert_eq!(vec.as_slice(), &[1]);
+    /// ```
+    pub fn try_reserve<E: Source>(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), E> {
+        // Cannot wrap because capacity always exceeds len,
+        // but avoids having to handle potential overflow here
+        let remaining = self.cap.wrapping_sub(self.len);
+        if additional > remaining {
+            self.do_try_reserve(additional)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Extend capacity after `try_reserve` has found it's necessary.
+    #[cold]
+    fn do_try_reserve<E: Source>(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), E> {
+        let new_cap = self
+            .len
+            .checked_add(additional)
+            .and_then(Self::grown_capacity);
+        match new_cap {
+            Some(new_cap) => self.try_grow_exact(new_cap),
+            None => fail!(AllocError::CapacityOverflow),
+        }
+    }
+
+    /// Changes the capacity to `new_cap`, converting allocation failures into
+    /// errors.
+    fn try_grow_exact<E: Source>(&mut self, new_cap: usize) -> Result<(), E> {
+        // SAFETY: Callers only grow to capacities which are greater than the
+        // current capacity and at most `MAX_CAPACITY`.
+        match unsafe { self.try_change_capacity(new_cap) } {
+            Ok(()) => Ok(()),
+            Err(layout) => fail!(AllocError::Exhausted { layout }),
+        }
+    }
+
     /// Resizes the Vec in-place so that len is equal to new_len.
     ///
     /// If new_len is greater than len, the Vec is extended by the difference,
@@ -680,6 +744,43 @@ impl<const ALIGNMENT: usize> AlignedVec<ALIGNMENT> {
         }
     }
 
+    /// Tries to reserve the minimum capacity for exactly `additional` more
+    /// bytes to be inserted into the given `AlignedVec`.
+    ///
+    /// This is the fallible version of
+    /// [`reserve_exact`](AlignedVec::reserve_exact). It returns an error
+    /// instead of panicking if the new capacity exceeds `Self::MAX_CAPACITY`
+    /// or aborting if the allocation fails.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rkyv::{rancor::Error, util::AlignedVec};
+    ///
+    /// let mut vec = AlignedVec::<16>::new();
+    /// vec.push(1);
+    /// vec.try_reserve_exact::<Error>(10).unwrap();
+    /// assert!(vec.capacity() >= 11);
+    ///
+    /// assert!(vec.try_reserve_exact::<Error>(usize::MAX).is_err());
+    /// ```
+    pub fn try_reserve_exact<E: Source>(
+        &mut self,
+        additional: usize,
+    ) -> Result<(), E> {
+        let Some(new_cap) = self
+            .len
+            .checked_add(additional)
+            .filter(|&new_cap| new_cap <= Self::MAX_CAPACITY)
+        else {
+            fail!(AllocError::CapacityOverflow);
+        };
+        if new_cap > self.cap {
+            self.try_grow_exact(new_cap)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Forces the length of the vector to `new_len`.
     ///
     /// This is a low-level operation that maintains none of the normal
@@ -1,4 +1,36 @@
 mod aligned_vec;
 mod arena;
 
+use core::{alloc::Layout, error::Error, fmt};
+
 pub use self::{aligned_vec::*, arena::*};
+
+/// An error which occurs when memory could not be allocated.
+#[derive(Debug)]
+pub(crate) enum AllocError {
+    /// The requested capacity overflowed the maximum allocation size.
+    CapacityOverflow,
+    /// The global allocator failed to allocate memory for the given layout.
+    Exhausted { layout: Layout },
+}
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityOverflow => {
+                write!(
+                    f,
+                    "requested capacity exceeds the maximum allocation size"
+                )
+            }
+            Self::Exhausted { layout } => write!(
+                f,
+                "failed to allocate {} bytes with alignment {}",
+                layout.size(),
+                layout.align(),
+            ),
+        }
+    }
+}
+
+impl Error for AllocError {}
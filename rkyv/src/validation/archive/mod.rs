@@ -1,5 +1,7 @@
 //! Basic archive buffer validation.
 
+#[cfg(feature = "alloc")]
+mod unordered;
 mod validator;
 
 use core::{alloc::Layout, ops::Range};
@@ -7,6 +9,8 @@ use core::{alloc::Layout, ops::Range};
 use bytecheck::rancor::{Fallible, Source, Strategy};
 use rancor::ResultExt as _;
 
+#[cfg(feature = "alloc")]
+pub use self::unordered::*;
 pub use self::validator::*;
 use crate::traits::LayoutRaw;
 
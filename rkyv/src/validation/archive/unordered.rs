@@ -0,0 +1,150 @@
+use core::{alloc::Layout, error::Error, fmt, marker::PhantomData, ops::Range};
+
+use rancor::{fail, Source};
+
+use super::validator::UnalignedPointer;
+use crate::{
+    alloc::collections::BTreeMap, fmt::Pointer, validation::ArchiveContext,
+};
+
+#[derive(Debug)]
+struct SubtreePointerOutOfBounds {
+    address: usize,
+    size: usize,
+    bytes: Range<usize>,
+}
+
+impl fmt::Display for SubtreePointerOutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "subtree pointer out of bounds: ptr {} size {} in buffer {}..{}",
+            Pointer(self.address),
+            self.size,
+            Pointer(self.bytes.start),
+            Pointer(self.bytes.end),
+        )
+    }
+}
+
+impl Error for SubtreePointerOutOfBounds {}
+
+#[derive(Debug)]
+struct OverlappingSubtreePointer {
+    address: usize,
+    size: usize,
+    claimed: Range<usize>,
+}
+
+impl fmt::Display for OverlappingSubtreePointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "subtree pointer overlapped a claimed subtree: ptr {} size {} \
+             overlaps {}..{}",
+            Pointer(self.address),
+            self.size,
+            Pointer(self.claimed.start),
+            Pointer(self.claimed.end),
+        )
+    }
+}
+
+impl Error for OverlappingSubtreePointer {}
+
+/// A validator that can verify archives with subtrees in any order.
+///
+/// [`ArchiveValidator`](super::ArchiveValidator) requires the nonlocal memory
+/// of each value to be located before it, which is how archives are written by
+/// serializers. Archives which have been patched by appending values to the end
+/// do not have that layout. This validator instead checks that no two subtrees
+/// overlap, which requires keeping track of every subtree it has visited.
+#[derive(Debug)]
+pub struct UnorderedArchiveValidator<'a> {
+    bytes: Range<usize>,
+    claimed: BTreeMap<usize, usize>,
+    _phantom: PhantomData<&'a [u8]>,
+}
+
+impl<'a> UnorderedArchiveValidator<'a> {
+    /// Creates a new unordered validator for the given bytes.
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        let Range { start, end } = bytes.as_ptr_range();
+        Self {
+            bytes: Range {
+                start: start as usize,
+                end: end as usize,
+            },
+            claimed: BTreeMap::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn find_overlapping(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Option<Range<usize>> {
+        let (&claimed_start, &claimed_end) =
+            self.claimed.range(..end).next_back()?;
+        (claimed_end > start).then_some(claimed_start..claimed_end)
+    }
+}
+
+unsafe impl<E: Source> ArchiveContext<E> for UnorderedArchiveValidator<'_> {
+    fn check_subtree_ptr(
+        &mut self,
+        ptr: *const u8,
+        layout: &Layout,
+    ) -> Result<(), E> {
+        let start = ptr as usize;
+        let end = ptr.wrapping_add(layout.size()) as usize;
+        if start < self.bytes.start || end > self.bytes.end || end < start {
+            fail!(SubtreePointerOutOfBounds {
+                address: start,
+                size: layout.size(),
+                bytes: self.bytes.clone(),
+            });
+        } else if start & (layout.align() - 1) != 0 {
+            fail!(UnalignedPointer {
+                address: start,
+                align: layout.align(),
+            });
+        }
+
+        // Zero-sized subtrees can't overlap anything.
+        if start != end {
+            if let Some(claimed) = self.find_overlapping(start, end) {
+                fail!(OverlappingSubtreePointer {
+                    address: start,
+                    size: layout.size(),
+                    claimed,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    unsafe fn push_subtree_range(
+        &mut self,
+        root: *const u8,
+        end: *const u8,
+    ) -> Result<Range<usize>, E> {
+        let range = Range {
+            start: root as usize,
+            end: end as usize,
+        };
+        if range.start != range.end {
+            self.claimed.insert(range.start, range.end);
+        }
+        Ok(range)
+    }
+
+    unsafe fn pop_subtree_range(&mut self, _: Range<usize>) -> Result<(), E> {
+        // Claimed subtrees stay claimed so that no other subtree may overlap
+        // them.
+        Ok(())
+    }
+}
@@ -8,9 +8,9 @@ use rancor::{fail, OptionExt, Source};
 use crate::{fmt::Pointer, validation::ArchiveContext};
 
 #[derive(Debug)]
-struct UnalignedPointer {
-    address: usize,
-    align: usize,
+pub(super) struct UnalignedPointer {
+    pub(super) address: usize,
+    pub(super) align: usize,
 }
 
 impl fmt::Display for UnalignedPointer {
@@ -0,0 +1,439 @@
+//! An archived vector of bit-packed values.
+
+use core::{
+    error::Error, fmt, iter::FusedIterator, marker::PhantomData, slice,
+};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    primitive::{ArchivedUsize, FixedUsize},
+    seal::Seal,
+    ser::Writer,
+    Place, Portable, RelPtr,
+};
+
+/// A value which can be stored in an [`ArchivedBitVec`].
+///
+/// This is implemented for `bool` and the unsigned integers. Other small types
+/// like bitflag sets can implement it by converting to and from their raw
+/// bits.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::vec::bits::BitPackable;
+///
+/// #[derive(Clone, Copy)]
+/// struct Permissions(u8);
+///
+/// impl Permissions {
+///     const READ: Self = Self(0b001);
+///     const WRITE: Self = Self(0b010);
+///     const EXECUTE: Self = Self(0b100);
+/// }
+///
+/// impl BitPackable for Permissions {
+///     const MAX_BITS: u32 = 3;
+///
+///     fn to_bits(&self) -> u64 {
+///         self.0 as u64
+///     }
+///
+///     fn from_bits(bits: u64) -> Self {
+///         Self(bits as u8)
+///     }
+/// }
+/// ```
+pub trait BitPackable: Copy {
+    /// The maximum number of bits which may be used to store the value.
+    const MAX_BITS: u32;
+
+    /// Returns the bits of the value.
+    fn to_bits(&self) -> u64;
+
+    /// Creates a value from its bits.
+    fn from_bits(bits: u64) -> Self;
+}
+
+impl BitPackable for bool {
+    const MAX_BITS: u32 = 1;
+
+    #[inline]
+    fn to_bits(&self) -> u64 {
+        *self as u64
+    }
+
+    #[inline]
+    fn from_bits(bits: u64) -> Self {
+        bits != 0
+    }
+}
+
+macro_rules! impl_bit_packable {
+    ($($ty:ty),*) => {
+        $(
+            impl BitPackable for $ty {
+                const MAX_BITS: u32 = <$ty>::BITS;
+
+                #[inline]
+                fn to_bits(&self) -> u64 {
+                    *self as u64
+                }
+
+                #[inline]
+                fn from_bits(bits: u64) -> Self {
+                    bits as $ty
+                }
+            }
+        )*
+    };
+}
+
+impl_bit_packable!(u8, u16, u32, u64);
+
+#[derive(Debug)]
+struct BitPackedValueTooLargeError {
+    bits: u32,
+}
+
+impl fmt::Display for BitPackedValueTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "value was too large to be packed into {} bits",
+            self.bits
+        )
+    }
+}
+
+impl Error for BitPackedValueTooLargeError {}
+
+#[inline]
+fn byte_len(len: usize, bits: u32) -> Option<usize> {
+    Some(len.checked_mul(bits as usize)?.div_ceil(8))
+}
+
+/// An archived vector of values packed into `BITS` bits each.
+///
+/// Values are packed least-significant bit first, and any unused bits in the
+/// final byte are zero. This can be selected for a field with the
+/// [`BitPacked`](crate::with::BitPacked) wrapper.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedBitVec<T, const BITS: u32> {
+    ptr: RelPtr<u8>,
+    len: ArchivedUsize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: BitPackable, const BITS: u32> ArchivedBitVec<T, BITS> {
+    const CHECK_BITS: () = assert!(
+        BITS > 0 && BITS <= T::MAX_BITS,
+        "the number of bits per value must be between 1 and the size of the \
+         value type",
+    );
+
+    /// Returns the number of values in the archived bit vec.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the archived bit vec is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the packed bytes of the archived bit vec.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        let len = byte_len(self.len(), BITS).unwrap();
+        // SAFETY: The relative pointer always points to `len` bytes.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), len) }
+    }
+
+    /// Returns the packed bytes of the archived bit vec as a sealed mutable
+    /// slice.
+    pub fn as_bytes_seal(this: Seal<'_, Self>) -> Seal<'_, [u8]> {
+        let len = byte_len(this.len(), BITS).unwrap();
+        munge!(let Self { ptr, .. } = this);
+        // SAFETY: The relative pointer always points to `len` bytes.
+        let slice =
+            unsafe { slice::from_raw_parts_mut(RelPtr::as_mut_ptr(ptr), len) };
+        Seal::new(slice)
+    }
+
+    /// Returns the value at the given index, or `None` if the index is out of
+    /// bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index < self.len() {
+            Some(T::from_bits(read_bits(self.as_bytes(), index, BITS)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over the values of the archived bit vec.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, BITS> {
+        Iter {
+            bytes: self.as_bytes(),
+            index: 0,
+            len: self.len(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Resolves an archived bit vec from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: BitVecResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedBitVec { ptr, len: out_len, _phantom: _ } = out);
+        RelPtr::emplace(resolver.pos as usize, ptr);
+        out_len.write(ArchivedUsize::from_native(len as FixedUsize));
+    }
+
+    /// Serializes an archived bit vec from a given iterator of values.
+    pub fn serialize_from_iter<I, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<BitVecResolver, S::Error>
+    where
+        I: Iterator<Item = T>,
+        S: Fallible + Writer + ?Sized,
+        S::Error: Source,
+    {
+        let () = Self::CHECK_BITS;
+
+        let pos = serializer.pos();
+        let mut buffer = [0u8; 64];
+        let mut byte = 0;
+        let mut bit = 0;
+        for value in iter {
+            let value = value.to_bits();
+            if value.checked_shr(BITS).unwrap_or(0) != 0 {
+                fail!(BitPackedValueTooLargeError { bits: BITS });
+            }
+
+            let mut written = 0;
+            while written < BITS {
+                let take = (8 - bit).min(BITS - written);
+                let chunk = (value >> written) & ((1 << take) - 1);
+                buffer[byte] |= (chunk as u8) << bit;
+                written += take;
+                bit += take;
+                if bit == 8 {
+                    bit = 0;
+                    byte += 1;
+                    if byte == buffer.len() {
+                        serializer.write(&buffer)?;
+                        buffer = [0; 64];
+                        byte = 0;
+                    }
+                }
+            }
+        }
+        if bit != 0 {
+            byte += 1;
+        }
+        serializer.write(&buffer[..byte])?;
+
+        Ok(BitVecResolver {
+            pos: pos as FixedUsize,
+        })
+    }
+}
+
+impl<T, const BITS: u32> fmt::Debug for ArchivedBitVec<T, BITS>
+where
+    T: BitPackable + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: BitPackable + Eq, const BITS: u32> Eq for ArchivedBitVec<T, BITS> {}
+
+impl<T, const BITS: u32> PartialEq for ArchivedBitVec<T, BITS>
+where
+    T: BitPackable + PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<T, const BITS: u32> PartialEq<[T]> for ArchivedBitVec<T, BITS>
+where
+    T: BitPackable + PartialEq,
+{
+    fn eq(&self, other: &[T]) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+impl<T, const BITS: u32, const N: usize> PartialEq<[T; N]>
+    for ArchivedBitVec<T, BITS>
+where
+    T: BitPackable + PartialEq,
+{
+    fn eq(&self, other: &[T; N]) -> bool {
+        self.eq(other.as_slice())
+    }
+}
+
+impl<'a, T: BitPackable, const BITS: u32> IntoIterator
+    for &'a ArchivedBitVec<T, BITS>
+{
+    type Item = T;
+    type IntoIter = Iter<'a, T, BITS>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[inline]
+fn read_bits(bytes: &[u8], index: usize, bits: u32) -> u64 {
+    let start = index * bits as usize;
+    let mut result = 0;
+    let mut read = 0;
+    while read < bits {
+        let bit = start + read as usize;
+        let offset = (bit % 8) as u32;
+        let take = (8 - offset).min(bits - read);
+        let chunk = (bytes[bit / 8] >> offset) as u64 & ((1 << take) - 1);
+        result |= chunk << read;
+        read += take;
+    }
+    result
+}
+
+/// An iterator over the values of an [`ArchivedBitVec`].
+pub struct Iter<'a, T, const BITS: u32> {
+    bytes: &'a [u8],
+    index: usize,
+    len: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T, const BITS: u32> fmt::Debug for Iter<'_, T, BITS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("index", &self.index)
+            .field("len", &self.len)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: BitPackable, const BITS: u32> Iterator for Iter<'_, T, BITS> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index < self.len {
+            let bits = read_bits(self.bytes, self.index, BITS);
+            self.index += 1;
+            Some(T::from_bits(bits))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: BitPackable, const BITS: u32> DoubleEndedIterator
+    for Iter<'_, T, BITS>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index < self.len {
+            self.len -= 1;
+            Some(T::from_bits(read_bits(self.bytes, self.len, BITS)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: BitPackable, const BITS: u32> ExactSizeIterator for Iter<'_, T, BITS> {}
+
+impl<T: BitPackable, const BITS: u32> FusedIterator for Iter<'_, T, BITS> {}
+
+/// The resolver for [`ArchivedBitVec`].
+pub struct BitVecResolver {
+    pos: FixedUsize,
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt, ptr::slice_from_raw_parts};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        CheckBytes, Verify,
+    };
+    use rancor::fail;
+
+    use crate::{
+        validation::{ArchiveContext, ArchiveContextExt},
+        vec::bits::{byte_len, ArchivedBitVec},
+    };
+
+    #[derive(Debug)]
+    struct CheckBitVecError;
+
+    impl fmt::Display for CheckBitVecError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "bit vec length overflowed or tail bits were not zero")
+        }
+    }
+
+    impl Error for CheckBitVecError {}
+
+    unsafe impl<T, C, const BITS: u32> Verify<C> for ArchivedBitVec<T, BITS>
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let len = self.len.to_native() as usize;
+            let Some(bytes_len) = byte_len(len, BITS) else {
+                fail!(CheckBitVecError);
+            };
+
+            let ptr =
+                slice_from_raw_parts(self.ptr.as_ptr_wrapping(), bytes_len);
+            context.in_subtree(ptr, |context| unsafe {
+                <[u8]>::check_bytes(ptr, context)
+            })?;
+
+            let tail = (len * BITS as usize % 8) as u32;
+            if tail != 0 {
+                // SAFETY: `ptr` was just checked and is at least one byte long
+                // because `tail` is nonzero.
+                let last = unsafe { *ptr.cast::<u8>().add(bytes_len - 1) };
+                if last >> tail != 0 {
+                    fail!(CheckBitVecError);
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! An archived version of `Vec`.
 
+pub mod bits;
+pub mod small;
+
 use core::{
     borrow::Borrow,
     cmp, fmt, hash,
@@ -21,7 +24,8 @@ use crate::{
 ///
 /// This uses a [`RelPtr`] to a `[T]` under the hood. Unlike
 /// [`ArchivedString`](crate::string::ArchivedString), it does not have an
-/// inline representation.
+/// inline representation. See [`ArchivedSmallVec`](small::ArchivedSmallVec)
+/// for a vector that inlines short vectors.
 #[derive(Portable)]
 #[cfg_attr(
     feature = "bytecheck",
@@ -66,6 +70,17 @@ impl<T> ArchivedVec<T> {
         Seal::new(slice)
     }
 
+    /// Shortens the archived vec, keeping the first `len` elements.
+    ///
+    /// If `len` is greater than or equal to the current length of the archived
+    /// vec, this has no effect. The removed elements are left in place.
+    pub fn truncate_seal(this: Seal<'_, Self>, len: usize) {
+        if len < this.len() {
+            munge!(let Self { len: out_len, .. } = this);
+            *out_len.unseal() = ArchivedUsize::from_native(len as FixedUsize);
+        }
+    }
+
     /// Resolves an archived `Vec` from a given slice.
     pub fn resolve_from_slice<U: Archive<Archived = T>>(
         slice: &[U],
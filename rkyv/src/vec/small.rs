@@ -0,0 +1,321 @@
+//! An archived vector representation that supports inlining short vectors.
+
+use core::{
+    borrow::Borrow,
+    cmp, fmt, hash,
+    mem::ManuallyDrop,
+    ops::{Deref, Index},
+    ptr::addr_of,
+    slice::{self, SliceIndex},
+};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    primitive::{ArchivedUsize, FixedUsize},
+    seal::Seal,
+    ser::{Allocator, Writer},
+    util::InlineVec,
+    Archive, Place, Portable, RelPtr, Serialize, SerializeUnsized,
+};
+
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(C)]
+union SmallVecData<T, const N: usize> {
+    inline: ManuallyDrop<[T; N]>,
+    out_of_line: ManuallyDrop<RelPtr<T>>,
+}
+
+/// An archived vector which stores up to `N` elements inline.
+///
+/// Vectors with at most `N` elements are stored directly inside of the
+/// archived vector. Longer vectors spill out-of-line and are stored behind a
+/// [`RelPtr`] like [`ArchivedVec`](crate::vec::ArchivedVec). This can be
+/// selected for a field with the [`InlineSmall`](crate::with::InlineSmall)
+/// wrapper.
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedSmallVec<T, const N: usize> {
+    len: ArchivedUsize,
+    data: SmallVecData<T, N>,
+}
+
+impl<T, const N: usize> ArchivedSmallVec<T, N> {
+    /// The maximum number of elements that can be stored inline.
+    pub const INLINE_CAPACITY: usize = N;
+
+    /// Returns whether the elements of the archived vec are stored inline.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.len() <= N
+    }
+
+    /// Returns the number of elements in the archived vec.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the archived vec is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a pointer to the first element of the archived vec.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        if self.is_inline() {
+            addr_of!(self.data).cast()
+        } else {
+            // SAFETY: The archived vec is out-of-line, so `out_of_line` is the
+            // active field of `data`.
+            unsafe { self.data.out_of_line.as_ptr() }
+        }
+    }
+
+    /// Gets the elements of the archived vec as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: `as_ptr` always points to `len` initialized elements.
+        unsafe { slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Gets the elements of the archived vec as a sealed mutable slice.
+    pub fn as_slice_seal(this: Seal<'_, Self>) -> Seal<'_, [T]> {
+        let len = this.len();
+        let is_inline = this.is_inline();
+        munge!(let Self { data, .. } = this);
+        // SAFETY: The elements are not moved out of `data`, and the active
+        // field of the union is determined by `is_inline`.
+        let data = unsafe { Seal::unseal_unchecked(data) };
+        let ptr = if is_inline {
+            (data as *mut SmallVecData<T, N>).cast::<T>()
+        } else {
+            // SAFETY: The archived vec is out-of-line, so `out_of_line` is the
+            // active field of `data`.
+            let out_of_line = unsafe { &mut *data.out_of_line };
+            unsafe { RelPtr::as_mut_ptr(Seal::new(out_of_line)) }
+        };
+        // SAFETY: `ptr` points to `len` initialized elements.
+        Seal::new(unsafe { slice::from_raw_parts_mut(ptr, len) })
+    }
+
+    /// Resolves an archived small vec from a given slice.
+    pub fn resolve_from_slice<U: Archive<Archived = T>>(
+        slice: &[U],
+        resolver: SmallVecResolver<U::Resolver, N>,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedSmallVec { len, data } = out);
+        usize::resolve(&slice.len(), (), len);
+
+        match resolver.inner {
+            SmallVecResolverInner::Inline(mut resolvers) => {
+                // SAFETY: `SmallVecData` is `repr(C)`, so `inline` is located
+                // at the start of the union.
+                let inline = unsafe { data.cast_unchecked::<[T; N]>() };
+                for (i, (value, resolver)) in
+                    slice.iter().zip(resolvers.drain()).enumerate()
+                {
+                    // SAFETY: Inline resolvers are only created for slices
+                    // with at most `N` elements, so `i` is in-bounds.
+                    value.resolve(resolver, unsafe { inline.index(i) });
+                }
+            }
+            SmallVecResolverInner::OutOfLine(pos) => {
+                // SAFETY: `SmallVecData` is `repr(C)`, so `out_of_line` is
+                // located at the start of the union.
+                let ptr = unsafe { data.cast_unchecked::<RelPtr<T>>() };
+                RelPtr::emplace(pos as usize, ptr);
+            }
+        }
+    }
+
+    /// Serializes an archived small vec from a given slice.
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<SmallVecResolver<U::Resolver, N>, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        let inner = if slice.len() <= N {
+            let mut resolvers = InlineVec::new();
+            for value in slice {
+                resolvers.push(value.serialize(serializer)?);
+            }
+            SmallVecResolverInner::Inline(resolvers)
+        } else {
+            SmallVecResolverInner::OutOfLine(
+                slice.serialize_unsized(serializer)? as FixedUsize,
+            )
+        };
+
+        Ok(SmallVecResolver { inner })
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for ArchivedSmallVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> Borrow<[T]> for ArchivedSmallVec<T, N> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArchivedSmallVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Deref for ArchivedSmallVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: Eq, const N: usize> Eq for ArchivedSmallVec<T, N> {}
+
+impl<T: hash::Hash, const N: usize> hash::Hash for ArchivedSmallVec<T, N> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, const N: usize> Index<I>
+    for ArchivedSmallVec<T, N>
+{
+    type Output = <[T] as Index<I>>::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        self.as_slice().index(index)
+    }
+}
+
+impl<T: Ord, const N: usize> Ord for ArchivedSmallVec<T, N> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize, const M: usize>
+    PartialEq<ArchivedSmallVec<U, M>> for ArchivedSmallVec<T, N>
+{
+    fn eq(&self, other: &ArchivedSmallVec<U, M>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U]>
+    for ArchivedSmallVec<T, N>
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<ArchivedSmallVec<U, N>>
+    for [T]
+{
+    fn eq(&self, other: &ArchivedSmallVec<U, N>) -> bool {
+        self.eq(other.as_slice())
+    }
+}
+
+impl<T: PartialOrd, const N: usize> PartialOrd for ArchivedSmallVec<T, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: PartialOrd, const N: usize> PartialOrd<[T]> for ArchivedSmallVec<T, N> {
+    fn partial_cmp(&self, other: &[T]) -> Option<cmp::Ordering> {
+        self.as_slice().partial_cmp(other)
+    }
+}
+
+enum SmallVecResolverInner<R, const N: usize> {
+    Inline(InlineVec<R, N>),
+    OutOfLine(FixedUsize),
+}
+
+/// The resolver for [`ArchivedSmallVec`].
+pub struct SmallVecResolver<R, const N: usize> {
+    inner: SmallVecResolverInner<R, N>,
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::ptr::{addr_of, slice_from_raw_parts};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        CheckBytes,
+    };
+
+    use crate::{
+        primitive::ArchivedUsize,
+        validation::{ArchiveContext, ArchiveContextExt},
+        vec::small::ArchivedSmallVec,
+        RelPtr,
+    };
+
+    unsafe impl<T, C, const N: usize> CheckBytes<C> for ArchivedSmallVec<T, N>
+    where
+        T: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        unsafe fn check_bytes(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<(), C::Error> {
+            // SAFETY: The caller has guaranteed that `value` is aligned and
+            // points to enough bytes for `Self`.
+            let len = unsafe { addr_of!((*value).len) };
+            // SAFETY: `len` is aligned and points to enough bytes for an
+            // `ArchivedUsize`.
+            unsafe {
+                ArchivedUsize::check_bytes(len, context)?;
+            }
+            // SAFETY: `len` was just checked.
+            let len = unsafe { (*len).to_native() as usize };
+
+            // SAFETY: The caller has guaranteed that `value` is aligned and
+            // points to enough bytes for `Self`.
+            let data = unsafe { addr_of!((*value).data) };
+            if len <= N {
+                let ptr = slice_from_raw_parts(data.cast::<T>(), len);
+                // SAFETY: The inline elements are located at the start of
+                // `data`, which has room for `N` elements.
+                unsafe { <[T]>::check_bytes(ptr, context) }
+            } else {
+                let rel_ptr = data.cast::<RelPtr<T>>();
+                // SAFETY: The relative pointer is located at the start of
+                // `data`, which has room for a `RelPtr<T>`.
+                unsafe {
+                    RelPtr::check_bytes(rel_ptr, context)?;
+                }
+                // SAFETY: `rel_ptr` was just checked.
+                let rel_ptr = unsafe { &*rel_ptr };
+                let ptr = slice_from_raw_parts(rel_ptr.as_ptr_wrapping(), len);
+
+                context.in_subtree(ptr, |context| unsafe {
+                    <[T]>::check_bytes(ptr, context)
+                })
+            }
+        }
+    }
+}
@@ -374,6 +374,149 @@ pub struct InlineAsBox;
 #[derive(Debug)]
 pub struct AsString;
 
+/// A wrapper that archives a string with a length prefix of type `L`.
+///
+/// The archived string is an
+/// [`ArchivedPrefixedString`](crate::string::compact::ArchivedPrefixedString),
+/// which stores only a relative pointer inline. `L` may be `u8`, `u16`, or
+/// `u32`. Serialization fails if the string is too long for `L`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::AsPrefixedString, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsPrefixedString<u8>)]
+///     name: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsPrefixedString<L> {
+    _phantom: PhantomData<L>,
+}
+
+/// A wrapper that archives a string which can inline up to `N` bytes.
+///
+/// The archived string is an
+/// [`ArchivedInlineString`](crate::string::compact::ArchivedInlineString).
+/// `N` must be at least
+/// [`INLINE_CAPACITY`](crate::string::repr::INLINE_CAPACITY).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::AsInlineString, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsInlineString<24>)]
+///     name: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsInlineString<const N: usize>;
+
+/// A wrapper that archives a string as a relative pointer and a length.
+///
+/// The archived string is an
+/// [`ArchivedPlainString`](crate::string::compact::ArchivedPlainString), which
+/// never inlines the string.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::AsPlainString, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsPlainString)]
+///     name: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsPlainString;
+
+/// A wrapper that serializes a vector as an
+/// [`ArchivedSmallVec`](crate::vec::small::ArchivedSmallVec), which stores up
+/// to `N` elements inline and the rest out-of-line.
+///
+/// This can be used with `Vec`, and with `SmallVec`, `ArrayVec`, and `TinyVec`
+/// when the corresponding features are enabled.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::InlineSmall, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = InlineSmall<2>)]
+///     values: Vec<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct InlineSmall<const N: usize>;
+
+/// A wrapper that serializes a sequence of booleans or small unsigned integers
+/// as an [`ArchivedBitVec`](crate::vec::bits::ArchivedBitVec), which packs
+/// each value into `BITS` bits.
+///
+/// This can be used with `Vec`, `Box<[T]>`, and arrays of any
+/// [`BitPackable`](crate::vec::bits::BitPackable) type. Serialization fails if
+/// a value does not fit in `BITS` bits.
+///
+/// Bitflag sets can be packed by implementing `BitPackable` for the flags
+/// type in terms of its raw bits. rkyv does not provide an integration with the
+/// `bitflags` crate.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::BitPacked, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = BitPacked<1>)]
+///     flags: Vec<bool>,
+///     #[rkyv(with = BitPacked<1>)]
+///     features: [bool; 12],
+///     #[rkyv(with = BitPacked<3>)]
+///     levels: Vec<u8>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BitPacked<const BITS: u32>;
+
+/// A wrapper that archives a field with an alignment of at least `N` bytes.
+///
+/// The field is archived as an
+/// [`ArchivedAligned`](crate::aligned::ArchivedAligned), which dereferences to
+/// the archived field. `N` must be a power of two. To align the entire
+/// archived type instead, use `#[rkyv(align = N)]` on the type.
+///
+/// Archived values are only aligned relative to the start of the buffer they
+/// were serialized into. Use a buffer with at least the same alignment, such
+/// as an [`AlignedVec<N>`](crate::util::AlignedVec), to serialize values which
+/// use large alignments.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::Aligned, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = Aligned<64>)]
+///     counters: [u64; 8],
+/// }
+///
+/// assert_eq!(core::mem::align_of::<ArchivedExample>(), 64);
+/// ```
+#[derive(Debug)]
+pub struct Aligned<const N: usize>;
+
 /// A wrapper that locks a lock and serializes the value immutably.
 ///
 /// This wrapper can panic under very specific circumstances when:
@@ -444,6 +587,109 @@ pub struct AsOwned;
 #[derive(Debug)]
 pub struct AsVec;
 
+/// A wrapper that serializes maps and sets as static perfect hash tables.
+///
+/// This works with `HashMap`, `HashSet`, `BTreeMap`, and `BTreeSet`. The
+/// archived [perfect hash tables](crate::collections::perfect_hash) have no
+/// empty buckets, and each lookup takes one hash and one probe. Building the
+/// table makes serialization slower than the default hash map, so this is best
+/// suited to data which is written once and read many times.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rkyv::{with::AsPerfectHash, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsPerfectHash)]
+///     values: HashMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsPerfectHash;
+
+/// A wrapper that serializes vectors, maps, and sets as contiguous sorted
+/// arrays.
+///
+/// This works with `Vec`, `BTreeSet`, `BTreeMap`, and `HashMap`. Vectors and
+/// sets are archived as [`ArchivedSortedVec`], and maps are archived as
+/// [`ArchivedSortedMap`]. Unordered collections are sorted during
+/// serialization, so the ordering of the archived elements must match the
+/// ordering of the unarchived elements. Vectors deserialize in sorted order.
+///
+/// [`ArchivedSortedVec`]: crate::collections::sorted_vec::ArchivedSortedVec
+/// [`ArchivedSortedMap`]: crate::collections::sorted_vec::ArchivedSortedMap
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rkyv::{with::AsSortedVec, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsSortedVec)]
+///     values: HashMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsSortedVec;
+
+/// A wrapper that serializes maps with string keys as radix tries.
+///
+/// This works with `BTreeMap<String, V>` and `HashMap<String, V>`, which are
+/// archived as [`ArchivedTrieMap`]. Keys which share a prefix share storage,
+/// and the archived map supports prefix and range queries.
+///
+/// [`ArchivedTrieMap`]: crate::collections::trie::ArchivedTrieMap
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use rkyv::{with::AsTrie, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsTrie)]
+///     words: BTreeMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsTrie;
+
+/// A wrapper that archives hash maps and hash sets using the table hasher `H`.
+///
+/// Hash maps and hash sets are archived with [`FxHasher64`] by default. A keyed
+/// hasher like [`SipHasher13`] prevents attackers from choosing keys which
+/// degrade lookups.
+///
+/// [`FxHasher64`]: crate::hash::FxHasher64
+/// [`SipHasher13`]: crate::hash::SipHasher13
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rkyv::{hash::SipHasher13, with::WithHasher, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = WithHasher<SipHasher13>)]
+///     values: HashMap<String, u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct WithHasher<H> {
+    _phantom: PhantomData<H>,
+}
+
 /// A wrapper that niches some type combinations.
 ///
 /// A common type combination is `Option<Box<T>>`. By using a null pointer, the
@@ -662,6 +908,29 @@ pub struct Skip;
 #[derive(Debug)]
 pub struct Unshare;
 
+/// A wrapper that serializes equal values only once.
+///
+/// The field is archived as an [`ArchivedRc`](crate::rc::ArchivedRc) pointing
+/// to the archived value, so every equal field shares the same archived value.
+/// Values are compared with `Hash` and `Eq` by the serializer's
+/// [`Interning`](crate::ser::sharing::Interning) strategy. Only
+/// [`Dedup`](crate::ser::sharing::Dedup) deduplicates values; other strategies
+/// serialize each value separately.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::Intern, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = Intern)]
+///     section: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Intern;
+
 /// A no-op wrapper which uses the default impls for the type.
 ///
 /// This is most useful for wrappers like [`MapKV`] when you only want to apply
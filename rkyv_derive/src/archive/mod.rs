@@ -58,6 +58,13 @@ fn derive_archive_impl(
             fields,
         )?,
         Data::Enum(enm) => {
+            if let Some(ref align) = attributes.align {
+                return Err(Error::new_spanned(
+                    align,
+                    "`align = ...` may only be used on structs",
+                ));
+            }
+
             r#enum::impl_enum(&printing, &input.generics, attributes, enm)?
         }
         Data::Union(_) => {
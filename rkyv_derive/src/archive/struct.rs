@@ -193,12 +193,18 @@ fn generate_archived_type(
         Fields::Unit => quote! { #where_clause; },
     };
 
+    let repr = if let Some(align) = &attributes.align {
+        quote! { #[repr(C, align(#align))] }
+    } else {
+        quote! { #[repr(C)] }
+    };
+
     let doc_string = archived_doc(name);
     Ok(quote! {
         #[automatically_derived]
         #[doc = #doc_string]
         #(#[#archived_metas])*
-        #[repr(C)]
+        #repr
         #vis struct #archived_name #generics #body
     })
 }
@@ -358,7 +364,7 @@ fn generate_copy_optimization(
     attributes: &Attributes,
     fields: &Fields,
 ) -> Result<Option<TokenStream>, Error> {
-    if !generics.params.is_empty() {
+    if !generics.params.is_empty() || attributes.align.is_some() {
         return Ok(None);
     }
 
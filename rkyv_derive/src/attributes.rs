@@ -2,8 +2,8 @@ use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
 use syn::{
     meta::ParseNestedMeta, parenthesized, parse::Parse, parse_quote,
-    punctuated::Punctuated, DeriveInput, Error, Field, Fields, Ident, Meta,
-    Path, Token, Type, Variant, WherePredicate,
+    punctuated::Punctuated, DeriveInput, Error, Field, Fields, Ident, LitInt,
+    Meta, Path, Token, Type, Variant, WherePredicate,
 };
 
 fn try_set_attribute<T: ToTokens>(
@@ -35,6 +35,9 @@ pub struct Attributes {
     pub deserialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     pub bytecheck: Option<TokenStream>,
     pub crate_path: Option<Path>,
+    pub align: Option<LitInt>,
+    pub size_hint: Option<Path>,
+    pub profile: Option<Path>,
 }
 
 impl Attributes {
@@ -138,6 +141,20 @@ impl Attributes {
             self.metas
                 .extend(metas.parse_terminated(Meta::parse, Token![,])?);
             Ok(())
+        } else if meta.path.is_ident("align") {
+            let align = meta.value()?.parse::<LitInt>()?;
+            let value = align.base10_parse::<usize>()?;
+            if !value.is_power_of_two() {
+                return Err(Error::new_spanned(
+                    align,
+                    "`align` must be a power of two",
+                ));
+            }
+            try_set_attribute(&mut self.align, align, "align")
+        } else if meta.path.is_ident("size_hint") {
+            try_set_attribute(&mut self.size_hint, meta.path, "size_hint")
+        } else if meta.path.is_ident("profile") {
+            try_set_attribute(&mut self.profile, meta.path, "profile")
         } else if meta.path.is_ident("remote") {
             try_set_attribute(
                 &mut self.remote,
@@ -175,6 +192,14 @@ impl Attributes {
                 ));
             }
 
+            if let Some(ref align) = result.align {
+                return Err(Error::new_spanned(
+                    align,
+                    "`align = ...` may not be used with `as = ...` because no \
+                     type is generated",
+                ));
+            }
+
             if result.bytecheck.is_some() {
                 return Err(Error::new_spanned(
                     result.bytecheck.unwrap(),
@@ -184,6 +209,15 @@ impl Attributes {
             }
         }
 
+        if result.remote.is_some() {
+            if let Some(ref size_hint) = result.size_hint {
+                return Err(Error::new_spanned(
+                    size_hint,
+                    "`size_hint` may not be used with remote derive",
+                ));
+            }
+        }
+
         Ok(result)
     }
 
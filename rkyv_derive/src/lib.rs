@@ -66,6 +66,13 @@ pub fn derive_portable(
 ///   default, resolver types are named `the name of the type` + "Resolver".
 /// - `remote = ..`: Generate a remote derive for the annotated type instead of
 ///   a regular derive.
+/// - `align = ..`: Raises the alignment of the generated archived struct to the
+///   given power of two.
+/// - `size_hint`: Implements `SizeHint` for the type when deriving `Serialize`.
+///   Fields may not use `with = ..` wrappers.
+/// - `profile`: Reports each field to the writer while serializing it, so that
+///   profiling writers like `SizeProfiler` can attribute bytes to fields. Adds
+///   a `Writer` bound to the generated `Serialize` impl.
 ///
 /// ## Fields only
 ///
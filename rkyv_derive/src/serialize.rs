@@ -2,7 +2,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::{
     parse_quote, punctuated::Punctuated, spanned::Spanned, Data, DeriveInput,
-    Error, Fields, Generics, Ident, Index, Path, WhereClause,
+    Error, Field, Fields, Generics, Ident, Index, Path, WhereClause,
 };
 
 use crate::{
@@ -21,6 +21,12 @@ fn derive_serialize_impl(
 ) -> Result<TokenStream, Error> {
     let rkyv_path = attributes.crate_path();
 
+    let size_hint_impl = if attributes.size_hint.is_some() {
+        generate_size_hint_impl(&input, attributes, &rkyv_path)?
+    } else {
+        TokenStream::new()
+    };
+
     let where_clause = input.generics.make_where_clause();
     if let Some(ref bounds) = attributes.archive_bounds {
         for bound in bounds {
@@ -113,10 +119,142 @@ fn derive_serialize_impl(
                     #body
                 }
             }
+
+            #size_hint_impl
         })
     }
 }
 
+fn generate_size_hint_impl(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+
+    let mut field_size_hint = |field: &Field, access: TokenStream| {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        if let Some(with) = field_attrs.with {
+            return Err(Error::new_spanned(
+                with,
+                "`size_hint` cannot be derived for fields with `with = ...`; \
+                 implement `SizeHint` manually instead",
+            ));
+        }
+
+        let ty = &field.ty;
+        if field_attrs.omit_bounds.is_none() {
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: #rkyv_path::traits::SizeHint });
+        }
+
+        Ok(quote! {
+            + <#ty as #rkyv_path::traits::SizeHint>::size_hint(#access)
+        })
+    };
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let sizes = data
+                .fields
+                .iter()
+                .enumerate()
+                .map(|(i, field)| {
+                    let access = match field.ident {
+                        Some(ref name) => quote! { &self.#name },
+                        None => {
+                            let index = Index::from(i);
+                            quote! { &self.#index }
+                        }
+                    };
+                    field_size_hint(field, access)
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            quote! { 0 #(#sizes)* }
+        }
+        Data::Enum(ref data) => {
+            let name = &input.ident;
+            let arms = data
+                .variants
+                .iter()
+                .map(|v| {
+                    let variant = &v.ident;
+                    match v.fields {
+                        Fields::Named(ref fields) => {
+                            let bindings =
+                                fields.named.iter().map(|f| &f.ident);
+                            let sizes = fields
+                                .named
+                                .iter()
+                                .map(|field| {
+                                    let binding = &field.ident;
+                                    field_size_hint(field, quote! { #binding })
+                                })
+                                .collect::<Result<Vec<_>, Error>>()?;
+                            Ok(quote! {
+                                #name::#variant { #(ref #bindings,)* .. } =>
+                                    0 #(#sizes)*
+                            })
+                        }
+                        Fields::Unnamed(ref fields) => {
+                            let bindings = (0..fields.unnamed.len())
+                                .map(|i| {
+                                    Ident::new(
+                                        &format!("_{}", i),
+                                        Span::call_site(),
+                                    )
+                                })
+                                .collect::<Vec<_>>();
+                            let sizes = fields
+                                .unnamed
+                                .iter()
+                                .zip(bindings.iter())
+                                .map(|(field, binding)| {
+                                    field_size_hint(field, quote! { #binding })
+                                })
+                                .collect::<Result<Vec<_>, Error>>()?;
+                            Ok(quote! {
+                                #name::#variant(#(ref #bindings,)* ..) =>
+                                    0 #(#sizes)*
+                            })
+                        }
+                        Fields::Unit => Ok(quote! { #name::#variant => 0 }),
+                    }
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            quote! {
+                match *self {
+                    #(#arms,)*
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "SizeHint cannot be derived for unions",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::traits::SizeHint for #name #ty_generics
+        #where_clause
+        {
+            fn size_hint(&self) -> usize {
+                #body
+            }
+        }
+    })
+}
+
 fn generate_serialize_body(
     input: &DeriveInput,
     attributes: &Attributes,
@@ -126,6 +264,36 @@ fn generate_serialize_body(
     name: Path,
 ) -> Result<TokenStream, Error> {
     let this = Ident::new("__this", Span::call_site());
+    let type_name = strip_raw(&input.ident);
+    if attributes.profile.is_some() {
+        serialize_where
+            .predicates
+            .push(parse_quote! { __S: #rkyv_path::ser::Writer });
+    }
+    let profile_field =
+        |label: String, field: &Field, serialize: TokenStream| {
+            if attributes.profile.is_none() {
+                return quote! { #serialize? };
+            }
+
+            // The scope is exited before propagating errors so that a failed
+            // field doesn't leave its scope open.
+            let ty = &field.ty;
+            quote! {
+                {
+                    <__S as #rkyv_path::ser::Writer>::enter_scope(
+                        serializer,
+                        #rkyv_path::ser::writer::Scope::Field {
+                            name: #label,
+                            ty: ::core::any::type_name::<#ty>(),
+                        },
+                    );
+                    let result = #serialize;
+                    <__S as #rkyv_path::ser::Writer>::exit_scope(serializer);
+                    result?
+                }
+            }
+        };
     let body = match input.data {
         Data::Struct(ref data) => match data.fields {
             Fields::Named(ref fields) => {
@@ -144,9 +312,16 @@ fn generate_serialize_body(
                         let access_field =
                             field_attrs.access_field(&this, name);
                         let serialize = field_attrs.serialize(rkyv_path, field);
-                        Ok(quote! {
-                            #name: #serialize(#access_field, serializer)?
-                        })
+                        let value = profile_field(
+                            format!(
+                                "{}.{}",
+                                type_name,
+                                strip_raw(name.as_ref().unwrap()),
+                            ),
+                            field,
+                            quote! { #serialize(#access_field, serializer) },
+                        );
+                        Ok(quote! { #name: #value })
                     })
                     .collect::<Result<Vec<_>, Error>>()?;
 
@@ -169,7 +344,11 @@ fn generate_serialize_body(
                         let access_field =
                             field_attrs.access_field(&this, &index);
                         let serialize = field_attrs.serialize(rkyv_path, field);
-                        Ok(quote! { #serialize(#access_field, serializer)? })
+                        Ok(profile_field(
+                            format!("{}.{}", type_name, i),
+                            field,
+                            quote! { #serialize(#access_field, serializer) },
+                        ))
                     })
                     .collect::<Result<Vec<_>, Error>>()?;
 
@@ -213,9 +392,19 @@ fn generate_serialize_body(
                                     let name = &field.ident;
                                     let serialize =
                                         field_attrs.serialize(rkyv_path, field);
-                                    Ok(quote! {
-                                        #name: #serialize(#name, serializer)?
-                                    })
+                                    let value = profile_field(
+                                        format!(
+                                            "{}::{}.{}",
+                                            type_name,
+                                            strip_raw(variant),
+                                            strip_raw(name.as_ref().unwrap()),
+                                        ),
+                                        field,
+                                        quote! {
+                                            #serialize(#name, serializer)
+                                        },
+                                    );
+                                    Ok(quote! { #name: #value })
                                 })
                                 .collect::<Result<Vec<_>, Error>>()?;
                             Ok(quote! {
@@ -254,9 +443,18 @@ fn generate_serialize_body(
                                     );
                                     let serialize =
                                         field_attrs.serialize(rkyv_path, field);
-                                    Ok(quote! {
-                                        #serialize(#binding, serializer)?
-                                    })
+                                    Ok(profile_field(
+                                        format!(
+                                            "{}::{}.{}",
+                                            type_name,
+                                            strip_raw(variant),
+                                            i,
+                                        ),
+                                        field,
+                                        quote! {
+                                            #serialize(#binding, serializer)
+                                        },
+                                    ))
                                 })
                                 .collect::<Result<Vec<_>, Error>>()?;
                             Ok(quote! {